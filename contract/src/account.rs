@@ -0,0 +1,114 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::account::{AccountAction, AccountDiscountPhase, AccountSummary};
+use aurora_launchpad_types::config::LaunchpadStatus;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near};
+
+use crate::discount::DiscountPhases;
+use crate::withdraw::WithdrawIntents;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Returns the state of the participation of the account in the launchpad: the deposit,
+    /// allocation, claims, the participation in the discount phases and the actions which
+    /// the account could make at the moment.
+    pub fn get_account_summary(&self, account: IntentsAccount) -> AccountSummary {
+        let investment = self.investments.get(&account).copied().unwrap_or_default();
+        let allocation = self.get_user_allocation(&account);
+        let available_for_claim = self.get_available_for_claim(&account);
+        let remaining_vesting = self.get_remaining_vesting(&account);
+        let is_withdraw_in_progress = self.locked_withdraw.contains(&account);
+        let status = self.get_status();
+        let is_claim_expected = matches!(
+            status,
+            LaunchpadStatus::NotStarted
+                | LaunchpadStatus::Ongoing
+                | LaunchpadStatus::PreTGE
+                | LaunchpadStatus::Success
+        );
+        let next_unlock = if allocation.0 > 0 && is_claim_expected {
+            self.next_unlock(env::block_timestamp())
+        } else {
+            None
+        };
+
+        AccountSummary {
+            deposit: U128(investment.amount),
+            weight: U128(investment.weight),
+            allocation,
+            claimed: U128(investment.claimed),
+            available_for_claim,
+            remaining_vesting,
+            next_unlock,
+            is_withdraw_in_progress,
+            discount_phases: self.account_discount_phases(&account),
+            allowed_actions: self.allowed_actions(
+                &status,
+                investment.amount,
+                available_for_claim.0,
+                is_withdraw_in_progress,
+            ),
+        }
+    }
+
+    fn account_discount_phases(&self, account: &IntentsAccount) -> Vec<AccountDiscountPhase> {
+        let (Some(discounts), Some(state)) = (&self.config.discounts, &self.discount_state) else {
+            return vec![];
+        };
+        let timestamp = env::block_timestamp();
+
+        discounts
+            .phases
+            .iter()
+            .map(|phase| AccountDiscountPhase {
+                id: phase.id,
+                sale_tokens: U128(state.account_sale_tokens(phase.id, account).unwrap_or(0)),
+                is_active: phase.start_time <= timestamp && timestamp < phase.end_time,
+                is_allowed: state.is_account_allowed(phase.id, account),
+            })
+            .collect()
+    }
+
+    fn allowed_actions(
+        &self,
+        status: &LaunchpadStatus,
+        deposit: u128,
+        available_for_claim: u128,
+        is_withdraw_in_progress: bool,
+    ) -> Vec<AccountAction> {
+        let mut actions = Vec::new();
+
+        if is_withdraw_in_progress {
+            return actions;
+        }
+
+        if *status == LaunchpadStatus::Ongoing {
+            actions.push(AccountAction::Deposit);
+        }
+
+        if deposit > 0 {
+            if self.is_withdrawal_allowed(WithdrawIntents::NotPresent) {
+                actions.push(AccountAction::Withdraw);
+            } else if self.is_withdrawal_allowed(WithdrawIntents::Present { valid: true }) {
+                actions.push(AccountAction::WithdrawWithIntents);
+            }
+        }
+
+        if *status == LaunchpadStatus::Success && available_for_claim > 0 {
+            actions.push(AccountAction::Claim);
+        }
+
+        actions
+    }
+
+    /// Returns the time after the given one when the next sale tokens are unlocked.
+    fn next_unlock(&self, timestamp: u64) -> Option<u64> {
+        let vesting_start = self.config.tge.unwrap_or(self.config.end_date);
+
+        self.config.vesting_schedule.map_or_else(
+            || (timestamp < vesting_start).then_some(vesting_start),
+            |vesting| vesting.next_unlock(vesting_start, timestamp),
+        )
+    }
+}
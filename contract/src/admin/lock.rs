@@ -1,7 +1,9 @@
 use aurora_launchpad_types::config::LaunchpadStatus;
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{near, require};
+use near_sdk::near;
 
+use crate::error::require;
 use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
 
 #[near]
@@ -10,12 +12,12 @@ impl AuroraLaunchpadContract {
     #[access_control_any(roles(Role::Admin))]
     pub fn lock(&mut self) {
         let status = self.get_status();
-        require!(
+        require(
             matches!(
                 status,
                 LaunchpadStatus::NotStarted | LaunchpadStatus::Ongoing | LaunchpadStatus::PreTGE
             ),
-            "The contract can only be locked when status is NotStarted, Ongoing, or PreTGE"
+            LaunchpadError::LockNotAllowed,
         );
 
         near_sdk::log!("The contract is locked");
@@ -26,9 +28,9 @@ impl AuroraLaunchpadContract {
     /// Unsets the `Locked` status from the contract.
     #[access_control_any(roles(Role::Admin))]
     pub fn unlock(&mut self) {
-        require!(
+        require(
             self.get_status() == LaunchpadStatus::Locked,
-            "The contract is not locked"
+            LaunchpadError::NotLocked,
         );
 
         near_sdk::log!("The contract is unlocked");
@@ -1,7 +1,9 @@
 use aurora_launchpad_types::config::LaunchpadStatus;
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::{AccessControllable, access_control_any};
-use near_sdk::{Promise, PublicKey, assert_one_yocto, env, near, require};
+use near_sdk::{Promise, PublicKey, assert_one_yocto, env, near};
 
+use crate::error::{PanicWithCode, require};
 use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
 
 mod lock;
@@ -23,25 +25,29 @@ impl AuroraLaunchpadContract {
     pub fn update_tge(&mut self, tge: chrono::DateTime<chrono::Utc>) {
         assert_one_yocto();
         let status = self.get_status();
-        // We can't update TGE if the contract is in the Success or Failed state.
-        require!(
-            !matches!(status, LaunchpadStatus::Success | LaunchpadStatus::Failed),
-            "Wrong status of the contract for the TGE update"
+        // We can't update TGE if the contract is in the Success, Failed or Archived state.
+        require(
+            !matches!(
+                status,
+                LaunchpadStatus::Success | LaunchpadStatus::Failed | LaunchpadStatus::Archived
+            ),
+            LaunchpadError::TgeUpdateNotAllowed,
         );
 
         let tge_timestamp_nanos = tge.timestamp_nanos_opt().map_or_else(
-            || env::panic_str("Provided TGE is out of range"),
+            || LaunchpadError::InvalidTge.panic_with("Provided TGE is out of range"),
             |ts| {
                 u64::try_from(ts).unwrap_or_else(|_| {
-                    env::panic_str("Negative TGE timestamp value is not allowed")
+                    LaunchpadError::InvalidTge
+                        .panic_with("Negative TGE timestamp value is not allowed")
                 })
             },
         );
 
-        require!(
+        require(
             tge_timestamp_nanos > self.config.end_date
                 && tge_timestamp_nanos > env::block_timestamp(),
-            "TGE must be after the end of the sale and in the future"
+            LaunchpadError::InvalidTge,
         );
 
         near_sdk::log!("Updating TGE to {tge}");
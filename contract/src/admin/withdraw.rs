@@ -1,13 +1,17 @@
 use aurora_launchpad_types::admin_withdraw::{AdminWithdrawDirection, WithdrawalToken};
-use aurora_launchpad_types::config::{DepositToken, Mechanics, TokenId};
+use aurora_launchpad_types::config::{DepositToken, Mechanics, SaleToken, TokenId};
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::{AccessControllable, access_control_any};
 use near_sdk::json_types::U128;
-use near_sdk::{AccountId, Gas, Promise, assert_one_yocto, env, near, require};
+use near_sdk::{AccountId, Gas, NearToken, Promise, PromiseOrValue, assert_one_yocto, env, near};
 
-use crate::traits::{MAX_FT_RESULT_LENGTH, ext_ft, ext_mt};
+use crate::error::{PanicWithCode, require, require_with};
+use crate::registration::{Registration, add_ft_transfer};
+use crate::sale_token::{read_transfer_call_result, transferable_token};
+use crate::traits::{MAX_MT_RESULT_LENGTH, ext_ft, ext_mt};
 use crate::{
-    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER,
-    GAS_FOR_FT_TRANSFER_CALL, GAS_FOR_MT_TRANSFER_CALL, ONE_YOCTO, Role,
+    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER_CALL,
+    GAS_FOR_MT_TRANSFER_CALL, ONE_YOCTO, Role,
 };
 
 const GAS_FOR_FT_BALANCE_OF: Gas = Gas::from_ggas(500);
@@ -15,7 +19,21 @@ const GAS_FOR_MT_BALANCE_OF: Gas = Gas::from_tgas(1);
 const GAS_FOR_MT_TRANSFER: Gas = Gas::from_tgas(5);
 const GAS_WITHDRAW_NEP141_CALLBACK: Gas = Gas::from_tgas(50);
 const GAS_WITHDRAW_NEP245_CALLBACK: Gas = Gas::from_tgas(60);
-const GAS_FINISH_UNSOLD_WITHDRAWAL: Gas = Gas::from_tgas(2);
+const GAS_CONTINUE_NEP141_WITHDRAWAL: Gas = Gas::from_tgas(15);
+const GAS_FINISH_ADMIN_WITHDRAWAL: Gas = Gas::from_tgas(2);
+
+/// The kind of the admin withdrawal, which defines how the withdrawn amount is accounted once
+/// the transfer is finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub enum WithdrawalKind {
+    /// Tokens which aren't accounted, e.g. the sale tokens after failing.
+    Tokens,
+    /// Unsold sale tokens after success.
+    UnsoldTokens,
+    /// Storage fees collected in the deposit tokens.
+    StorageFees,
+}
 
 #[near]
 impl AuroraLaunchpadContract {
@@ -32,62 +50,129 @@ impl AuroraLaunchpadContract {
 
         match token {
             WithdrawalToken::Deposit => {
-                require!(
-                    self.is_success(),
-                    "Deposited tokens could be withdrawn after success only"
+                require_with(
+                    self.is_success() || self.is_archived(),
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Deposited tokens could be withdrawn after success only",
                 );
 
-                require!(
-                    self.is_deposits_distributed(),
-                    "Deposits distribution should be completed first"
+                require_with(
+                    self.is_archived() || self.is_deposits_distributed(),
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Deposits distribution should be completed first",
                 );
 
-                match &self.config.deposit_token {
-                    DepositToken::Nep141(token_account_id) => {
-                        self.withdraw_nep141_tokens(token_account_id, direction, amount, false)
-                    }
-                    DepositToken::Nep245((token_account_id, token_id)) => {
-                        self.withdraw_nep245_tokens(token_account_id, token_id, direction, amount)
-                    }
-                }
+                require_with(
+                    self.is_archived() || self.is_liquidity_seeded(),
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Liquidity seeding should be completed first",
+                );
+
+                self.withdraw_deposit_tokens(direction, amount, WithdrawalKind::Tokens)
+            }
+            // The storage fees are withdrawn after failing or archiving only, since they are
+            // withdrawn together with the deposited tokens after success.
+            WithdrawalToken::StorageFees => {
+                require_with(
+                    self.is_failed() || self.is_archived(),
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Storage fees could be withdrawn after failing or archiving only",
+                );
+
+                let amount = self.take_storage_fees(amount);
+
+                self.withdraw_deposit_tokens(direction, Some(amount), WithdrawalKind::StorageFees)
             }
             WithdrawalToken::Sale => {
                 let unsold_amount = self.unsold_amount_of_tokens();
-                require!(
+                require_with(
                     self.is_failed()
                         || self.is_locked()
+                        || self.is_archived()
                         || (self.is_success() && unsold_amount > 0),
-                    "Sale tokens could be withdrawn after failing, in locked mode, or if there are unsold tokens"
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Sale tokens could be withdrawn after failing, in locked mode, or if there are unsold tokens",
                 );
 
-                let (amount, is_unsold) = if self.is_success() {
-                    require!(
+                let (amount, kind) = if self.is_success() {
+                    require(
                         !self.withdrawn_unsold_tokens.is_ongoing,
-                        "Withdrawal is already ongoing"
+                        LaunchpadError::AdminWithdrawOngoing,
                     );
 
                     self.withdrawn_unsold_tokens.is_ongoing = true;
 
                     (
                         Some(match amount {
-                            Some(amount) if amount.0 > unsold_amount => env::panic_str(
-                                "The amount is greater than the available number of unsold tokens",
-                            ),
+                            Some(amount) if amount.0 > unsold_amount => {
+                                LaunchpadError::UnsoldAmountExceeded.panic()
+                            }
                             Some(amount) => amount,
                             None => unsold_amount.into(),
                         }),
-                        true,
+                        WithdrawalKind::UnsoldTokens,
                     )
                 } else {
-                    (amount, false)
+                    (amount, WithdrawalKind::Tokens)
+                };
+
+                match &self.config.sale_token {
+                    SaleToken::Nep141(token_account_id) => {
+                        self.withdraw_nep141_tokens(token_account_id, direction, amount, kind)
+                    }
+                    SaleToken::Nep245((token_account_id, token_id)) => self.withdraw_nep245_tokens(
+                        token_account_id,
+                        token_id,
+                        direction,
+                        amount,
+                        kind,
+                    ),
+                }
+            }
+            // The unsold bundled tokens are withdrawn after archiving, when all participants
+            // have claimed their allocations.
+            WithdrawalToken::Bundled(index) => {
+                require_with(
+                    self.is_failed() || self.is_locked() || self.is_archived(),
+                    LaunchpadError::AdminWithdrawNotAllowed,
+                    "Bundled sale tokens could be withdrawn after failing, in locked mode, or after archiving",
+                );
+
+                let Some(bundled) = self.config.bundled_sale_tokens.get(usize::from(index)) else {
+                    LaunchpadError::BundledTokenNotFound.panic();
                 };
 
-                self.withdraw_nep141_tokens(
-                    &self.config.sale_token_account_id,
-                    direction,
-                    amount,
-                    is_unsold,
-                )
+                match &bundled.token {
+                    SaleToken::Nep141(token_account_id) => self.withdraw_nep141_tokens(
+                        token_account_id,
+                        direction,
+                        amount,
+                        WithdrawalKind::Tokens,
+                    ),
+                    SaleToken::Nep245((token_account_id, token_id)) => self.withdraw_nep245_tokens(
+                        token_account_id,
+                        token_id,
+                        direction,
+                        amount,
+                        WithdrawalKind::Tokens,
+                    ),
+                }
+            }
+        }
+    }
+
+    fn withdraw_deposit_tokens(
+        &self,
+        direction: AdminWithdrawDirection,
+        amount: Option<U128>,
+        kind: WithdrawalKind,
+    ) -> Promise {
+        match &self.config.deposit_token {
+            DepositToken::Nep141(token_account_id) => {
+                self.withdraw_nep141_tokens(token_account_id, direction, amount, kind)
+            }
+            DepositToken::Nep245((token_account_id, token_id)) => {
+                self.withdraw_nep245_tokens(token_account_id, token_id, direction, amount, kind)
             }
         }
     }
@@ -97,7 +182,7 @@ impl AuroraLaunchpadContract {
         token_account_id: &AccountId,
         direction: AdminWithdrawDirection,
         amount: Option<U128>,
-        is_unsold: bool,
+        kind: WithdrawalKind,
     ) -> Promise {
         match amount {
             None => ext_ft::ext(token_account_id.clone())
@@ -106,10 +191,10 @@ impl AuroraLaunchpadContract {
                 .then(
                     Self::ext(env::current_account_id())
                         .with_static_gas(GAS_WITHDRAW_NEP141_CALLBACK)
-                        .withdraw_nep141_tokens_callback(token_account_id, direction, is_unsold),
+                        .withdraw_nep141_tokens_callback(token_account_id, direction, kind),
                 ),
             Some(amount) => {
-                self.do_withdraw_nep141_tokens(token_account_id, direction, amount, is_unsold)
+                self.do_withdraw_nep141_tokens(token_account_id, direction, amount, kind)
             }
         }
     }
@@ -120,6 +205,7 @@ impl AuroraLaunchpadContract {
         token_id: &TokenId,
         direction: AdminWithdrawDirection,
         amount: Option<U128>,
+        kind: WithdrawalKind,
     ) -> Promise {
         match amount {
             None => ext_mt::ext(token_account_id.clone())
@@ -128,10 +214,15 @@ impl AuroraLaunchpadContract {
                 .then(
                     Self::ext(env::current_account_id())
                         .with_static_gas(GAS_WITHDRAW_NEP245_CALLBACK)
-                        .withdraw_nep245_tokens_callback(token_account_id, token_id, direction),
+                        .withdraw_nep245_tokens_callback(
+                            token_account_id,
+                            token_id,
+                            direction,
+                            kind,
+                        ),
                 ),
             Some(amount) => {
-                self.do_withdraw_nep245_tokens(token_account_id, token_id, direction, amount)
+                self.do_withdraw_nep245_tokens(token_account_id, token_id, direction, amount, kind)
             }
         }
     }
@@ -141,10 +232,10 @@ impl AuroraLaunchpadContract {
         &mut self,
         token_account_id: &AccountId,
         direction: AdminWithdrawDirection,
-        is_unsold: bool,
+        kind: WithdrawalKind,
         #[callback_unwrap] balance: U128,
     ) -> Promise {
-        self.do_withdraw_nep141_tokens(token_account_id, direction, balance, is_unsold)
+        self.do_withdraw_nep141_tokens(token_account_id, direction, balance, kind)
     }
 
     #[private]
@@ -153,42 +244,83 @@ impl AuroraLaunchpadContract {
         token_account_id: &AccountId,
         token_id: &TokenId,
         direction: AdminWithdrawDirection,
+        kind: WithdrawalKind,
         #[callback_unwrap] balance: U128,
     ) -> Promise {
-        self.do_withdraw_nep245_tokens(token_account_id, token_id, direction, balance)
+        self.do_withdraw_nep245_tokens(token_account_id, token_id, direction, balance, kind)
     }
 
+    /// Finishes the admin withdrawal: accounts the withdrawn unsold sale tokens, returns
+    /// the storage fees which weren't withdrawn, and returns the deposit for the receiver
+    /// registration to the budget if the transfer failed.
     #[private]
-    pub fn finish_unsold_withdrawal(&mut self, amount: U128, is_call: bool) {
-        require!(
+    pub fn finish_admin_withdrawal(
+        &mut self,
+        amount: U128,
+        kind: WithdrawalKind,
+        is_call: bool,
+        storage_deposit: Option<NearToken>,
+    ) {
+        require_with(
             env::promise_results_count() == 1,
-            "Only one promise result is expected"
+            LaunchpadError::PromiseResultsCount,
+            "Only one promise result is expected",
         );
 
-        match env::promise_result_checked(0, MAX_FT_RESULT_LENGTH) {
-            Ok(bytes) => {
-                let withdrawn_amount = if is_call {
-                    near_sdk::serde_json::from_slice(&bytes).unwrap_or_default()
-                } else {
-                    amount
-                };
+        let result = env::promise_result_checked(0, MAX_MT_RESULT_LENGTH);
+
+        if result.is_err() {
+            self.restore_storage_deposit_budget(storage_deposit);
+        }
 
-                self.withdrawn_unsold_tokens.amount = self
-                    .withdrawn_unsold_tokens
-                    .amount
-                    .saturating_add(withdrawn_amount.0);
+        match kind {
+            WithdrawalKind::Tokens => {}
+            WithdrawalKind::UnsoldTokens => {
+                match result {
+                    Ok(_) => {
+                        let withdrawn_amount = if is_call {
+                            U128(
+                                read_transfer_call_result(&self.config.sale_token, 0)
+                                    .unwrap_or_default(),
+                            )
+                        } else {
+                            amount
+                        };
 
-                near_sdk::log!(
-                    "{} unsold sale tokens were withdrawn successfully",
-                    withdrawn_amount.0
-                );
+                        self.withdrawn_unsold_tokens.amount = self
+                            .withdrawn_unsold_tokens
+                            .amount
+                            .saturating_add(withdrawn_amount.0);
+
+                        near_sdk::log!(
+                            "{} unsold sale tokens were withdrawn successfully",
+                            withdrawn_amount.0
+                        );
+                    }
+                    Err(e) => {
+                        near_sdk::log!("Withdrawal of unsold sale tokens failed: {e}");
+                    }
+                }
+
+                self.withdrawn_unsold_tokens.is_ongoing = false;
             }
-            Err(e) => {
-                near_sdk::log!("Withdrawal of unsold sale tokens failed: {e}");
+            WithdrawalKind::StorageFees => {
+                let withdrawn_amount = match result {
+                    Ok(_) if is_call => read_transfer_call_result(
+                        &transferable_token(&self.config.deposit_token),
+                        0,
+                    )
+                    .unwrap_or_default(),
+                    Ok(_) => amount.0,
+                    Err(e) => {
+                        near_sdk::log!("Withdrawal of storage fees failed: {e}");
+                        0
+                    }
+                };
+
+                self.return_storage_fees(amount.0.saturating_sub(withdrawn_amount));
             }
         }
-
-        self.withdrawn_unsold_tokens.is_ongoing = false;
     }
 
     fn do_withdraw_nep141_tokens(
@@ -196,17 +328,18 @@ impl AuroraLaunchpadContract {
         token_account_id: &AccountId,
         direction: AdminWithdrawDirection,
         amount: U128,
-        is_unsold: bool,
+        kind: WithdrawalKind,
     ) -> Promise {
-        let (root, is_call) = match direction {
-            AdminWithdrawDirection::Near(receiver_id) => (
-                ext_ft::ext(token_account_id.clone())
-                    .with_attached_deposit(ONE_YOCTO)
-                    .with_static_gas(GAS_FOR_FT_TRANSFER)
-                    .ft_transfer(receiver_id, amount, None),
-                false,
-            ),
-            AdminWithdrawDirection::Intents(intents_account) => (
+        let root = match direction {
+            // The receiver is registered in the token before the transfer if needed.
+            AdminWithdrawDirection::Near(receiver_id) => {
+                return Self::check_registration(token_account_id, &receiver_id).then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_CONTINUE_NEP141_WITHDRAWAL)
+                        .continue_nep141_withdrawal(token_account_id, &receiver_id, amount, kind),
+                );
+            }
+            AdminWithdrawDirection::Intents(intents_account) => {
                 ext_ft::ext(token_account_id.clone())
                     .with_attached_deposit(ONE_YOCTO)
                     .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
@@ -215,35 +348,81 @@ impl AuroraLaunchpadContract {
                         amount,
                         intents_account.to_string(),
                         None,
-                    ),
-                true,
-            ),
+                    )
+            }
         };
 
-        if is_unsold {
+        Self::finish_withdrawal(root, amount, kind, true, None)
+    }
+
+    fn finish_withdrawal(
+        root: Promise,
+        amount: U128,
+        kind: WithdrawalKind,
+        is_call: bool,
+        storage_deposit: Option<NearToken>,
+    ) -> Promise {
+        if kind != WithdrawalKind::Tokens || storage_deposit.is_some() {
             root.then(
                 Self::ext(env::current_account_id())
-                    .with_static_gas(GAS_FINISH_UNSOLD_WITHDRAWAL)
-                    .finish_unsold_withdrawal(amount, is_call),
+                    .with_static_gas(GAS_FINISH_ADMIN_WITHDRAWAL)
+                    .finish_admin_withdrawal(amount, kind, is_call, storage_deposit),
             )
         } else {
             root
         }
     }
 
+    /// Transfers the NEP-141 tokens to the NEAR receiver after resolving its registration in
+    /// the token. The withdrawal is cancelled if the receiver couldn't be registered.
+    #[private]
+    pub fn continue_nep141_withdrawal(
+        &mut self,
+        token_account_id: &AccountId,
+        receiver_id: &AccountId,
+        amount: U128,
+        kind: WithdrawalKind,
+    ) -> PromiseOrValue<()> {
+        let registration = self.resolve_registration(receiver_id, 0, 0);
+
+        if matches!(registration, Registration::Unfunded) {
+            match kind {
+                WithdrawalKind::Tokens => {}
+                WithdrawalKind::UnsoldTokens => self.withdrawn_unsold_tokens.is_ongoing = false,
+                WithdrawalKind::StorageFees => self.return_storage_fees(amount.0),
+            }
+
+            return PromiseOrValue::Value(());
+        }
+
+        let storage_deposit = registration.storage_deposit();
+        let root = add_ft_transfer(
+            Promise::new(token_account_id.clone()),
+            receiver_id,
+            amount,
+            storage_deposit,
+        );
+
+        Self::finish_withdrawal(root, amount, kind, false, storage_deposit).into()
+    }
+
     fn do_withdraw_nep245_tokens(
         &self,
         token_account_id: &AccountId,
         token_id: &TokenId,
         direction: AdminWithdrawDirection,
         amount: U128,
+        kind: WithdrawalKind,
     ) -> Promise {
-        match direction {
-            AdminWithdrawDirection::Near(receiver_id) => ext_mt::ext(token_account_id.clone())
-                .with_attached_deposit(ONE_YOCTO)
-                .with_static_gas(GAS_FOR_MT_TRANSFER)
-                .mt_transfer(receiver_id, token_id.clone(), amount, None, None),
-            AdminWithdrawDirection::Intents(intents_account) => {
+        let (root, is_call) = match direction {
+            AdminWithdrawDirection::Near(receiver_id) => (
+                ext_mt::ext(token_account_id.clone())
+                    .with_attached_deposit(ONE_YOCTO)
+                    .with_static_gas(GAS_FOR_MT_TRANSFER)
+                    .mt_transfer(receiver_id, token_id.clone(), amount, None, None),
+                false,
+            ),
+            AdminWithdrawDirection::Intents(intents_account) => (
                 ext_mt::ext(token_account_id.clone())
                     .with_attached_deposit(ONE_YOCTO)
                     .with_static_gas(GAS_FOR_MT_TRANSFER_CALL)
@@ -254,20 +433,26 @@ impl AuroraLaunchpadContract {
                         None,
                         None,
                         intents_account.to_string(),
-                    )
-            }
-        }
+                    ),
+                true,
+            ),
+        };
+
+        Self::finish_withdrawal(root, amount, kind, is_call, None)
     }
 
-    pub(crate) const fn unsold_amount_of_tokens(&self) -> u128 {
-        if let Mechanics::FixedPrice { .. } = &self.config.mechanics {
+    pub(crate) fn unsold_amount_of_tokens(&self) -> u128 {
+        let unsold_amount = if let Mechanics::FixedPrice { .. } = &self.config.mechanics {
             self.config
                 .sale_amount
                 .0
                 .saturating_sub(self.total_sold_tokens)
-                .saturating_sub(self.withdrawn_unsold_tokens.amount)
         } else {
             0
-        }
+        };
+
+        unsold_amount
+            .saturating_add(self.unused_liquidity_allocation())
+            .saturating_sub(self.withdrawn_unsold_tokens.amount)
     }
 }
@@ -0,0 +1,213 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::{SaleToken, TokenId};
+use aurora_launchpad_types::error::LaunchpadError;
+use near_sdk::json_types::U128;
+use near_sdk::store::LookupMap;
+use near_sdk::{AccountId, PromiseOrValue, env, near};
+
+use crate::error::{PanicWithCode, require, require_with};
+use crate::mechanics::claim::{available_for_bundled_claim, user_allocation};
+use crate::storage_key::StorageKey;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
+
+/// The state of the sale tokens bundled with the main sale token.
+#[near(serializers = [borsh])]
+pub struct Bundle {
+    /// Whether the main sale token is transferred to the launchpad.
+    is_sale_token_funded: bool,
+    /// Whether the bundled sale tokens are transferred to the launchpad, in the order of
+    /// the configuration.
+    funded: Vec<bool>,
+    /// The claimed amounts of the bundled sale tokens by the index of the token and the account.
+    claimed: LookupMap<(u8, IntentsAccount), u128>,
+}
+
+impl Bundle {
+    pub fn new(is_sale_token_funded: bool, tokens: usize) -> Self {
+        Self {
+            is_sale_token_funded,
+            funded: vec![false; tokens],
+            claimed: LookupMap::new(StorageKey::BundleClaimed),
+        }
+    }
+
+    /// Returns `true` if the main sale token and all bundled sale tokens are transferred.
+    pub fn is_funded(&self) -> bool {
+        self.is_sale_token_funded && self.funded.iter().all(|funded| *funded)
+    }
+
+    /// Returns the claimed amount of the bundled sale token for the account.
+    pub fn claimed(&self, index: u8, account: &IntentsAccount) -> u128 {
+        self.claimed
+            .get(&(index, account.clone()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the claimed amount of the bundled sale token for the account.
+    pub fn set_claimed(&mut self, index: u8, account: &IntentsAccount, amount: u128) {
+        self.claimed.insert((index, account.clone()), amount);
+    }
+
+    /// Removes the claimed amounts of all bundled sale tokens for the account.
+    pub fn remove_account(&mut self, account: &IntentsAccount) {
+        for index in (0u8..).take(self.funded.len()) {
+            self.claimed.remove(&(index, account.clone()));
+        }
+    }
+}
+
+/// The allocation of the bundled sale token for a participant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct BundledAllocation {
+    /// The bundled sale token.
+    pub token: SaleToken,
+    /// The number of tokens allocated to the participant.
+    pub allocation: U128,
+    /// The number of tokens claimed by the participant.
+    pub claimed: U128,
+    /// The number of tokens available for claim.
+    pub available_for_claim: U128,
+}
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Returns the sale tokens which are transferred to the launchpad. The launchpad is
+    /// initialized once the main sale token and all bundled sale tokens are transferred.
+    pub fn get_funded_sale_tokens(&self) -> Vec<SaleToken> {
+        let sale_token = self
+            .bundle
+            .is_sale_token_funded
+            .then(|| self.config.sale_token.clone());
+        let bundled_tokens = self
+            .config
+            .bundled_sale_tokens
+            .iter()
+            .zip(&self.bundle.funded)
+            .filter(|(_, funded)| **funded)
+            .map(|(bundled, _)| bundled.token.clone());
+
+        sale_token.into_iter().chain(bundled_tokens).collect()
+    }
+
+    /// Returns the allocations of the bundled sale tokens for the given intents account.
+    pub fn get_bundled_allocations(&self, account: &IntentsAccount) -> Vec<BundledAllocation> {
+        let sale_allocation = self.investments.get(account).map_or(0, |investment| {
+            user_allocation(investment.weight, self.total_sold_tokens, &self.config)
+                .unwrap_or_default()
+        });
+        let vesting_start = self.config.tge.unwrap_or(self.config.end_date);
+
+        (0u8..)
+            .zip(&self.config.bundled_sale_tokens)
+            .map(|(index, bundled)| {
+                let claimed = self.bundle.claimed(index, account);
+                let available = available_for_bundled_claim(
+                    sale_allocation,
+                    bundled,
+                    vesting_start,
+                    env::block_timestamp(),
+                )
+                .unwrap_or_default();
+
+                BundledAllocation {
+                    token: bundled.token.clone(),
+                    allocation: bundled
+                        .allocation(sale_allocation)
+                        .unwrap_or_default()
+                        .into(),
+                    claimed: claimed.into(),
+                    available_for_claim: available.saturating_sub(claimed).into(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl AuroraLaunchpadContract {
+    /// Returns the index of the bundled sale token matching the transferred token. The token
+    /// id is `None` for NEP-141 tokens.
+    pub(crate) fn bundled_sale_token_index(
+        &self,
+        token_account_id: &AccountId,
+        token_id: Option<&TokenId>,
+    ) -> Option<u8> {
+        (0u8..)
+            .zip(&self.config.bundled_sale_tokens)
+            .find_map(|(index, bundled)| {
+                let is_same = match (&bundled.token, token_id) {
+                    (SaleToken::Nep141(account_id), None) => account_id == token_account_id,
+                    (SaleToken::Nep245((account_id, id)), Some(token_id)) => {
+                        account_id == token_account_id && id == token_id
+                    }
+                    _ => false,
+                };
+
+                is_same.then_some(index)
+            })
+    }
+
+    pub(crate) fn fund_sale_token(&mut self, amount: U128) -> PromiseOrValue<U128> {
+        if self.is_sale_token_set || self.bundle.is_sale_token_funded {
+            LaunchpadError::AlreadyInitialized.panic();
+        }
+
+        require(
+            amount == self.config.total_sale_amount,
+            LaunchpadError::WrongTotalSaleAmount,
+        );
+
+        self.bundle.is_sale_token_funded = true;
+        self.finish_funding();
+
+        PromiseOrValue::Value(0.into())
+    }
+
+    pub(crate) fn fund_bundled_sale_token(
+        &mut self,
+        index: u8,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        let index = usize::from(index);
+
+        require(
+            !self.bundle.funded[index],
+            LaunchpadError::BundledTokenAlreadyFunded,
+        );
+        require_with(
+            amount == self.config.bundled_sale_tokens[index].total_sale_amount,
+            LaunchpadError::WrongTotalSaleAmount,
+            "Wrong total sale amount of the bundled sale token",
+        );
+
+        self.bundle.funded[index] = true;
+        self.finish_funding();
+
+        PromiseOrValue::Value(0.into())
+    }
+
+    /// Returns `true` if all bundled sale tokens allocated to the account are claimed.
+    pub(crate) fn is_bundle_claimed(
+        &self,
+        account: &IntentsAccount,
+        sale_allocation: u128,
+    ) -> bool {
+        (0u8..)
+            .zip(&self.config.bundled_sale_tokens)
+            .all(|(index, bundled)| {
+                bundled
+                    .allocation(sale_allocation)
+                    .is_ok_and(|allocation| self.bundle.claimed(index, account) >= allocation)
+            })
+    }
+
+    fn finish_funding(&mut self) {
+        if self.bundle.is_funded() {
+            near_sdk::log!("The contract has been initialized successfully");
+            self.is_sale_token_set = true;
+        } else {
+            near_sdk::log!("The sale token has been funded, waiting for the rest of the bundle");
+        }
+    }
+}
@@ -1,21 +1,25 @@
 use aurora_launchpad_types::IntentsAccount;
-use aurora_launchpad_types::config::DistributionAccount;
+use aurora_launchpad_types::account::VestingPoint;
+use aurora_launchpad_types::config::{DistributionAccount, SaleToken, VestingSchedule};
+use aurora_launchpad_types::error::LaunchpadError;
 use defuse::core::payload::multi::MultiPayload;
 use defuse::tokens::{DepositAction, DepositMessage};
 use near_plugins::{Pausable, pause};
 use near_sdk::json_types::U128;
-use near_sdk::{Gas, Promise, assert_one_yocto, env, near, require};
+use near_sdk::{Gas, NearToken, Promise, PromiseOrValue, assert_one_yocto, env, near};
 
+use crate::error::{PanicWithCode, require, require_with};
 use crate::mechanics::claim::{
-    available_for_claim, available_for_individual_vesting_claim, user_allocation,
-};
-use crate::traits::{ext_ft, read_ft_result};
-use crate::{
-    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER,
-    GAS_FOR_FT_TRANSFER_CALL, ONE_YOCTO,
+    available_for_bundled_claim, available_for_claim, available_for_individual_vesting_claim,
+    user_allocation, vesting_schedule,
 };
+use crate::registration::Registration;
+use crate::sale_token::{add_transfer, read_transfer_call_result, transfer_call};
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
 
+const GAS_FOR_CONTINUE_CLAIM: Gas = Gas::from_tgas(15);
 const GAS_FOR_FINISH_CLAIM: Gas = Gas::from_tgas(2);
+const MAX_VESTING_POINTS: u16 = 100;
 
 #[near]
 impl AuroraLaunchpadContract {
@@ -37,6 +41,16 @@ impl AuroraLaunchpadContract {
     pub fn get_available_for_individual_vesting_claim(
         &self,
         account: &DistributionAccount,
+    ) -> U128 {
+        self.get_available_for_individual_vesting_claim_at(account, env::block_timestamp())
+    }
+
+    /// Returns the number of tokens which will be available for individual vesting claim for
+    /// the given distribution account at the given time.
+    pub fn get_available_for_individual_vesting_claim_at(
+        &self,
+        account: &DistributionAccount,
+        timestamp: u64,
     ) -> U128 {
         self.config
             .distribution_proportions
@@ -48,7 +62,7 @@ impl AuroraLaunchpadContract {
                         individual_distribution.allocation.0,
                         individual_distribution.vesting.as_ref(),
                         self.config.tge.unwrap_or(self.config.end_date),
-                        env::block_timestamp(),
+                        timestamp,
                     )
                     .unwrap_or_default()
                     .saturating_sub(
@@ -64,19 +78,20 @@ impl AuroraLaunchpadContract {
 
     /// Returns the number of tokens available for claim for the given intents account.
     pub fn get_available_for_claim(&self, account: &IntentsAccount) -> U128 {
+        self.get_available_for_claim_at(account, env::block_timestamp())
+    }
+
+    /// Returns the number of tokens which will be available for claim for the given intents
+    /// account at the given time, calculated from the current totals of the sale.
+    pub fn get_available_for_claim_at(&self, account: &IntentsAccount, timestamp: u64) -> U128 {
         let Some(investment) = self.investments.get(account) else {
             return 0.into();
         };
 
-        available_for_claim(
-            investment,
-            self.total_sold_tokens,
-            &self.config,
-            env::block_timestamp(),
-        )
-        .unwrap_or_default()
-        .saturating_sub(investment.claimed)
-        .into()
+        available_for_claim(investment, self.total_sold_tokens, &self.config, timestamp)
+            .unwrap_or_default()
+            .saturating_sub(investment.claimed)
+            .into()
     }
 
     /// Returns the allocation of tokens for a specific intents account.
@@ -102,16 +117,18 @@ impl AuroraLaunchpadContract {
 
     /// Calculates and returns the remaining vesting amount for a given intents account.
     pub fn get_remaining_vesting(&self, account: &IntentsAccount) -> U128 {
+        self.get_remaining_vesting_at(account, env::block_timestamp())
+    }
+
+    /// Calculates and returns the remaining vesting amount for a given intents account at
+    /// the given time, calculated from the current totals of the sale.
+    pub fn get_remaining_vesting_at(&self, account: &IntentsAccount, timestamp: u64) -> U128 {
         let Some(investment) = self.investments.get(account) else {
             return 0.into();
         };
-        let available_for_claim = available_for_claim(
-            investment,
-            self.total_sold_tokens,
-            &self.config,
-            env::block_timestamp(),
-        )
-        .unwrap_or_default();
+        let available_for_claim =
+            available_for_claim(investment, self.total_sold_tokens, &self.config, timestamp)
+                .unwrap_or_default();
         let user_allocation =
             user_allocation(investment.weight, self.total_sold_tokens, &self.config)
                 .unwrap_or_default();
@@ -122,6 +139,16 @@ impl AuroraLaunchpadContract {
     /// Calculates and returns the remaining vesting amount for a given distribution account
     /// in individual vesting.
     pub fn get_individual_vesting_remaining_vesting(&self, account: &DistributionAccount) -> U128 {
+        self.get_individual_vesting_remaining_vesting_at(account, env::block_timestamp())
+    }
+
+    /// Calculates and returns the remaining vesting amount for a given distribution account
+    /// in individual vesting at the given time.
+    pub fn get_individual_vesting_remaining_vesting_at(
+        &self,
+        account: &DistributionAccount,
+        timestamp: u64,
+    ) -> U128 {
         self.config
             .distribution_proportions
             .get_individual_vesting_distribution(account)
@@ -130,7 +157,7 @@ impl AuroraLaunchpadContract {
                     individual_distribution.allocation.0,
                     individual_distribution.vesting.as_ref(),
                     self.config.tge.unwrap_or(self.config.end_date),
-                    env::block_timestamp(),
+                    timestamp,
                 )
                 .unwrap_or_default();
                 individual_distribution
@@ -141,9 +168,43 @@ impl AuroraLaunchpadContract {
             .into()
     }
 
+    /// Returns the cumulative number of unlocked tokens for a given intents account at the start
+    /// of the vesting, at the end of the cliff and at `points` equal steps until the end of
+    /// the vesting. The number of steps is limited by 100.
+    pub fn get_vesting_schedule_for(
+        &self,
+        account: &IntentsAccount,
+        points: u16,
+    ) -> Vec<VestingPoint> {
+        let allocation = self.get_user_allocation(account);
+
+        self.vesting_points(allocation.0, self.config.vesting_schedule.as_ref(), points)
+    }
+
+    /// Returns the cumulative number of unlocked tokens for a given distribution account in
+    /// individual vesting at the start of the vesting, at the end of the cliff and at `points`
+    /// equal steps until the end of the vesting. The number of steps is limited by 100.
+    pub fn get_individual_vesting_schedule_for(
+        &self,
+        account: &DistributionAccount,
+        points: u16,
+    ) -> Vec<VestingPoint> {
+        self.config
+            .distribution_proportions
+            .get_individual_vesting_distribution(account)
+            .map_or_else(Vec::new, |individual_distribution| {
+                self.vesting_points(
+                    individual_distribution.allocation.0,
+                    individual_distribution.vesting.as_ref(),
+                    points,
+                )
+            })
+    }
+
     /// The transaction allows users to claim their bought assets after the launchpad finishes
     /// with success status. The optional array of the signed intents allows adding custom logic
-    /// inside the intents contract.
+    /// inside the intents contract. The available bundled sale tokens are claimed together with
+    /// the main sale token, while the intents are executed for the main sale token only.
     #[pause]
     #[payable]
     pub fn claim(
@@ -153,13 +214,10 @@ impl AuroraLaunchpadContract {
         refund_if_fails: Option<bool>,
     ) -> Promise {
         assert_one_yocto();
-        require!(
-            self.is_success(),
-            "Claim can be called only if the launchpad finishes with success status"
-        );
+        require(self.is_success(), LaunchpadError::ClaimNotAllowed);
 
         let Some(investment) = self.investments.get_mut(&account) else {
-            env::panic_str("No deposit was found for the intents account");
+            LaunchpadError::NoDeposit.panic();
         };
         // available_for_claim - claimed
         let assets_amount = match available_for_claim(
@@ -169,12 +227,18 @@ impl AuroraLaunchpadContract {
             env::block_timestamp(),
         ) {
             Ok(amount) => amount.saturating_sub(investment.claimed),
-            Err(err) => env::panic_str(&format!("Claim failed: {err}")),
+            Err(err) => LaunchpadError::ClaimFailed.panic_with(&format!("Claim failed: {err}")),
         };
 
-        require!(assets_amount > 0, "No assets to claim");
-
         investment.claimed = investment.claimed.saturating_add(assets_amount);
+        let weight = investment.weight;
+        let bundled_claim = self.claim_bundled_sale_tokens(&account, weight);
+
+        if assets_amount == 0 {
+            return bundled_claim.unwrap_or_else(|| LaunchpadError::NothingToClaim.panic());
+        }
+
+        self.cleanup.start_claim(&account);
 
         let receiver_id = account.clone().into();
         let msg = if let Some(intents) = intents
@@ -194,20 +258,22 @@ impl AuroraLaunchpadContract {
 
         near_sdk::log!("Claiming for: {account} amount: {assets_amount}");
 
-        ext_ft::ext(self.config.sale_token_account_id.clone())
-            .with_attached_deposit(ONE_YOCTO)
-            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-            .ft_transfer_call(
-                self.config.intents_account_id.clone(),
-                assets_amount.into(),
-                msg,
-                None,
-            )
-            .then(
-                Self::ext(env::current_account_id())
-                    .with_static_gas(GAS_FOR_FINISH_CLAIM)
-                    .finish_claim(&account, assets_amount),
-            )
+        let claim = transfer_call(
+            &self.config.sale_token,
+            self.config.intents_account_id.clone(),
+            assets_amount.into(),
+            msg,
+        )
+        .then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_FINISH_CLAIM)
+                .finish_claim(&account, assets_amount),
+        );
+
+        match bundled_claim {
+            Some(bundled_claim) => claim.and(bundled_claim),
+            None => claim,
+        }
     }
 
     /// The transaction allows stakeholders to claim their distributed assets with vesting after
@@ -216,17 +282,14 @@ impl AuroraLaunchpadContract {
     #[payable]
     pub fn claim_individual_vesting(&mut self, account: DistributionAccount) -> Promise {
         assert_one_yocto();
-        require!(
-            self.is_success(),
-            "Claim can be called only if the launchpad finishes with success status"
-        );
+        require(self.is_success(), LaunchpadError::ClaimNotAllowed);
 
         let Some(stakeholder_proportion) = self
             .config
             .distribution_proportions
             .get_individual_vesting_distribution(&account)
         else {
-            env::panic_str("No proportion was found for the account");
+            LaunchpadError::NoStakeholderProportion.panic();
         };
 
         let individual_claimed = self
@@ -240,60 +303,108 @@ impl AuroraLaunchpadContract {
             self.config.tge.unwrap_or(self.config.end_date),
             env::block_timestamp(),
         ) {
-            Ok(0) => env::panic_str("No assets to claim"),
+            Ok(0) => LaunchpadError::NothingToClaim.panic(),
             Ok(amount) => amount.saturating_sub(*individual_claimed),
-            Err(err) => env::panic_str(&format!("Claim failed: {err}")),
+            Err(err) => LaunchpadError::ClaimFailed.panic_with(&format!("Claim failed: {err}")),
         };
 
-        require!(assets_amount > 0, "No assets to claim");
+        require(assets_amount > 0, LaunchpadError::NothingToClaim);
 
         *individual_claimed = individual_claimed.saturating_add(assets_amount);
 
         near_sdk::log!("Claiming individual vesting for: {account} amount: {assets_amount}");
 
-        let is_call;
-        match &account {
-            DistributionAccount::Intents(intents_account) => {
-                is_call = true;
-                ext_ft::ext(self.config.sale_token_account_id.clone())
-                    .with_attached_deposit(ONE_YOCTO)
-                    .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-                    .ft_transfer_call(
-                        self.config.intents_account_id.clone(),
-                        assets_amount.into(),
-                        intents_account.to_string(),
-                        None,
-                    )
+        match (&account, &self.config.sale_token) {
+            (DistributionAccount::Intents(intents_account), _) => transfer_call(
+                &self.config.sale_token,
+                self.config.intents_account_id.clone(),
+                assets_amount.into(),
+                intents_account.to_string(),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_CLAIM)
+                    .finish_claim_individual_vesting(&account, assets_amount, true, None),
+            ),
+            // The recipient is registered in the NEP-141 sale token before the transfer if needed.
+            (DistributionAccount::Near(account_id), SaleToken::Nep141(token_account_id)) => {
+                Self::check_registration(token_account_id, account_id).then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_CONTINUE_CLAIM)
+                        .continue_claim_individual_vesting(&account, assets_amount),
+                )
             }
+            (DistributionAccount::Near(account_id), SaleToken::Nep245((token_account_id, _))) => {
+                add_transfer(
+                    &self.config.sale_token,
+                    Promise::new(token_account_id.clone()),
+                    account_id,
+                    assets_amount.into(),
+                    None,
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(GAS_FOR_FINISH_CLAIM)
+                        .finish_claim_individual_vesting(&account, assets_amount, false, None),
+                )
+            }
+        }
+    }
+
+    /// Transfers the claimed tokens to the NEAR recipient after resolving its registration in
+    /// the sale token. The claim is reverted if the recipient couldn't be registered.
+    #[private]
+    pub fn continue_claim_individual_vesting(
+        &mut self,
+        account: &DistributionAccount,
+        assets_amount: u128,
+    ) -> PromiseOrValue<()> {
+        let DistributionAccount::Near(account_id) = account else {
+            LaunchpadError::RegistrationNotRequired.panic();
+        };
+
+        let registration = self.resolve_registration(account_id, 0, 0);
 
-            DistributionAccount::Near(account_id) => {
-                is_call = false;
-                ext_ft::ext(self.config.sale_token_account_id.clone())
-                    .with_attached_deposit(ONE_YOCTO)
-                    .with_static_gas(GAS_FOR_FT_TRANSFER)
-                    .ft_transfer(account_id.clone(), assets_amount.into(), None)
+        if matches!(registration, Registration::Unfunded) {
+            if let Some(individual_vesting) = self.individual_vesting_claimed.get_mut(account) {
+                *individual_vesting = individual_vesting.saturating_sub(assets_amount);
             }
+
+            return PromiseOrValue::Value(());
         }
+
+        let storage_deposit = registration.storage_deposit();
+
+        add_transfer(
+            &self.config.sale_token,
+            Promise::new(self.config.sale_token.account_id().clone()),
+            account_id,
+            assets_amount.into(),
+            storage_deposit,
+        )
         .then(
             Self::ext(env::current_account_id())
                 .with_static_gas(GAS_FOR_FINISH_CLAIM)
-                .finish_claim_individual_vesting(&account, assets_amount, is_call),
+                .finish_claim_individual_vesting(account, assets_amount, false, storage_deposit),
         )
+        .into()
     }
 
     #[private]
     pub fn finish_claim(&mut self, account: &IntentsAccount, assets_amount: u128) {
-        require!(
+        require(
             env::promise_results_count() == 1,
-            "Expected one promise result"
+            LaunchpadError::PromiseResultsCount,
         );
 
-        let refund =
-            read_ft_result(0).map_or(assets_amount, |used| assets_amount.saturating_sub(used));
+        self.cleanup.finish_claim(account);
+
+        let refund = read_transfer_call_result(&self.config.sale_token, 0)
+            .map_or(assets_amount, |used| assets_amount.saturating_sub(used));
 
         if refund > 0 {
             let Some(investment) = self.investments.get_mut(account) else {
-                env::panic_str("No deposit was found for the intents account");
+                LaunchpadError::NoDeposit.panic();
             };
             near_sdk::log!("Refund: {refund}");
 
@@ -302,29 +413,66 @@ impl AuroraLaunchpadContract {
         }
     }
 
+    #[private]
+    pub fn finish_bundled_claim(
+        &mut self,
+        account: &IntentsAccount,
+        index: u8,
+        assets_amount: u128,
+    ) {
+        require(
+            env::promise_results_count() == 1,
+            LaunchpadError::PromiseResultsCount,
+        );
+
+        self.cleanup.finish_claim(account);
+
+        let bundled = &self.config.bundled_sale_tokens[usize::from(index)];
+        let refund = read_transfer_call_result(&bundled.token, 0)
+            .map_or(assets_amount, |used| assets_amount.saturating_sub(used));
+
+        if refund > 0 {
+            near_sdk::log!("Refund: {refund}");
+
+            // Refund claimed assets
+            let claimed = self.bundle.claimed(index, account);
+            self.bundle
+                .set_claimed(index, account, claimed.saturating_sub(refund));
+        }
+    }
+
     #[private]
     pub fn finish_claim_individual_vesting(
         &mut self,
         account: &DistributionAccount,
         assets_amount: u128,
         is_call: bool,
+        storage_deposit: Option<NearToken>,
     ) {
-        require!(
+        require_with(
             env::promise_results_count() == 1,
-            "Expected one promise result only"
+            LaunchpadError::PromiseResultsCount,
+            "Expected one promise result only",
         );
 
         let refund = if is_call {
-            read_ft_result(0).map_or(assets_amount, |used| assets_amount.saturating_sub(used))
+            read_transfer_call_result(&self.config.sale_token, 0)
+                .map_or(assets_amount, |used| assets_amount.saturating_sub(used))
         } else {
-            // A plain ft_transfer returns no value: a successful promise means nothing was
+            // A plain ft_transfer or mt_transfer returns no value: a successful promise means nothing was
             // refunded, while a failed promise refunds the whole amount.
-            env::promise_result_checked(0, 0).map_or(assets_amount, |_| 0)
+            let result = env::promise_result_checked(0, 0);
+
+            if result.is_err() {
+                self.restore_storage_deposit_budget(storage_deposit);
+            }
+
+            result.map_or(assets_amount, |_| 0)
         };
 
         if refund > 0 {
             let Some(individual_vesting) = self.individual_vesting_claimed.get_mut(account) else {
-                env::panic_str("No deposit was found for the intents account");
+                LaunchpadError::NoDeposit.panic();
             };
             near_sdk::log!("Refund: {refund}");
 
@@ -333,3 +481,86 @@ impl AuroraLaunchpadContract {
         }
     }
 }
+
+impl AuroraLaunchpadContract {
+    /// Transfers the available bundled sale tokens of the participant to the intents account.
+    /// Returns `None` if there are no bundled sale tokens available for claim.
+    fn claim_bundled_sale_tokens(
+        &mut self,
+        account: &IntentsAccount,
+        weight: u128,
+    ) -> Option<Promise> {
+        let sale_allocation = user_allocation(weight, self.total_sold_tokens, &self.config)
+            .unwrap_or_else(|err| {
+                LaunchpadError::ClaimFailed.panic_with(&format!("Claim failed: {err}"))
+            });
+        let vesting_start = self.config.tge.unwrap_or(self.config.end_date);
+        let mut promise: Option<Promise> = None;
+
+        for (index, bundled) in (0u8..).zip(&self.config.bundled_sale_tokens) {
+            let claimed = self.bundle.claimed(index, account);
+            let assets_amount = match available_for_bundled_claim(
+                sale_allocation,
+                bundled,
+                vesting_start,
+                env::block_timestamp(),
+            ) {
+                Ok(amount) => amount.saturating_sub(claimed),
+                Err(err) => LaunchpadError::ClaimFailed.panic_with(&format!("Claim failed: {err}")),
+            };
+
+            if assets_amount == 0 {
+                continue;
+            }
+
+            self.bundle
+                .set_claimed(index, account, claimed.saturating_add(assets_amount));
+            self.cleanup.start_claim(account);
+
+            near_sdk::log!(
+                "Claiming bundled sale token {} for: {account} amount: {assets_amount}",
+                bundled.token.account_id()
+            );
+
+            let claim = transfer_call(
+                &bundled.token,
+                self.config.intents_account_id.clone(),
+                assets_amount.into(),
+                DepositMessage::new(account.clone().into()).to_string(),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_CLAIM)
+                    .finish_bundled_claim(account, index, assets_amount),
+            );
+
+            promise = Some(match promise {
+                Some(promise) => promise.and(claim),
+                None => claim,
+            });
+        }
+
+        promise
+    }
+
+    fn vesting_points(
+        &self,
+        allocation: u128,
+        vesting: Option<&VestingSchedule>,
+        points: u16,
+    ) -> Vec<VestingPoint> {
+        vesting_schedule(
+            allocation,
+            vesting,
+            self.config.tge.unwrap_or(self.config.end_date),
+            u64::from(points.min(MAX_VESTING_POINTS)),
+        )
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(timestamp, unlocked)| VestingPoint {
+            timestamp,
+            unlocked: unlocked.into(),
+        })
+        .collect()
+    }
+}
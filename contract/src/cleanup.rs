@@ -0,0 +1,288 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::DistributionAccount;
+use aurora_launchpad_types::error::LaunchpadError;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::U128;
+use near_sdk::store::{LookupMap, Vector};
+use near_sdk::{AccountId, NearToken, Promise, assert_one_yocto, env, near};
+
+use crate::error::{require, require_with};
+use crate::mechanics::claim::user_allocation;
+use crate::storage_key::StorageKey;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
+
+/// The maximum number of entries removed by a single `cleanup` call.
+const MAX_CLEANUP_LIMIT: u32 = 100;
+/// The amount of NEAR kept on the balance by `withdraw_near` until the launchpad is archived. It
+/// pays for the storage taken by the ongoing claims and distributions.
+const NEAR_RESERVE: NearToken = NearToken::from_near(1);
+
+/// The state of the cleanup of the launchpad after the sale has concluded.
+#[near(serializers = [borsh])]
+pub struct Cleanup {
+    /// Participants whose entries aren't removed yet.
+    participants: Vector<IntentsAccount>,
+    /// The position in `participants` the next cleanup continues from.
+    cursor: u32,
+    /// The number of claims in progress per participant. Participants with ongoing claims
+    /// aren't removed, so the claimed amount could be restored if the transfer fails.
+    pending_claims: LookupMap<IntentsAccount, u32>,
+    /// Whether all entries are removed, and only the aggregated stats are kept.
+    archived: bool,
+    /// Whether all participants are tracked in `participants`. The launchpad is never archived
+    /// otherwise, since the entries of untracked participants couldn't be removed.
+    tracks_participants: bool,
+}
+
+impl Default for Cleanup {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl Cleanup {
+    /// Creates the cleanup state. `tracks_participants` is `false` for the launchpads whose
+    /// participants were added before the participants were tracked.
+    pub fn new(tracks_participants: bool) -> Self {
+        Self {
+            participants: Vector::new(StorageKey::Participants),
+            cursor: 0,
+            pending_claims: LookupMap::new(StorageKey::PendingClaims),
+            archived: false,
+            tracks_participants,
+        }
+    }
+
+    /// Returns `true` if the launchpad is archived.
+    pub const fn is_archived(&self) -> bool {
+        self.archived
+    }
+
+    /// Tracks a new participant for the cleanup.
+    pub fn add_participant(&mut self, account: IntentsAccount) {
+        self.participants.push(account);
+    }
+
+    /// Marks the start of a claim of the participant.
+    pub fn start_claim(&mut self, account: &IntentsAccount) {
+        *self.pending_claims.entry(account.clone()).or_insert(0) += 1;
+    }
+
+    /// Marks the end of a claim of the participant.
+    pub fn finish_claim(&mut self, account: &IntentsAccount) {
+        match self.pending_claims.get(account).copied() {
+            Some(count) if count > 1 => {
+                self.pending_claims.insert(account.clone(), count - 1);
+            }
+            Some(_) => {
+                self.pending_claims.remove(account);
+            }
+            None => {}
+        }
+    }
+}
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Removes up to `limit` entries of the discount whitelists and fully settled participants
+    /// after the sale has concluded. A participant is settled once the deposit is withdrawn or
+    /// refunded after a failed sale, or all bought tokens, including the bundled sale tokens,
+    /// are claimed after a successful one.
+    /// The launchpad is archived once all entries are removed and all distributions, claims and
+    /// withdrawals of unsold tokens are finished. Returns the number of removed entries.
+    pub fn cleanup(&mut self, limit: u32) -> u32 {
+        require(
+            self.is_success() || self.is_failed(),
+            LaunchpadError::CleanupNotAllowed,
+        );
+
+        let limit = limit.min(MAX_CLEANUP_LIMIT) as usize;
+        let mut removed = self
+            .discount_state
+            .as_mut()
+            .map_or(0, |state| state.clear_whitelists(limit));
+        let mut checked = removed;
+
+        while checked < limit {
+            let cursor = self.cleanup.cursor;
+            let Some(account) = self.cleanup.participants.get(cursor).cloned() else {
+                break;
+            };
+
+            checked += 1;
+
+            if self.is_settled(&account) {
+                self.remove_participant(&account);
+                self.cleanup.participants.swap_remove(cursor);
+                removed += 1;
+            } else {
+                self.cleanup.cursor += 1;
+            }
+        }
+
+        // Participants which aren't settled yet are checked again by the next cleanup.
+        if self.cleanup.cursor >= self.cleanup.participants.len() {
+            self.cleanup.cursor = 0;
+        }
+
+        if self.is_ready_for_archive() {
+            self.archive();
+        }
+
+        u32::try_from(removed).unwrap_or_default()
+    }
+
+    /// Returns the number of participants whose entries aren't removed by the cleanup yet.
+    pub fn get_participants_to_cleanup(&self) -> u32 {
+        self.cleanup.participants.len()
+    }
+
+    /// Withdraws NEAR which isn't used for the storage to the treasury after the sale has
+    /// concluded. The storage balances of the registered participants and the budget for
+    /// the registration of NEAR recipients are kept until the launchpad is archived.
+    #[payable]
+    #[access_control_any(roles(Role::Admin))]
+    pub fn withdraw_near(&mut self, treasury: AccountId, amount: Option<U128>) -> Promise {
+        assert_one_yocto();
+        require_with(
+            self.is_success() || self.is_failed() || self.is_archived(),
+            LaunchpadError::CleanupNotAllowed,
+            "NEAR could be withdrawn after the sale has concluded only",
+        );
+
+        let available = self.get_withdrawable_near().0;
+        let amount = amount.map_or(available, |amount| amount.0);
+
+        require(amount > 0, LaunchpadError::NothingToWithdraw);
+        require(amount <= available, LaunchpadError::NearWithdrawTooHigh);
+
+        near_sdk::log!("Withdrawing {amount} yoctoNEAR to {treasury}");
+
+        Promise::new(treasury).transfer(NearToken::from_yoctonear(amount))
+    }
+
+    /// Returns the amount of NEAR which could be withdrawn by `withdraw_near`.
+    pub fn get_withdrawable_near(&self) -> U128 {
+        let storage_cost = env::storage_byte_cost()
+            .as_yoctonear()
+            .saturating_mul(u128::from(env::storage_usage()));
+        let reserve = if self.cleanup.is_archived() {
+            0
+        } else {
+            NEAR_RESERVE
+                .as_yoctonear()
+                .saturating_add(self.storage_deposit_budget())
+        };
+
+        env::account_balance()
+            .as_yoctonear()
+            .saturating_sub(storage_cost)
+            .saturating_sub(self.storage_balances_total())
+            .saturating_sub(reserve)
+            .into()
+    }
+}
+
+impl AuroraLaunchpadContract {
+    fn is_settled(&self, account: &IntentsAccount) -> bool {
+        if self.locked_withdraw.contains(account)
+            || self.cleanup.pending_claims.contains_key(account)
+        {
+            return false;
+        }
+
+        self.investments.get(account).is_none_or(|investment| {
+            if self.is_success() {
+                user_allocation(investment.weight, self.total_sold_tokens, &self.config).is_ok_and(
+                    |allocation| {
+                        investment.claimed >= allocation
+                            && self.is_bundle_claimed(account, allocation)
+                    },
+                )
+            } else {
+                investment.amount == 0
+            }
+        })
+    }
+
+    fn remove_participant(&mut self, account: &IntentsAccount) {
+        self.investments.remove(account);
+        self.vestings.remove(account);
+        self.bundle.remove_account(account);
+
+        if let Some(discount_state) = self.discount_state.as_mut() {
+            discount_state.remove_account(account);
+        }
+    }
+
+    fn is_ready_for_archive(&self) -> bool {
+        if !self.cleanup.tracks_participants
+            || !self.cleanup.participants.is_empty()
+            || self
+                .discount_state
+                .as_ref()
+                .is_some_and(crate::discount::DiscountState::has_whitelists)
+        {
+            return false;
+        }
+
+        if self.is_failed() {
+            return true;
+        }
+
+        let proportions = &self.config.distribution_proportions;
+        let is_distributed = std::iter::once((
+            &proportions.solver_account_id,
+            proportions.solver_allocation.0,
+        ))
+        .chain(
+            proportions
+                .stakeholder_proportions
+                .iter()
+                .filter(|proportion| proportion.vesting.is_none())
+                .map(|proportion| (&proportion.account, proportion.allocation.0)),
+        )
+        .all(|(account, allocation)| self.distributed_amount(account) >= allocation);
+        let is_vesting_claimed = proportions
+            .stakeholder_proportions
+            .iter()
+            .filter(|proportion| proportion.vesting.is_some())
+            .all(|proportion| {
+                self.individual_vesting_claimed
+                    .get(&proportion.account)
+                    .is_some_and(|claimed| *claimed >= proportion.allocation.0)
+            });
+
+        is_distributed
+            && is_vesting_claimed
+            && self.is_deposits_distributed()
+            && self.is_liquidity_seeded()
+            && !self.withdrawn_unsold_tokens.is_ongoing
+    }
+
+    fn distributed_amount(&self, account: &DistributionAccount) -> u128 {
+        self.distributed_accounts
+            .get(account)
+            .map_or(0, |(amount, _)| *amount)
+    }
+
+    /// Removes the distribution entries and archives the launchpad. The claimed amounts of
+    /// the individual vestings are kept, so the ongoing claims could restore them on failures.
+    fn archive(&mut self) {
+        let proportions = &self.config.distribution_proportions;
+
+        for account in std::iter::once(&proportions.solver_account_id).chain(
+            proportions
+                .stakeholder_proportions
+                .iter()
+                .map(|proportion| &proportion.account),
+        ) {
+            self.distributed_accounts.remove(account);
+            self.distribution_failures.remove(account);
+        }
+
+        self.cleanup.archived = true;
+
+        near_sdk::log!("The launchpad has been archived");
+    }
+}
@@ -1,11 +1,13 @@
-use aurora_launchpad_types::config::{DepositToken, TokenId};
+use aurora_launchpad_types::config::{DepositToken, SaleToken, TokenId};
 use aurora_launchpad_types::discount::DepositDistribution;
+use aurora_launchpad_types::error::LaunchpadError;
 use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
 use defuse::tokens::DepositMessage;
 use near_plugins::{Pausable, pause};
 use near_sdk::json_types::U128;
-use near_sdk::{AccountId, Gas, Promise, PromiseOrValue, env, near, require};
+use near_sdk::{AccountId, Gas, Promise, PromiseOrValue, env, near};
 
+use crate::error::{PanicWithCode, require, require_with};
 use crate::traits::{MAX_FT_RESULT_LENGTH, MAX_MT_RESULT_LENGTH, ext_ft, ext_mt};
 use crate::{
     AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER_CALL,
@@ -27,12 +29,14 @@ impl AuroraLaunchpadContract {
         let _ = (sender_id, memo);
         let token_account_id = env::predecessor_account_id();
 
-        if token_account_id == self.config.sale_token_account_id {
-            self.init_contract(amount)
+        if self.is_nep141_sale_token(&token_account_id) {
+            self.fund_sale_token(amount)
+        } else if let Some(index) = self.bundled_sale_token_index(&token_account_id, None) {
+            self.fund_bundled_sale_token(index, amount)
         } else if self.is_nep141_deposit_token(&token_account_id) {
             self.handle_deposit(amount, &msg)
         } else {
-            env::panic_str("Unsupported NEP-141 token");
+            LaunchpadError::UnsupportedDepositToken.panic();
         }
     }
 
@@ -47,61 +51,65 @@ impl AuroraLaunchpadContract {
     ) -> PromiseOrValue<Vec<U128>> {
         let _ = sender_id;
 
-        require!(
+        require(
             token_ids.len() == 1 && amounts.len() == 1 && previous_owner_ids.len() == 1,
-            "Arrays must have length 1 for a single token deposit"
+            LaunchpadError::InvalidTokenArrays,
         );
 
-        require!(
-            self.is_nep245_deposit_token(&env::predecessor_account_id(), &token_ids),
-            "Wrong NEP-245 deposit token"
-        );
+        let token_account_id = env::predecessor_account_id();
+
+        let result = if self.is_nep245_sale_token(&token_account_id, &token_ids) {
+            self.fund_sale_token(amounts[0])
+        } else if let Some(index) =
+            self.bundled_sale_token_index(&token_account_id, token_ids.first())
+        {
+            self.fund_bundled_sale_token(index, amounts[0])
+        } else {
+            require_with(
+                self.is_nep245_deposit_token(&token_account_id, &token_ids),
+                LaunchpadError::UnsupportedDepositToken,
+                "Wrong NEP-245 deposit token",
+            );
+
+            self.handle_deposit(amounts[0], &msg)
+        };
 
-        match self.handle_deposit(amounts[0], &msg) {
+        match result {
             PromiseOrValue::Promise(promise) => PromiseOrValue::Promise(promise),
             PromiseOrValue::Value(value) => PromiseOrValue::Value(vec![value]),
         }
     }
 
-    fn init_contract(&mut self, amount: U128) -> PromiseOrValue<U128> {
-        if self.is_sale_token_set {
-            env::panic_str("The contract is already initialized");
-        }
-
-        require!(
-            amount == self.config.total_sale_amount,
-            "Wrong total sale amount"
-        );
-
-        near_sdk::log!("The contract has been initialized successfully");
-
-        self.is_sale_token_set = true;
-        PromiseOrValue::Value(0.into())
-    }
-
     fn handle_deposit(&mut self, amount: U128, msg: &str) -> PromiseOrValue<U128> {
-        require!(self.is_ongoing(), "Launchpad is not ongoing");
-        require!(
+        require(self.is_ongoing(), LaunchpadError::NotOngoing);
+        require(
             amount >= self.config.min_deposit,
-            "Deposit amount is too low"
+            LaunchpadError::DepositTooLow,
         );
 
         // Get IntentsAccount from the message
         let account: IntentsAccount = msg.try_into().unwrap_or_else(|e| {
-            env::panic_str(&format!("Failed to parse an account from msg: {e}"))
+            LaunchpadError::InvalidDepositMessage
+                .panic_with(&format!("Failed to parse an account from msg: {e}"))
         });
 
-        require!(
+        require_with(
             !self.locked_withdraw.contains(&account),
-            "Withdrawal is in progress for this account"
+            LaunchpadError::WithdrawInProgress,
+            "Withdrawal is in progress for this account",
         );
 
+        let storage_fee = self.charge_storage_fee(&account, amount.0);
+        let amount = U128(amount.0 - storage_fee);
+
         near_sdk::log!("Depositing amount: {} for: {account}", amount.0);
 
         let deposit_distribution =
             self.get_deposit_distribution(&account, amount.0, env::block_timestamp());
 
         if let DepositDistribution::Refund(refund) = deposit_distribution {
+            self.refund_storage_fee(storage_fee);
+            let refund = refund + storage_fee;
             near_sdk::log!("Refunding the whole amount: {refund} to {account}");
             return PromiseOrValue::Promise(self.create_refund_promise(account, refund.into()));
         }
@@ -113,7 +121,7 @@ impl AuroraLaunchpadContract {
             InvestmentAmount::default()
         });
 
-        let refund = match mechanics::deposit::deposit(
+        let mut refund = match mechanics::deposit::deposit(
             investments,
             amount.0,
             &mut self.total_deposited,
@@ -136,6 +144,10 @@ impl AuroraLaunchpadContract {
         if refund == amount.0 && is_new_participant {
             self.participants_count -= 1;
             self.investments.remove(&account);
+            self.refund_storage_fee(storage_fee);
+            refund += storage_fee;
+        } else if is_new_participant {
+            self.cleanup.add_participant(account.clone());
         }
 
         if refund > 0 {
@@ -148,14 +160,18 @@ impl AuroraLaunchpadContract {
 
     #[private]
     pub fn finish_ft_refund(&mut self, amount: U128) -> U128 {
-        require!(
+        require_with(
             env::promise_results_count() == 1,
-            "Only one promise result is expected"
+            LaunchpadError::PromiseResultsCount,
+            "Only one promise result is expected",
         );
 
         env::promise_result_checked(0, MAX_FT_RESULT_LENGTH).map_or(amount, |bytes| {
-            let refund_amount: U128 = near_sdk::serde_json::from_slice(&bytes)
-                .unwrap_or_else(|e| env::panic_str(&format!("Failed to parse refund amount: {e}")));
+            let refund_amount: U128 =
+                near_sdk::serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                    LaunchpadError::InvalidRefund
+                        .panic_with(&format!("Failed to parse refund amount: {e}"))
+                });
 
             U128(amount.0.saturating_sub(refund_amount.0))
         })
@@ -163,16 +179,23 @@ impl AuroraLaunchpadContract {
 
     #[private]
     pub fn finish_mt_refund(&mut self, amount: U128) -> Vec<U128> {
-        require!(
+        require_with(
             env::promise_results_count() == 1,
-            "Only one promise result is expected"
+            LaunchpadError::PromiseResultsCount,
+            "Only one promise result is expected",
         );
 
         let result = env::promise_result_checked(0, MAX_MT_RESULT_LENGTH).map_or(amount, |bytes| {
             let refund_amount = near_sdk::serde_json::from_slice::<Vec<U128>>(&bytes)
-                .unwrap_or_else(|e| env::panic_str(&format!("Failed to parse refund amount: {e}")))
+                .unwrap_or_else(|e| {
+                    LaunchpadError::InvalidRefund
+                        .panic_with(&format!("Failed to parse refund amount: {e}"))
+                })
                 .first()
-                .map_or_else(|| env::panic_str("Refund amount vector is empty"), |v| v.0);
+                .map_or_else(
+                    || LaunchpadError::InvalidRefund.panic_with("Refund amount vector is empty"),
+                    |v| v.0,
+                );
 
             U128(amount.0.saturating_sub(refund_amount))
         });
@@ -184,14 +207,27 @@ impl AuroraLaunchpadContract {
         matches!(&self.config.deposit_token, DepositToken::Nep141(account_id) if account_id == predecessor_account_id)
     }
 
+    pub(crate) fn is_nep141_sale_token(&self, predecessor_account_id: &AccountId) -> bool {
+        matches!(&self.config.sale_token, SaleToken::Nep141(account_id) if account_id == predecessor_account_id)
+    }
+
+    pub(crate) fn is_nep245_sale_token(
+        &self,
+        predecessor_account_id: &AccountId,
+        token_ids: &[TokenId],
+    ) -> bool {
+        matches!(&self.config.sale_token, SaleToken::Nep245((account_id, token_id)) if account_id == predecessor_account_id && token_ids.first() == Some(token_id))
+    }
+
     pub(crate) fn is_nep245_deposit_token(
         &self,
         predecessor_account_id: &AccountId,
         token_ids: &[TokenId],
     ) -> bool {
-        require!(
+        require_with(
             token_ids.len() == 1,
-            "Only one token_id is allowed for deposit"
+            LaunchpadError::InvalidTokenArrays,
+            "Only one token_id is allowed for deposit",
         );
         matches!(&self.config.deposit_token, DepositToken::Nep245((account_id, token_id)) if account_id == predecessor_account_id && token_id == &token_ids[0])
     }
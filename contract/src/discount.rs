@@ -1,14 +1,18 @@
 use aurora_launchpad_types::IntentsAccount;
 use aurora_launchpad_types::config::Mechanics;
 use aurora_launchpad_types::discount::DepositDistribution;
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::AccessControllable;
 use near_plugins::access_control_any;
-use near_sdk::{env, near};
+use near_sdk::near;
 
+pub use phases::DiscountPhases;
 pub use state::DiscountState;
 
+use crate::error::PanicWithCode;
 use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
 
+mod phases;
 mod state;
 
 #[near]
@@ -32,7 +36,8 @@ impl AuroraLaunchpadContract {
         accounts: Vec<IntentsAccount>,
     ) {
         let phase = self.get_phase_by_id(phase_id).unwrap_or_else(|| {
-            env::panic_str(&format!("Discount phase with id {phase_id} not found"))
+            LaunchpadError::PhaseNotFound
+                .panic_with(&format!("Discount phase with id {phase_id} not found"))
         });
 
         phase.extend_whitelist(accounts);
@@ -47,11 +52,12 @@ impl AuroraLaunchpadContract {
         accounts: Vec<IntentsAccount>,
     ) {
         let phase = self.get_phase_by_id(phase_id).unwrap_or_else(|| {
-            env::panic_str(&format!("Discount phase with id {phase_id} not found"))
+            LaunchpadError::PhaseNotFound
+                .panic_with(&format!("Discount phase with id {phase_id} not found"))
         });
 
         phase.remove_from_whitelist(accounts).unwrap_or_else(|| {
-            env::panic_str(&format!(
+            LaunchpadError::WhitelistNotFound.panic_with(&format!(
                 "There is no whitelist for the phase with id {phase_id}"
             ))
         });
@@ -62,7 +68,8 @@ impl AuroraLaunchpadContract {
     #[access_control_any(roles(Role::Admin))]
     pub fn delete_whitelist_for_discount_phase(&mut self, phase_id: u16) {
         let phase = self.get_phase_by_id(phase_id).unwrap_or_else(|| {
-            env::panic_str(&format!("Discount phase with id {phase_id} not found"))
+            LaunchpadError::PhaseNotFound
+                .panic_with(&format!("Discount phase with id {phase_id} not found"))
         });
 
         phase.delete_whitelist();
@@ -104,7 +111,13 @@ impl AuroraLaunchpadContract {
         } = mechanics
             && let Some(state) = self.discount_state.as_mut()
         {
-            state.update(account, distribution, deposit_token.0, sale_token.0);
+            state
+                .update(account, distribution, deposit_token.0, sale_token.0)
+                .unwrap_or_else(|_| {
+                    LaunchpadError::Overflow.panic_with(
+                        "Overflow in DiscountState update is impossible because it follows a successful deposit",
+                    )
+                });
         }
     }
 
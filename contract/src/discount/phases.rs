@@ -0,0 +1,438 @@
+use alloy_primitives::ruint::aliases::U256;
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::{LaunchpadConfig, Mechanics};
+use aurora_launchpad_types::discount::{DepositDistribution, DiscountParams, DiscountPhase};
+use aurora_launchpad_types::utils::to_u128;
+
+use crate::mechanics::deposit::{
+    calculate_amount_of_sale_tokens, calculate_weight_from_sale_tokens,
+};
+
+const MULTIPLIER: u128 = 10_000;
+
+/// The state of the discount phases which is required to calculate the distribution of deposits
+/// between the phases. The calculation doesn't depend on the storage, so the state could be kept
+/// in the contract storage or in memory, e.g. to simulate the sale off-chain. The calculations
+/// don't panic, and the errors are left to the callers.
+pub trait DiscountPhases {
+    /// Returns the ids of all discount phases.
+    fn phase_ids(&self) -> Vec<u16>;
+
+    /// Returns `true` if the state of the discount phase exists.
+    fn has_phase(&self, phase_id: u16) -> bool;
+
+    /// Returns the total number of sale tokens sold with discount in the phase.
+    fn total_sale_tokens(&self, phase_id: u16) -> u128;
+
+    /// Returns the limit of sale tokens for the phase, if any.
+    fn limit_per_phase(&self, phase_id: u16) -> Option<u128>;
+
+    /// Returns the number of sale tokens bought with discount by the account in the phase.
+    fn account_sale_tokens(&self, phase_id: u16, account: &IntentsAccount) -> Option<u128>;
+
+    /// Returns `true` if the account is allowed to participate in the phase.
+    fn is_account_allowed(&self, phase_id: u16, account: &IntentsAccount) -> bool;
+
+    /// Returns `true` if the phases share their sale limits.
+    fn is_phases_linked(&self, phase_id: u16, linked_id: u16) -> bool;
+
+    /// Adds the sale tokens bought with discount by the account to the phase.
+    fn add_sale_tokens(&mut self, phase_id: u16, account: &IntentsAccount, sale_tokens: u128);
+
+    /// Reports a notable outcome of the calculation, e.g. to the logs of the contract. Nothing
+    /// is reported by default.
+    fn report(&self, _message: &str) {}
+
+    /// Returns the limit of sale tokens for the phase including the tokens which weren't sold
+    /// in the linked phases, or `None` if the phase has no limit.
+    fn effective_limit_per_phase(&self, phase_id: u16) -> Option<u128> {
+        self.limit_per_phase(phase_id)?;
+
+        let (limits, linked_sale_tokens) = self
+            .phase_ids()
+            .into_iter()
+            .filter(|id| *id == phase_id || self.is_phases_linked(phase_id, *id))
+            .fold((0u128, 0u128), |(limits, sale_tokens), id| {
+                let limit = self.limit_per_phase(id);
+                let sale_tokens = if id == phase_id || limit.is_none() {
+                    sale_tokens
+                } else {
+                    sale_tokens.saturating_add(self.total_sale_tokens(id))
+                };
+
+                (limits.saturating_add(limit.unwrap_or(0)), sale_tokens)
+            });
+
+        Some(limits.saturating_sub(linked_sale_tokens))
+    }
+
+    /// Returns the distribution of the deposit between the discount phases and the public sale.
+    /// The whole deposit is refunded if the distribution couldn't be calculated.
+    #[must_use]
+    fn get_deposit_distribution(
+        &self,
+        account: &IntentsAccount,
+        deposit: u128,
+        timestamp: u64,
+        config: &LaunchpadConfig,
+        total_sold_tokens: u128,
+    ) -> DepositDistribution {
+        if let Some(discount_params) = config.discounts.as_ref() {
+            let percentages_per_phase =
+                self.get_discount_percentage_per_phase(account, timestamp, discount_params);
+            let is_public_sale_allowed = discount_params
+                .public_sale_start_time
+                .is_none_or(|start| timestamp >= start);
+
+            if percentages_per_phase.is_empty() {
+                // There are no discount phases, but public sale is available.
+                return if is_public_sale_allowed {
+                    DepositDistribution::WithoutDiscount(deposit)
+                // The public sale hasn't started yet. Return the refund only.
+                } else {
+                    DepositDistribution::Refund(deposit)
+                };
+            }
+
+            let available_for_sale = config.sale_amount.0.saturating_sub(total_sold_tokens);
+
+            if available_for_sale == 0 && config.mechanics != Mechanics::PriceDiscovery {
+                self.report("There are no tokens left for sale. Returning the refund only");
+                return DepositDistribution::Refund(deposit);
+            }
+
+            match self.calculate_deposit_distribution(
+                account,
+                deposit,
+                &percentages_per_phase,
+                config.mechanics,
+                discount_params,
+                available_for_sale,
+                is_public_sale_allowed,
+            ) {
+                Ok(distribution) => distribution,
+                Err(e) => {
+                    self.report(&format!(
+                        "Error occurred while calculating deposit distribution: {e}"
+                    ));
+                    DepositDistribution::Refund(deposit)
+                }
+            }
+        } else {
+            DepositDistribution::WithoutDiscount(deposit)
+        }
+    }
+
+    /// Returns the ids and discount percentages of the phases available for the account at the
+    /// given time.
+    #[must_use]
+    fn get_discount_percentage_per_phase(
+        &self,
+        account: &IntentsAccount,
+        timestamp: u64,
+        discount_params: &DiscountParams,
+    ) -> Vec<(u16, u16)> {
+        discount_params
+            .get_phases_by_time(timestamp)
+            .iter()
+            .filter(|phase_params| {
+                self.has_phase(phase_params.id)
+                    && !self.is_exceeded_account_limit(account, phase_params)
+                    && self.is_account_allowed(phase_params.id, account)
+            })
+            .map(|phase_params| (phase_params.id, phase_params.percentage))
+            .collect::<Vec<_>>()
+    }
+
+    /// Returns `true` if the account has bought the maximum number of sale tokens in the phase.
+    #[must_use]
+    fn is_exceeded_account_limit(
+        &self,
+        account: &IntentsAccount,
+        discount_phase: &DiscountPhase,
+    ) -> bool {
+        self.account_sale_tokens(discount_phase.id, account)
+            .and_then(|users_bought_tokens| {
+                discount_phase
+                    .max_limit_per_account
+                    .map(|limit| users_bought_tokens >= limit.0)
+            })
+            .unwrap_or(false)
+    }
+
+    /// Adds the sale tokens bought with discount in the phases of the distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the amount of sale tokens of a phase overflows.
+    fn update(
+        &mut self,
+        account: &IntentsAccount,
+        distribution: &DepositDistribution,
+        deposit_token: u128,
+        sale_token: u128,
+    ) -> Result<(), &'static str> {
+        if let DepositDistribution::WithDiscount { phase_weights, .. } = distribution {
+            for (id, weight) in phase_weights {
+                let sale_tokens =
+                    calculate_amount_of_sale_tokens(*weight, deposit_token, sale_token)?;
+
+                self.add_sale_tokens(*id, account, sale_tokens);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the distribution of the deposit between the discount phases and the public sale
+    /// for the mechanics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the distribution calculation overflows or the discount phases
+    /// don't match the config.
+    #[allow(clippy::too_many_arguments)]
+    fn calculate_deposit_distribution(
+        &self,
+        account: &IntentsAccount,
+        deposit: u128,
+        percent_per_phase: &[(u16, u16)],
+        mechanics: Mechanics,
+        discount_params: &DiscountParams,
+        available_for_sale: u128,
+        is_public_sale_allowed: bool,
+    ) -> Result<DepositDistribution, &'static str> {
+        match mechanics {
+            Mechanics::FixedPrice { .. } => self.deposit_distribution_fixed_price(
+                percent_per_phase,
+                deposit,
+                account,
+                discount_params,
+                mechanics,
+                available_for_sale,
+                is_public_sale_allowed,
+            ),
+            Mechanics::PriceDiscovery => {
+                deposit_distribution_price_discovery(percent_per_phase, deposit)
+            }
+        }
+    }
+
+    /// Returns the distribution of the deposit between the discount phases and the public sale
+    /// for the `FixedPrice` mechanics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mechanics is not `FixedPrice`, a phase doesn't exist or the
+    /// calculation overflows.
+    #[allow(clippy::too_many_arguments)]
+    fn deposit_distribution_fixed_price(
+        &self,
+        percent_per_phase: &[(u16, u16)],
+        deposit: u128,
+        account: &IntentsAccount,
+        discount_params: &DiscountParams,
+        mechanics: Mechanics,
+        available_for_sale: u128,
+        is_public_sale_allowed: bool,
+    ) -> Result<DepositDistribution, &'static str> {
+        let mut remain_deposit = deposit;
+        let mut remain_available_for_sale = available_for_sale;
+        let mut refund = 0;
+        // The number of sale tokens that were sold in the previous phases in the current transaction.
+        let mut phase_weights: Vec<(u16, u128)> = Vec::with_capacity(percent_per_phase.len());
+        let Mechanics::FixedPrice {
+            deposit_token,
+            sale_token,
+        } = mechanics
+        else {
+            return Err("FixedPrice mechanic is expected");
+        };
+
+        for (id, percent) in percent_per_phase {
+            let weight = calculate_weight_with_discount(remain_deposit, *percent)?;
+            let phase_params = discount_params.get_phase_params_by_id(*id)?;
+            let existed_account_sale_tokens = self.get_account_sale_tokens_for_phase(account, *id);
+            // The number of sale tokens that were sold in the previous phases made in previous transactions.
+            let existed_phase_sale_tokens = self.get_total_sale_tokens_for_phases_with_limits(*id);
+            let sale_tokens_per_deposit =
+                calculate_amount_of_sale_tokens(weight, deposit_token.0, sale_token.0)?;
+
+            if !phase_params
+                .is_min_limit_passed(sale_tokens_per_deposit, existed_account_sale_tokens)
+            {
+                continue;
+            }
+
+            let sale_tokens_per_account =
+                existed_account_sale_tokens.saturating_add(sale_tokens_per_deposit);
+            let sale_tokens_for_prev_phases = self.get_total_sale_tokens_for_previous_phases(
+                *id,
+                &phase_weights,
+                deposit_token.0,
+                sale_token.0,
+            )?;
+            let sale_tokens_per_phases = existed_phase_sale_tokens
+                .saturating_add(sale_tokens_for_prev_phases)
+                .saturating_add(sale_tokens_per_deposit);
+
+            let exceeded_account_limit =
+                phase_params.calculate_account_limit_exceeded(sale_tokens_per_account);
+            let exceeded_phase_limit =
+                self.calculate_phase_limit_excess(sale_tokens_per_phases, *id);
+            let exceeded_global_limit =
+                sale_tokens_per_deposit.saturating_sub(remain_available_for_sale);
+
+            // Get the maximum value between user limit, phase limit and global limit that was exceeded.
+            let max_exceeded = exceeded_phase_limit
+                .max(exceeded_account_limit)
+                .max(exceeded_global_limit);
+
+            if max_exceeded > 0 {
+                let available_tokens_for_sale =
+                    sale_tokens_per_deposit.saturating_sub(max_exceeded);
+                let weight_for_phase = calculate_weight_from_sale_tokens(
+                    available_tokens_for_sale,
+                    deposit_token.0,
+                    sale_token.0,
+                )?;
+
+                phase_weights.push((*id, weight_for_phase));
+                let required_deposit =
+                    calculate_weight_without_discount(weight_for_phase, *percent)?;
+
+                remain_deposit = remain_deposit.saturating_sub(required_deposit);
+                remain_available_for_sale =
+                    remain_available_for_sale.saturating_sub(available_tokens_for_sale);
+            } else {
+                // No limits exceeded - this phase consumes the entire remaining deposit
+                phase_weights.push((*id, weight));
+                remain_deposit = 0;
+                remain_available_for_sale =
+                    remain_available_for_sale.saturating_sub(sale_tokens_per_deposit);
+            }
+
+            // No more deposit or nothing to sell.
+            if remain_deposit == 0 || remain_available_for_sale == 0 {
+                break;
+            }
+        }
+
+        // There are available tokens for sale and remain deposit tokens, which we can spend them for public sale.
+        let public_sale_weight =
+            if remain_deposit > 0 && remain_available_for_sale > 0 && is_public_sale_allowed {
+                let sale_tokens =
+                    calculate_amount_of_sale_tokens(remain_deposit, deposit_token.0, sale_token.0)?;
+
+                if sale_tokens > remain_available_for_sale {
+                    // The deposit buys more sale tokens than remain available, so cap the accepted
+                    // weight to the remaining capacity. We convert the cap directly into deposit
+                    // weight and round *down*, which guarantees the accepted weight never maps back
+                    // to more than `remain_available_for_sale` sale tokens. Computing the excess in
+                    // sale-token units and subtracting its rounded-down deposit equivalent instead
+                    // would round the accepted weight up and let it exceed the cap (e.g., when the
+                    // excess is smaller than one deposit unit at the configured price granularity).
+                    let available_weight = calculate_weight_from_sale_tokens(
+                        remain_available_for_sale,
+                        deposit_token.0,
+                        sale_token.0,
+                    )?;
+
+                    refund = remain_deposit.saturating_sub(available_weight);
+                    available_weight
+                } else {
+                    remain_deposit
+                }
+            } else {
+                refund = remain_deposit;
+                0
+            };
+
+        Ok(DepositDistribution::WithDiscount {
+            phase_weights,
+            public_sale_weight,
+            refund,
+        })
+    }
+
+    fn get_account_sale_tokens_for_phase(&self, account: &IntentsAccount, phase_id: u16) -> u128 {
+        self.account_sale_tokens(phase_id, account).unwrap_or(0)
+    }
+
+    fn get_total_sale_tokens_for_phases_with_limits(&self, phase_id: u16) -> u128 {
+        self.phase_ids()
+            .into_iter()
+            .filter(|id| self.limit_per_phase(*id).is_some())
+            .filter(|id| *id == phase_id || self.is_phases_linked(phase_id, *id))
+            .map(|id| self.total_sale_tokens(id))
+            .sum()
+    }
+
+    fn calculate_phase_limit_excess(&self, sale_tokens: u128, phase_id: u16) -> u128 {
+        // We don't care if the current phase has no limit. Spend full deposit for sale tokens.
+        if self.limit_per_phase(phase_id).is_none() {
+            return 0;
+        }
+
+        // ID of phases that share their limits with the current phase.
+        let total_limits = self
+            .phase_ids()
+            .into_iter()
+            .filter(|id| *id == phase_id || self.is_phases_linked(phase_id, *id))
+            .map(|id| self.limit_per_phase(id).unwrap_or(0))
+            .sum();
+
+        sale_tokens.saturating_sub(total_limits)
+    }
+
+    /// Returns the number of sale tokens bought in the linked phases of the distribution.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of sale tokens overflows.
+    fn get_total_sale_tokens_for_previous_phases(
+        &self,
+        phase_id: u16,
+        phase_weights: &[(u16, u128)],
+        deposit_token: u128,
+        sale_token: u128,
+    ) -> Result<u128, &'static str> {
+        phase_weights
+            .iter()
+            .filter(|(prev_id, _)| self.is_phases_linked(phase_id, *prev_id))
+            .try_fold(0u128, |acc, (_, prev_weight)| {
+                calculate_amount_of_sale_tokens(*prev_weight, deposit_token, sale_token)
+                    .and_then(|result| acc.checked_add(result).ok_or("Overflow occurred"))
+            })
+    }
+}
+
+fn deposit_distribution_price_discovery(
+    percent_per_phase: &[(u16, u16)],
+    deposit: u128,
+) -> Result<DepositDistribution, &'static str> {
+    let (id, percent) = percent_per_phase
+        .first()
+        .ok_or("At least one discount must exist")?;
+    let weight = calculate_weight_with_discount(deposit, *percent)?;
+
+    Ok(DepositDistribution::WithDiscount {
+        phase_weights: vec![(*id, weight)],
+        public_sale_weight: 0,
+        refund: 0,
+    })
+}
+
+fn calculate_weight_with_discount(deposit: u128, percent: u16) -> Result<u128, &'static str> {
+    to_u128(
+        U256::from(deposit) * U256::from(MULTIPLIER.saturating_add(u128::from(percent)))
+            / U256::from(MULTIPLIER),
+    )
+}
+
+fn calculate_weight_without_discount(weight: u128, percent: u16) -> Result<u128, &'static str> {
+    to_u128(
+        U256::from(weight) * U256::from(MULTIPLIER)
+            / U256::from(MULTIPLIER.saturating_add(u128::from(percent))),
+    )
+}
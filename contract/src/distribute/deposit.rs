@@ -1,188 +1,154 @@
-use aurora_launchpad_types::config::{DepositToken, TokenId};
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::{DepositToken, DistributionAccount};
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::{Pausable, pause};
 use near_sdk::json_types::U128;
-use near_sdk::{AccountId, Gas, Promise, assert_one_yocto, env, near, require};
+use near_sdk::{Gas, NearToken, Promise, PromiseOrValue, assert_one_yocto, env, near};
 
-use crate::traits::{ext_ft, ext_mt, read_ft_result, read_mt_result};
-use crate::{
-    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER_CALL,
-    GAS_FOR_MT_TRANSFER_CALL, ONE_YOCTO,
+use crate::error::{PanicWithCode, require, require_with};
+use crate::registration::Registration;
+use crate::sale_token::{
+    add_transfer, read_transfer_call_result, transfer_call, transferable_token,
 };
+use crate::traits::MAX_FT_RESULT_LENGTH;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
 
+const GAS_FOR_CONTINUE_DEPOSITS_DISTRIBUTION: Gas = Gas::from_tgas(10);
 const GAS_FOR_FINISH_DISTRIBUTE_DEPOSITS: Gas = Gas::from_tgas(10);
 
+/// A transfer of the deposit tokens to the solver if `recipient` is `None`, or to the recipient
+/// with the index in the configuration.
+#[near(serializers = [json])]
+pub struct DepositTransfer {
+    recipient: Option<u8>,
+    account: DistributionAccount,
+    amount: U128,
+}
+
 #[near]
 impl AuroraLaunchpadContract {
     #[pause]
     #[payable]
     pub fn distribute_deposit_tokens(&mut self) -> Promise {
         assert_one_yocto();
-        require!(
+        require_with(
             self.is_success(),
-            "Deposited tokens could be distributed after success only"
+            LaunchpadError::DistributionNotAllowed,
+            "Deposited tokens could be distributed after success only",
         );
 
-        require!(
+        require(
             !self.deposits_distribution.is_ongoing,
-            "Deposit distribution is ongoing"
+            LaunchpadError::DistributionOngoing,
         );
         self.deposits_distribution.is_ongoing = true;
 
-        let (solver_amount, fee_amount) = self
+        let transfers = self
             .calculate_distribution()
-            .unwrap_or_else(|e| env::panic_str(e));
+            .unwrap_or_else(|e| LaunchpadError::DistributionFailed.panic_with(e));
 
-        require!(
-            solver_amount > 0 || fee_amount > 0,
-            "Deposit tokens have been already distributed"
+        require_with(
+            !transfers.is_empty(),
+            LaunchpadError::AlreadyDistributed,
+            "Deposit tokens have been already distributed",
         );
 
-        match &self.config.deposit_token {
-            DepositToken::Nep141(token_account_id) => {
-                self.distribute_nep141_deposit_tokens(token_account_id, solver_amount, fee_amount)
-            }
-            DepositToken::Nep245((token_account_id, token_id)) => self
-                .distribute_nep245_deposit_tokens(
-                    token_account_id,
-                    token_id,
-                    solver_amount,
-                    fee_amount,
-                ),
-        }
-    }
+        let registrations = match &self.config.deposit_token {
+            DepositToken::Nep141(token_account_id) => transfers
+                .iter()
+                .filter_map(|transfer| match &transfer.account {
+                    DistributionAccount::Near(account_id) => Some(account_id),
+                    DistributionAccount::Intents(_) => None,
+                })
+                .map(|account_id| Self::check_registration(token_account_id, account_id))
+                .reduce(Promise::and),
+            DepositToken::Nep245(_) => None,
+        };
 
-    fn distribute_nep141_deposit_tokens(
-        &self,
-        token_account_id: &AccountId,
-        solver_amount: u128,
-        fee_amount: u128,
-    ) -> Promise {
-        let solver_promise = (solver_amount > 0).then(|| {
-            ext_ft::ext(token_account_id.clone())
-                .with_attached_deposit(ONE_YOCTO)
-                .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-                .ft_transfer_call(
-                    self.config.intents_account_id.clone(),
-                    solver_amount.into(),
-                    self.config
-                        .distribution_proportions
-                        .solver_account_id
-                        .as_account_id()
-                        .to_string(),
-                    None,
-                )
-        });
-
-        let fee_promise = (fee_amount > 0).then(|| {
-            ext_ft::ext(token_account_id.clone())
-                .with_attached_deposit(ONE_YOCTO)
-                .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-                .ft_transfer_call(
-                    self.config.intents_account_id.clone(),
-                    U128(fee_amount),
-                    self.config
-                        .distribution_proportions
-                        .deposits
-                        .as_ref()
-                        .unwrap()
-                        .fee_account
-                        .to_string(),
-                    None,
-                )
-        });
-
-        match (solver_promise, fee_promise) {
-            (Some(solver), Some(fee)) => solver.and(fee),
-            (Some(solver), None) => solver,
-            (None, Some(fee)) => fee,
-            (None, None) => env::panic_str("No NEP-141 tokens to distribute"),
+        // NEAR recipients are registered in the NEP-141 deposit token before the transfers if needed.
+        if let Some(registrations) = registrations {
+            return registrations.then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_CONTINUE_DEPOSITS_DISTRIBUTION)
+                    .continue_deposits_distribution(transfers),
+            );
         }
-        .then(
-            Self::ext(env::current_account_id())
-                .with_static_gas(GAS_FOR_FINISH_DISTRIBUTE_DEPOSITS)
-                .finish_distribute_deposits(solver_amount, fee_amount, true),
+
+        self.transfer_deposit_tokens(
+            transfers
+                .into_iter()
+                .map(|transfer| (transfer, None))
+                .collect(),
         )
     }
 
-    fn distribute_nep245_deposit_tokens(
-        &self,
-        token_account_id: &AccountId,
-        token_id: &TokenId,
-        solver_amount: u128,
-        fee_amount: u128,
-    ) -> Promise {
-        let solver_promise = (solver_amount > 0).then_some(
-            ext_mt::ext(token_account_id.clone())
-                .with_attached_deposit(ONE_YOCTO)
-                .with_static_gas(GAS_FOR_MT_TRANSFER_CALL)
-                .mt_transfer_call(
-                    self.config.intents_account_id.clone(),
-                    token_id.clone(),
-                    solver_amount.into(),
-                    None,
-                    None,
-                    self.config
-                        .distribution_proportions
-                        .solver_account_id
-                        .as_account_id()
-                        .to_string(),
-                ),
-        );
+    /// Transfers the deposit tokens after resolving the registrations of NEAR recipients.
+    /// Recipients which couldn't be registered are skipped and retried by the next distribution.
+    #[private]
+    pub fn continue_deposits_distribution(
+        &mut self,
+        transfers: Vec<DepositTransfer>,
+    ) -> PromiseOrValue<()> {
+        let mut index = 0;
+        let mut reserved = 0;
+        let mut resolved = Vec::with_capacity(transfers.len());
 
-        let fee_promise = (fee_amount > 0).then_some(
-            ext_mt::ext(token_account_id.clone())
-                .with_attached_deposit(ONE_YOCTO)
-                .with_static_gas(GAS_FOR_MT_TRANSFER_CALL)
-                .mt_transfer_call(
-                    self.config.intents_account_id.clone(),
-                    token_id.clone(),
-                    U128(fee_amount),
-                    None,
-                    None,
-                    self.config
-                        .distribution_proportions
-                        .deposits
-                        .as_ref()
-                        .unwrap()
-                        .fee_account
-                        .to_string(),
-                ),
-        );
+        for transfer in transfers {
+            let storage_deposit = if let DistributionAccount::Near(account_id) = &transfer.account {
+                let registration = self.resolve_registration(account_id, index, reserved);
+                index += 1;
+
+                if matches!(registration, Registration::Unfunded) {
+                    continue;
+                }
 
-        match (solver_promise, fee_promise) {
-            (Some(solver), Some(fee)) => solver.and(fee),
-            (Some(solver), None) => solver,
-            (None, Some(fee)) => fee,
-            (None, None) => env::panic_str("No NEP-245 tokens to distribute"),
+                let storage_deposit = registration.storage_deposit();
+                reserved += storage_deposit.map_or(0, |deposit| deposit.as_yoctonear());
+                storage_deposit
+            } else {
+                None
+            };
+
+            resolved.push((transfer, storage_deposit));
         }
-        .then(
-            Self::ext(env::current_account_id())
-                .with_static_gas(GAS_FOR_FINISH_DISTRIBUTE_DEPOSITS)
-                .finish_distribute_deposits(solver_amount, fee_amount, false),
-        )
+
+        if resolved.is_empty() {
+            self.deposits_distribution.is_ongoing = false;
+            return PromiseOrValue::Value(());
+        }
+
+        self.transfer_deposit_tokens(resolved).into()
     }
 
     #[private]
     pub fn finish_distribute_deposits(
         &mut self,
-        solver_amount: u128,
-        fee_amount: u128,
-        is_ft: bool,
+        transfers: Vec<DepositTransfer>,
+        transfer_calls: Vec<DepositTransfer>,
+        storage_deposits: NearToken,
     ) {
         let results_count = env::promise_results_count();
+        let has_batch = !transfers.is_empty();
+        let expected = transfer_calls.len() as u64 + u64::from(has_batch);
 
-        if results_count == 1 || results_count == 2 {
-            let value_reader = if is_ft { read_ft_value } else { read_mt_value };
+        if results_count == expected {
+            // Promise with a batch of transfers to NEAR recipients.
+            if has_batch {
+                if env::promise_result_checked(0, MAX_FT_RESULT_LENGTH).is_ok() {
+                    for transfer in transfers {
+                        self.add_distributed_deposits(transfer.recipient, transfer.amount.0);
+                    }
+                } else {
+                    self.restore_storage_deposit_budget(Some(storage_deposits));
+                }
+            }
 
-            let (solver_distributed, fee_distributed) = match (solver_amount, fee_amount) {
-                (amount, fee) if amount > 0 && fee > 0 => (value_reader(0), value_reader(1)),
-                (amount, 0) if amount > 0 => (value_reader(0), 0),
-                (0, fee) if fee > 0 => (0, value_reader(0)),
-                (_, _) => (0, 0),
-            };
+            let token = transferable_token(&self.config.deposit_token);
 
-            self.deposits_distribution.solver_amount += solver_distributed;
-            self.deposits_distribution.fee_amount += fee_distributed;
+            for (index, transfer) in (u64::from(has_batch)..).zip(transfer_calls) {
+                let distributed = read_transfer_call_result(&token, index).unwrap_or_default();
+                self.add_distributed_deposits(transfer.recipient, distributed);
+            }
         } else {
             near_sdk::log!("Unexpected number of promises: {results_count}");
         }
@@ -196,47 +162,145 @@ impl AuroraLaunchpadContract {
             .deposits
             .as_ref()
             .is_none_or(|dist| {
-                let (total_solver_amount, total_fee_amount) = dist
+                let (total_solver_amount, total_recipient_amounts) = dist
                     .calculate_proportions(self.total_deposited)
                     .unwrap_or_default();
 
                 self.deposits_distribution.solver_amount == total_solver_amount
-                    && self.deposits_distribution.fee_amount == total_fee_amount
+                    && (0u8..)
+                        .zip(total_recipient_amounts)
+                        .all(|(index, amount)| self.distributed_deposits(index) == amount)
             })
     }
 
-    fn calculate_distribution(&self) -> Result<(u128, u128), &'static str> {
+    fn calculate_distribution(&self) -> Result<Vec<DepositTransfer>, &'static str> {
         let total = self.total_deposited;
+        let liquidity_amount = self.liquidity_deposit_amount()?;
+        let deposits = self.config.distribution_proportions.deposits.as_ref();
 
-        self.config
-            .distribution_proportions
-            .deposits
-            .as_ref()
-            .map_or_else(
-                || {
-                    Ok((
-                        total.saturating_sub(self.deposits_distribution.solver_amount),
-                        0,
-                    ))
-                },
-                |deposit_distribution| {
-                    deposit_distribution
-                        .calculate_proportions(total)
-                        .map(|(solver, fee)| {
-                            (
-                                solver.saturating_sub(self.deposits_distribution.solver_amount),
-                                fee.saturating_sub(self.deposits_distribution.fee_amount),
-                            )
-                        })
-                },
+        let (solver_amount, recipient_amounts) = deposits.map_or_else(
+            || Ok((total.saturating_sub(liquidity_amount), Vec::new())),
+            |deposit_distribution| deposit_distribution.calculate_proportions(total),
+        )?;
+
+        // The solver always receives the deposit tokens on the intents account.
+        let solver = DepositTransfer {
+            recipient: None,
+            account: IntentsAccount::from(
+                &self
+                    .config
+                    .distribution_proportions
+                    .solver_account_id
+                    .as_account_id(),
             )
+            .into(),
+            amount: solver_amount
+                .saturating_sub(self.deposits_distribution.solver_amount)
+                .into(),
+        };
+        let recipients = deposits
+            .map_or(&[][..], |deposit_distribution| {
+                deposit_distribution.recipients.as_slice()
+            })
+            .iter()
+            .zip(recipient_amounts);
+        let recipients =
+            (0u8..)
+                .zip(recipients)
+                .map(|(index, (recipient, amount))| DepositTransfer {
+                    recipient: Some(index),
+                    account: recipient.account.clone(),
+                    amount: amount
+                        .saturating_sub(self.distributed_deposits(index))
+                        .into(),
+                });
+
+        Ok(std::iter::once(solver)
+            .chain(recipients)
+            .filter(|transfer| transfer.amount.0 > 0)
+            .collect())
     }
-}
 
-fn read_ft_value(index: u64) -> u128 {
-    read_ft_result(index).unwrap_or_default()
-}
+    fn transfer_deposit_tokens(
+        &self,
+        transfers: Vec<(DepositTransfer, Option<NearToken>)>,
+    ) -> Promise {
+        let token = transferable_token(&self.config.deposit_token);
+        let mut batch = None;
+        let mut promises = vec![];
+        let mut batch_transfers = vec![];
+        let mut transfer_calls = vec![];
+        let mut storage_deposits = 0u128;
+
+        for (transfer, storage_deposit) in transfers {
+            match &transfer.account {
+                DistributionAccount::Intents(intents_account) => {
+                    promises.push(transfer_call(
+                        &token,
+                        self.config.intents_account_id.clone(),
+                        transfer.amount,
+                        intents_account.to_string(),
+                    ));
+                    transfer_calls.push(transfer);
+                }
+                DistributionAccount::Near(account_id) => {
+                    let promise = batch
+                        .take()
+                        .unwrap_or_else(|| Promise::new(token.account_id().clone()));
+                    batch = Some(add_transfer(
+                        &token,
+                        promise,
+                        account_id,
+                        transfer.amount,
+                        storage_deposit,
+                    ));
+                    storage_deposits = storage_deposits.saturating_add(
+                        storage_deposit.map_or(0, |deposit| deposit.as_yoctonear()),
+                    );
+                    batch_transfers.push(transfer);
+                }
+            }
+        }
+
+        // The batch (if any) goes first, then the intents calls chained with `and`.
+        batch
+            .into_iter()
+            .chain(promises)
+            .reduce(Promise::and)
+            .unwrap_or_else(|| {
+                LaunchpadError::AlreadyDistributed.panic_with("No deposit tokens to distribute")
+            })
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_DISTRIBUTE_DEPOSITS)
+                    .finish_distribute_deposits(
+                        batch_transfers,
+                        transfer_calls,
+                        NearToken::from_yoctonear(storage_deposits),
+                    ),
+            )
+    }
 
-fn read_mt_value(index: u64) -> u128 {
-    read_mt_result(index).unwrap_or_default()
+    /// Returns the number of deposit tokens distributed to the recipient with the index.
+    fn distributed_deposits(&self, index: u8) -> u128 {
+        self.deposits_distribution
+            .recipient_amounts
+            .get(usize::from(index))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn add_distributed_deposits(&mut self, recipient: Option<u8>, amount: u128) {
+        let Some(index) = recipient.map(usize::from) else {
+            self.deposits_distribution.solver_amount += amount;
+            return;
+        };
+
+        let amounts = &mut self.deposits_distribution.recipient_amounts;
+        if amounts.len() <= index {
+            amounts.resize(index + 1, 0);
+        }
+
+        amounts[index] += amount;
+    }
 }
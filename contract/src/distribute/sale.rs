@@ -1,34 +1,66 @@
-use aurora_launchpad_types::config::DistributionAccount;
+use aurora_launchpad_types::config::{DistributionAccount, SaleToken};
+use aurora_launchpad_types::error::LaunchpadError;
 use near_plugins::{Pausable, pause};
 use near_sdk::json_types::U128;
-use near_sdk::serde_json::json;
-use near_sdk::{Gas, Promise, assert_one_yocto, env, near, require};
+use near_sdk::{Gas, NearToken, Promise, PromiseOrValue, assert_one_yocto, env, near};
 
-use crate::traits::{MAX_FT_RESULT_LENGTH, ext_ft};
-use crate::{
-    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER,
-    GAS_FOR_FT_TRANSFER_CALL, ONE_YOCTO,
-};
+use crate::error::{PanicWithCode, require};
+use crate::registration::Registration;
+use crate::sale_token::{add_transfer, read_transfer_call_result, transfer_call};
+use crate::traits::{MAX_FT_RESULT_LENGTH, MAX_MT_RESULT_LENGTH};
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
 
+const GAS_FOR_CONTINUE_DISTRIBUTION: Gas = Gas::from_tgas(10);
 const GAS_FOR_FINISH_DISTRIBUTION: Gas = Gas::from_tgas(10);
-/// Max number of recipients processed per call (applies to both NEAR and Intents)
-const DISTRIBUTION_LIMIT_FOR_INTENTS: usize = 7;
+/// Max number of recipients processed per call (applies to both NEAR and Intents). It keeps
+/// the gas of the transfers within the limit of the transaction.
+const MAX_BATCH_SIZE_FOR_NEP141: u8 = 7;
+/// Max number of recipients processed per call for the NEP-245 sale token, which requires more
+/// gas for `mt_transfer_call`.
+const MAX_BATCH_SIZE_FOR_NEP245: u8 = 6;
+
+/// The progress of the distribution of the sale tokens to the solver or a stakeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct DistributionProgress {
+    /// The solver or stakeholder account.
+    pub account: DistributionAccount,
+    /// The number of tokens to be distributed to the account.
+    pub target: U128,
+    /// The number of tokens distributed to the account.
+    pub distributed: U128,
+    /// Whether the distribution to the account is in progress.
+    pub is_busy: bool,
+    /// The reason of the last failed distribution to the account, if any.
+    pub last_failure: Option<String>,
+}
 
 #[near]
 impl AuroraLaunchpadContract {
+    /// Distributes the sale tokens to the solver and stakeholders without vesting. Every call
+    /// processes up to `batch_size` accounts, which is limited by 7 for the NEP-141 sale token
+    /// and by 6 for the NEP-245 sale token. The limit is used if `batch_size` is missing.
     #[pause]
     #[payable]
-    pub fn distribute_sale_tokens(&mut self) -> Promise {
+    pub fn distribute_sale_tokens(&mut self, batch_size: Option<u8>) -> Promise {
         assert_one_yocto();
-        require!(
-            self.is_success(),
-            "Distribution can be called only if the launchpad finishes with success status"
-        );
+        require(self.is_success(), LaunchpadError::DistributionNotAllowed);
+
+        let max_batch_size = match self.config.sale_token {
+            SaleToken::Nep141(_) => MAX_BATCH_SIZE_FOR_NEP141,
+            SaleToken::Nep245(_) => MAX_BATCH_SIZE_FOR_NEP245,
+        };
+        let batch_size = batch_size.unwrap_or(max_batch_size);
+        if batch_size == 0 || batch_size > max_batch_size {
+            LaunchpadError::WrongBatchSize.panic_with(&format!(
+                "The batch size must be between 1 and {max_batch_size}"
+            ));
+        }
 
-        let distributions = self.get_filtered_distributions();
-        require!(
+        let distributions = self.get_filtered_distributions(usize::from(batch_size));
+        require(
             !distributions.is_empty(),
-            "Tokens have been already distributed"
+            LaunchpadError::AlreadyDistributed,
         );
         // Mark accounts as busy to avoid double distribution
         for (account, _) in &distributions {
@@ -40,67 +72,75 @@ impl AuroraLaunchpadContract {
             *busy = true;
         }
 
-        let (maybe_batch, promises, distributions) = distributions.iter().fold(
-            (None, vec![], Distributions::default()),
-            |(mut maybe_batch, mut promises, mut distributions), (account, amount)| {
-                match account {
-                    DistributionAccount::Intents(intents_account) => {
-                        promises.push(
-                            ext_ft::ext(self.config.sale_token_account_id.clone())
-                                .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
-                                .with_attached_deposit(ONE_YOCTO)
-                                .ft_transfer_call(
-                                    self.config.intents_account_id.clone(),
-                                    *amount,
-                                    intents_account.to_string(),
-                                    None,
-                                ),
-                        );
-                        distributions.add_ft_transfer_call(account.clone(), *amount);
-                    }
-                    DistributionAccount::Near(near_account) => {
-                        let batch = maybe_batch
-                            .unwrap_or_else(|| {
-                                Promise::new(self.config.sale_token_account_id.clone())
-                            })
-                            .function_call(
-                                "ft_transfer".to_string(),
-                                json!({
-                                    "receiver_id": near_account,
-                                    "amount": amount,
-                                })
-                                .to_string()
-                                .into_bytes(),
-                                ONE_YOCTO,
-                                GAS_FOR_FT_TRANSFER,
-                            );
-                        maybe_batch = Some(batch);
-
-                        distributions.add_ft_transfer(account.clone(), *amount);
-                    }
+        let near_accounts = match &self.config.sale_token {
+            SaleToken::Nep141(token_account_id) => distributions
+                .iter()
+                .filter_map(|(account, _)| match account {
+                    DistributionAccount::Near(account_id) => Some(account_id),
+                    DistributionAccount::Intents(_) => None,
+                })
+                .map(|account_id| Self::check_registration(token_account_id, account_id))
+                .reduce(Promise::and),
+            SaleToken::Nep245(_) => None,
+        };
+
+        // NEAR recipients are registered in the NEP-141 sale token before the transfers if needed.
+        if let Some(registrations) = near_accounts {
+            return registrations.then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_CONTINUE_DISTRIBUTION)
+                    .continue_distribution(distributions),
+            );
+        }
+
+        let transfers = distributions
+            .into_iter()
+            .map(|(account, amount)| (account, amount, None))
+            .collect();
+
+        self.transfer_sale_tokens(transfers)
+    }
+
+    /// Transfers the sale tokens after resolving the registrations of NEAR recipients. Recipients
+    /// which couldn't be registered are released to be retried by the next distribution.
+    #[private]
+    pub fn continue_distribution(
+        &mut self,
+        distributions: Vec<(DistributionAccount, U128)>,
+    ) -> PromiseOrValue<()> {
+        let mut index = 0;
+        let mut reserved = 0;
+        let mut transfers = Vec::with_capacity(distributions.len());
+
+        for (account, amount) in distributions {
+            let storage_deposit = if let DistributionAccount::Near(account_id) = &account {
+                let registration = self.resolve_registration(account_id, index, reserved);
+                index += 1;
+
+                if matches!(registration, Registration::Unfunded) {
+                    self.set_distribution_failure(
+                        &account,
+                        Some("No funds to register the account in the token".to_string()),
+                    );
+                    self.release_accounts(vec![(account, amount)]);
+                    continue;
                 }
 
-                (maybe_batch, promises, distributions)
-            },
-        );
+                let storage_deposit = registration.storage_deposit();
+                reserved += storage_deposit.map_or(0, |deposit| deposit.as_yoctonear());
+                storage_deposit
+            } else {
+                None
+            };
 
-        // Combine promises preserving order: batch (if any) first, then intents calls chained with `and`.
-        let root = if let Some(batch) = maybe_batch {
-            promises.into_iter().fold(batch, Promise::and)
-        } else {
-            // There must be at least one intents promise here because distributions was not empty.
-            let mut iter = promises.into_iter();
-            let first = iter
-                .next()
-                .unwrap_or_else(|| env::panic_str("No batch nor promises"));
-            iter.fold(first, Promise::and)
-        };
+            transfers.push((account, amount, storage_deposit));
+        }
 
-        root.then(
-            Self::ext(env::current_account_id())
-                .with_static_gas(GAS_FOR_FINISH_DISTRIBUTION)
-                .finish_distribution(distributions),
-        )
+        if transfers.is_empty() {
+            return PromiseOrValue::Value(());
+        }
+
+        self.transfer_sale_tokens(transfers).into()
     }
 
     #[private]
@@ -110,15 +150,21 @@ impl AuroraLaunchpadContract {
         let Distributions {
             ft_transfers,
             ft_transfer_calls,
+            storage_deposits,
         } = distributions;
 
         let has_batch = !ft_transfers.is_empty();
         let expected = ft_transfer_calls.len() as u64 + u64::from(has_batch);
 
         if expected != promises_count {
-            near_sdk::log!(
-                "Mismatched number of promise results, expected {expected}, got {promises_count}",
+            let reason = format!(
+                "Mismatched number of promise results, expected {expected}, got {promises_count}"
             );
+            near_sdk::log!("{reason}");
+
+            for (account, _) in ft_transfers.iter().chain(&ft_transfer_calls) {
+                self.set_distribution_failure(account, Some(reason.clone()));
+            }
 
             self.release_accounts(ft_transfers);
             self.release_accounts(ft_transfer_calls);
@@ -128,14 +174,23 @@ impl AuroraLaunchpadContract {
 
         // Promise with a batch of ft_transfers.
         if has_batch {
-            let assignment_fn =
-                get_assignment_fn(env::promise_result_checked(0, MAX_FT_RESULT_LENGTH).is_ok());
+            let result = env::promise_result_checked(0, MAX_FT_RESULT_LENGTH);
+            let assignment_fn = get_assignment_fn(result.is_ok());
+
+            if result.is_err() {
+                self.restore_storage_deposit_budget(Some(NearToken::from_yoctonear(
+                    storage_deposits.0,
+                )));
+            }
+            let failure = result.err().map(|e| format!("The transfer failed: {e}"));
 
             for (account, distributed_amount) in ft_transfers {
                 if let Some((amount, busy)) = self.distributed_accounts.get_mut(&account) {
                     assignment_fn(amount, distributed_amount.0);
                     *busy = false;
                 }
+
+                self.set_distribution_failure(&account, failure.clone());
             }
         }
 
@@ -144,24 +199,143 @@ impl AuroraLaunchpadContract {
         for (i, (account, distributed_amount)) in ft_transfer_calls.into_iter().enumerate() {
             let promise_index = i as u64 + start_index;
 
-            if let Some((amount, busy)) = self.distributed_accounts.get_mut(&account) {
-                if let Ok(bytes) = env::promise_result_checked(promise_index, MAX_FT_RESULT_LENGTH)
+            let failure = if let Some((amount, busy)) = self.distributed_accounts.get_mut(&account)
+            {
+                let failure = match env::promise_result_checked(promise_index, MAX_MT_RESULT_LENGTH)
                 {
-                    let used_tokens: U128 = near_sdk::serde_json::from_slice(&bytes)
-                        .unwrap_or_else(|_| {
-                            near_sdk::log!("Failed to parse ft_transfer_call result");
-                            distributed_amount
-                        });
+                    Ok(_) => {
+                        let used_tokens =
+                            read_transfer_call_result(&self.config.sale_token, promise_index)
+                                .unwrap_or_else(|| {
+                                    near_sdk::log!("Failed to parse the transfer call result");
+                                    distributed_amount.0
+                                });
 
-                    *amount += used_tokens.0;
-                }
+                        *amount += used_tokens;
+
+                        (used_tokens < distributed_amount.0).then(|| {
+                            format!(
+                                "{} of {} tokens were refunded",
+                                distributed_amount.0 - used_tokens,
+                                distributed_amount.0
+                            )
+                        })
+                    }
+                    Err(e) => Some(format!("The transfer failed: {e}")),
+                };
 
                 *busy = false;
-            }
+                failure
+            } else {
+                None
+            };
+
+            self.set_distribution_failure(&account, failure);
         }
     }
 
-    fn get_filtered_distributions(&self) -> Vec<(DistributionAccount, U128)> {
+    /// Returns the progress of the distribution of the sale tokens to the solver and
+    /// stakeholders without vesting.
+    pub fn get_distribution_progress(&self) -> Vec<DistributionProgress> {
+        self.distribution_targets()
+            .map(|(account, target)| {
+                let (distributed, is_busy) = self
+                    .distributed_accounts
+                    .get(account)
+                    .copied()
+                    .unwrap_or_default();
+
+                DistributionProgress {
+                    account: account.clone(),
+                    target: *target,
+                    distributed: U128(distributed),
+                    is_busy,
+                    last_failure: self.distribution_failures.get(account).cloned(),
+                }
+            })
+            .collect()
+    }
+
+    fn transfer_sale_tokens(
+        &self,
+        transfers: Vec<(DistributionAccount, U128, Option<NearToken>)>,
+    ) -> Promise {
+        let (maybe_batch, promises, distributions) = transfers.into_iter().fold(
+            (None, vec![], Distributions::default()),
+            |(maybe_batch, mut promises, mut distributions), (account, amount, storage_deposit)| {
+                let maybe_batch = match &account {
+                    DistributionAccount::Intents(intents_account) => {
+                        promises.push(transfer_call(
+                            &self.config.sale_token,
+                            self.config.intents_account_id.clone(),
+                            amount,
+                            intents_account.to_string(),
+                        ));
+                        distributions.add_ft_transfer_call(account, amount);
+
+                        maybe_batch
+                    }
+                    DistributionAccount::Near(near_account) => {
+                        let batch = maybe_batch.unwrap_or_else(|| {
+                            Promise::new(self.config.sale_token.account_id().clone())
+                        });
+                        let batch = add_transfer(
+                            &self.config.sale_token,
+                            batch,
+                            near_account,
+                            amount,
+                            storage_deposit,
+                        );
+                        distributions.add_ft_transfer(account, amount, storage_deposit);
+
+                        Some(batch)
+                    }
+                };
+
+                (maybe_batch, promises, distributions)
+            },
+        );
+
+        // Combine promises preserving order: batch (if any) first, then intents calls chained with `and`.
+        let root = if let Some(batch) = maybe_batch {
+            promises.into_iter().fold(batch, Promise::and)
+        } else {
+            // There must be at least one intents promise here because distributions was not empty.
+            let mut iter = promises.into_iter();
+            let first = iter
+                .next()
+                .unwrap_or_else(|| LaunchpadError::Internal.panic_with("No batch nor promises"));
+            iter.fold(first, Promise::and)
+        };
+
+        root.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_FINISH_DISTRIBUTION)
+                .finish_distribution(distributions),
+        )
+    }
+
+    fn get_filtered_distributions(&self, batch_size: usize) -> Vec<(DistributionAccount, U128)> {
+        self.distribution_targets()
+            .filter_map(|(account, amount)| {
+                self.distributed_accounts.get(account).map_or(
+                    Some((account, *amount)),
+                    |(distributed_amount, busy)| {
+                        if *distributed_amount < amount.0 && !busy {
+                            Some((account, U128(amount.0 - *distributed_amount)))
+                        } else {
+                            None
+                        }
+                    },
+                )
+            })
+            .take(batch_size)
+            .map(|(account, amount)| (account.clone(), amount))
+            .collect()
+    }
+
+    /// Returns the solver and stakeholders without vesting with their non-zero allocations.
+    fn distribution_targets(&self) -> impl Iterator<Item = (&DistributionAccount, &U128)> {
         std::iter::once((
             &self.config.distribution_proportions.solver_account_id,
             &self.config.distribution_proportions.solver_allocation,
@@ -180,21 +354,14 @@ impl AuroraLaunchpadContract {
                 }),
         )
         .filter(|(_, amount)| amount.0 > 0)
-        .filter_map(|(account, amount)| {
-            self.distributed_accounts.get(account).map_or(
-                Some((account, *amount)),
-                |(distributed_amount, busy)| {
-                    if *distributed_amount < amount.0 && !busy {
-                        Some((account, U128(amount.0 - *distributed_amount)))
-                    } else {
-                        None
-                    }
-                },
-            )
-        })
-        .take(DISTRIBUTION_LIMIT_FOR_INTENTS)
-        .map(|(account, amount)| (account.clone(), amount))
-        .collect()
+    }
+
+    fn set_distribution_failure(&mut self, account: &DistributionAccount, reason: Option<String>) {
+        if let Some(reason) = reason {
+            self.distribution_failures.insert(account.clone(), reason);
+        } else {
+            self.distribution_failures.remove(account);
+        }
     }
 
     fn release_accounts(&mut self, accounts: Vec<(DistributionAccount, U128)>) {
@@ -211,11 +378,25 @@ impl AuroraLaunchpadContract {
 pub struct Distributions {
     ft_transfers: Vec<(DistributionAccount, U128)>,
     ft_transfer_calls: Vec<(DistributionAccount, U128)>,
+    /// The total deposit attached to `storage_deposit` calls in the batch of transfers.
+    storage_deposits: U128,
 }
 
 impl Distributions {
-    fn add_ft_transfer(&mut self, account: DistributionAccount, amount: U128) {
+    fn add_ft_transfer(
+        &mut self,
+        account: DistributionAccount,
+        amount: U128,
+        storage_deposit: Option<NearToken>,
+    ) {
         self.ft_transfers.push((account, amount));
+
+        if let Some(deposit) = storage_deposit {
+            self.storage_deposits.0 = self
+                .storage_deposits
+                .0
+                .saturating_add(deposit.as_yoctonear());
+        }
     }
 
     fn add_ft_transfer_call(&mut self, account: DistributionAccount, amount: U128) {
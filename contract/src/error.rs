@@ -0,0 +1,36 @@
+use aurora_launchpad_types::error::LaunchpadError;
+use near_sdk::env;
+
+/// Panics of the contract in the `ERR_<CODE>: message` format.
+pub(crate) trait PanicWithCode {
+    /// Panics with the default message of the error.
+    fn panic(self) -> !;
+
+    /// Panics with the given message instead of the default one, e.g. with the details of
+    /// the failure.
+    fn panic_with(self, message: &str) -> !;
+}
+
+impl PanicWithCode for LaunchpadError {
+    fn panic(self) -> ! {
+        env::panic_str(&self.to_string())
+    }
+
+    fn panic_with(self, message: &str) -> ! {
+        env::panic_str(&self.with_message(message))
+    }
+}
+
+/// Panics with the error if the condition isn't met.
+pub(crate) fn require(condition: bool, error: LaunchpadError) {
+    if !condition {
+        error.panic();
+    }
+}
+
+/// Panics with the error and the given message if the condition isn't met.
+pub(crate) fn require_with(condition: bool, error: LaunchpadError, message: &str) {
+    if !condition {
+        error.panic_with(message);
+    }
+}
@@ -1,22 +1,41 @@
 use aurora_launchpad_types::admin_withdraw::WithdrawnUnsoldTokens;
 use aurora_launchpad_types::config::{DistributionAccount, LaunchpadConfig};
-use aurora_launchpad_types::distribution::DepositsDistribution;
+use aurora_launchpad_types::distribution::{DepositsDistribution, LiquiditySeeding};
+use aurora_launchpad_types::error::LaunchpadError;
 use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
 use near_plugins::{AccessControlRole, AccessControllable, Pausable, Upgradable, access_control};
 use near_sdk::borsh::BorshDeserialize;
 use near_sdk::store::{LookupMap, LookupSet};
 use near_sdk::{AccountId, Gas, NearToken, PanicOnDefault, env, near};
 
+use crate::bundle::Bundle;
+use crate::cleanup::Cleanup;
 use crate::discount::DiscountState;
+use crate::error::PanicWithCode;
+use crate::registration::RecipientRegistration;
 use crate::storage_key::StorageKey;
+use crate::storage_management::StorageManagement;
 
+pub use crate::discount::DiscountPhases;
+
+mod account;
 mod admin;
+mod bundle;
 mod claim;
+mod cleanup;
 mod deposit;
 mod discount;
 mod distribute;
-mod mechanics;
+mod error;
+mod liquidity;
+pub mod mechanics;
+mod migration;
+mod registration;
+mod sale_token;
+mod simulation;
+mod stats;
 mod storage_key;
+mod storage_management;
 #[cfg(test)]
 mod tests;
 mod traits;
@@ -36,13 +55,14 @@ enum Role {
     Admin,
     PauseManager,
     UnpauseManager,
+    Upgrader,
 }
 
 #[derive(PanicOnDefault, Pausable, Upgradable)]
 #[access_control(role_type(Role))]
 #[upgradable(access_control_roles(
-    code_stagers(Role::Admin),
-    code_deployers(Role::Admin),
+    code_stagers(Role::Admin, Role::Upgrader),
+    code_deployers(Role::Admin, Role::Upgrader),
     duration_initializers(Role::Admin),
     duration_update_stagers(Role::Admin),
     duration_update_appliers(Role::Admin),
@@ -67,13 +87,16 @@ pub struct AuroraLaunchpadContract {
     pub vestings: LookupMap<IntentsAccount, u128>,
     /// Individual vesting claimed amounts for each stakeholder
     pub individual_vesting_claimed: LookupMap<DistributionAccount, u128>,
-    /// Flag indicating whether the sale token was transferred to the contract
+    /// Flag indicating whether the sale token and all bundled sale tokens were transferred to
+    /// the contract
     pub is_sale_token_set: bool,
     /// Flag indicating whether the launchpad is locked or not.
     is_locked: bool,
     /// Already distributed accounts and their fully or partly distributed amounts
     /// and statuses to prevent double distributions.
     pub distributed_accounts: LookupMap<DistributionAccount, (u128, bool)>,
+    /// The reasons of the last failed distributions of the sale tokens per account.
+    distribution_failures: LookupMap<DistributionAccount, String>,
     /// Set of accounts that have withdrawal in progress in the locked state.
     pub locked_withdraw: LookupSet<IntentsAccount>,
     /// Deposits distribution to solver and fee accounts, if any.
@@ -82,6 +105,16 @@ pub struct AuroraLaunchpadContract {
     withdrawn_unsold_tokens: WithdrawnUnsoldTokens,
     /// The discounts state includes state for every discount phase.
     discount_state: Option<DiscountState>,
+    /// Registration of NEAR recipients in the tokens transferred by the launchpad.
+    registration: RecipientRegistration,
+    /// NEP-145 storage management of the participants.
+    storage: StorageManagement,
+    /// The state of the cleanup after the sale has concluded.
+    cleanup: Cleanup,
+    /// The state of the sale tokens bundled with the main sale token.
+    bundle: Bundle,
+    /// The state of the liquidity seeding of the DEX pool after the sale.
+    liquidity: LiquiditySeeding,
 }
 
 #[near]
@@ -91,11 +124,12 @@ impl AuroraLaunchpadContract {
     #[must_use]
     #[allow(clippy::use_self)]
     pub fn new(config: LaunchpadConfig, admin: Option<AccountId>) -> Self {
-        config
-            .validate()
-            .unwrap_or_else(|err| env::panic_str(&format!("Invalid config: {err}")));
+        config.validate_all().unwrap_or_else(|errors| {
+            LaunchpadError::InvalidConfig.panic_with(&format!("Invalid config: {errors}"))
+        });
 
         let discount_state = config.discounts.as_ref().map(DiscountState::init);
+        let bundle = Bundle::new(false, config.bundled_sale_tokens.len());
         let mut contract = Self {
             config,
             participants_count: 0,
@@ -107,15 +141,32 @@ impl AuroraLaunchpadContract {
             total_sold_tokens: 0,
             is_locked: false,
             distributed_accounts: LookupMap::new(StorageKey::DistributedAccounts),
+            distribution_failures: LookupMap::new(StorageKey::DistributionFailures),
             locked_withdraw: LookupSet::new(StorageKey::LockedWithdraw),
             deposits_distribution: DepositsDistribution::default(),
             withdrawn_unsold_tokens: WithdrawnUnsoldTokens::default(),
             discount_state,
+            registration: RecipientRegistration::default(),
+            storage: StorageManagement::default(),
+            cleanup: Cleanup::default(),
+            bundle,
+            liquidity: LiquiditySeeding::default(),
         };
 
         let admin_account_id = admin.unwrap_or_else(env::signer_account_id);
         contract.grant_roles(&admin_account_id);
 
+        // The factory which created the launchpad is allowed to upgrade it. The launchpad
+        // is deployed by the factory on its direct subaccount.
+        let predecessor_account_id = env::predecessor_account_id();
+        if is_direct_subaccount_of(&env::current_account_id(), &predecessor_account_id) {
+            contract
+                .acl_get_or_init()
+                .grant_role_unchecked(Role::Upgrader, &predecessor_account_id);
+        }
+
+        migration::write_state_version();
+
         contract
     }
 
@@ -132,3 +183,11 @@ impl AuroraLaunchpadContract {
         acl.grant_role_unchecked(Role::UnpauseManager, admin_account_id);
     }
 }
+
+fn is_direct_subaccount_of(account_id: &AccountId, parent_account_id: &AccountId) -> bool {
+    account_id
+        .as_str()
+        .strip_suffix(parent_account_id.as_str())
+        .and_then(|name| name.strip_suffix('.'))
+        .is_some_and(|name| !name.is_empty() && !name.contains('.'))
+}
@@ -0,0 +1,361 @@
+use aurora_launchpad_types::config::{DepositToken, LiquidityProportion, SaleToken};
+use aurora_launchpad_types::distribution::LiquiditySeeding;
+use aurora_launchpad_types::error::LaunchpadError;
+use near_plugins::{Pausable, pause};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::{AccountId, Gas, NearToken, Promise, PromiseOrValue, assert_one_yocto, env, near};
+
+use crate::error::{PanicWithCode, require};
+use crate::mechanics::liquidity::liquidity_amounts;
+use crate::traits::{ext_dex, ext_ft, read_ft_result, read_pool_id_result};
+use crate::{
+    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER_CALL, ONE_YOCTO,
+};
+
+/// The NEAR attached to the storage deposit of the launchpad on the DEX.
+const NEAR_FOR_DEX_STORAGE: NearToken = NearToken::from_millinear(100);
+/// The NEAR attached to the creation of the pool on the DEX.
+const NEAR_FOR_POOL_CREATION: NearToken = NearToken::from_millinear(100);
+
+const GAS_FOR_STORAGE_DEPOSIT: Gas = Gas::from_tgas(5);
+const GAS_FOR_REGISTER_TOKENS: Gas = Gas::from_tgas(10);
+const GAS_FOR_ADD_SIMPLE_POOL: Gas = Gas::from_tgas(10);
+const GAS_FOR_ADD_LIQUIDITY: Gas = Gas::from_tgas(20);
+const GAS_FOR_MFT_TRANSFER: Gas = Gas::from_tgas(10);
+const GAS_FOR_FINISH_SHARES_TRANSFER: Gas = Gas::from_tgas(5);
+const GAS_FOR_FINISH_ADD_LIQUIDITY: Gas = Gas::from_tgas(25);
+const GAS_FOR_FINISH_TOKENS_TRANSFER: Gas = Gas::from_tgas(55);
+const GAS_FOR_FINISH_POOL_CREATION: Gas = Gas::from_tgas(135);
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Seeds the DEX pool with a part of the deposited tokens and the sale tokens at the sale
+    /// price. The seeding consists of the pool creation, the transfer of the tokens to the DEX,
+    /// adding the liquidity and the transfer of the LP shares to the shares receiver. Every step
+    /// continues with the next one, and a failed step could be retried by calling the method
+    /// again. The pool creation requires `0.2` NEAR attached, and the excess is refunded. The
+    /// other steps require 1 yoctoNEAR.
+    #[pause]
+    #[payable]
+    pub fn seed_liquidity(&mut self) -> Promise {
+        require(self.is_success(), LaunchpadError::LiquidityNotAllowed);
+
+        let Some(liquidity) = self.config.distribution_proportions.liquidity.clone() else {
+            LaunchpadError::LiquidityNotConfigured.panic();
+        };
+
+        require(!self.liquidity.is_ongoing, LaunchpadError::LiquidityOngoing);
+
+        if self.liquidity.pool_id.is_none() {
+            let required = NEAR_FOR_DEX_STORAGE.saturating_add(NEAR_FOR_POOL_CREATION);
+            let attached = env::attached_deposit();
+            require(attached >= required, LaunchpadError::NotEnoughNearForPool);
+
+            let refund = attached.saturating_sub(required);
+            if !refund.is_zero() {
+                let _ = Promise::new(env::predecessor_account_id()).transfer(refund);
+            }
+        } else {
+            assert_one_yocto();
+        }
+
+        let promise = self
+            .next_liquidity_step(&liquidity)
+            .unwrap_or_else(|e| env::panic_str(&e))
+            .unwrap_or_else(|| LaunchpadError::LiquidityAlreadySeeded.panic());
+        self.liquidity.is_ongoing = true;
+
+        promise
+    }
+
+    /// Returns the state of the liquidity seeding.
+    pub fn get_liquidity_seeding(&self) -> LiquiditySeeding {
+        self.liquidity.clone()
+    }
+
+    /// Returns `true` if the liquidity is added to the pool and the LP shares are transferred
+    /// to the shares receiver, or if the liquidity seeding isn't configured.
+    pub fn is_liquidity_seeded(&self) -> bool {
+        self.config
+            .distribution_proportions
+            .liquidity
+            .as_ref()
+            .is_none_or(|liquidity| {
+                self.liquidity.is_added
+                    && (liquidity.shares_receiver_id.is_none()
+                        || self.liquidity.is_shares_transferred)
+            })
+    }
+
+    #[private]
+    pub fn finish_pool_creation(&mut self) -> PromiseOrValue<()> {
+        match read_pool_id_result(0) {
+            Some(pool_id) => {
+                near_sdk::log!("The pool {pool_id} has been created");
+                self.liquidity.pool_id = Some(pool_id);
+                self.continue_liquidity_seeding()
+            }
+            None => self.stop_liquidity_seeding("Creation of the pool failed"),
+        }
+    }
+
+    #[private]
+    pub fn finish_liquidity_tokens_transfer(
+        &mut self,
+        deposit_amount: U128,
+        sale_amount: U128,
+    ) -> PromiseOrValue<()> {
+        let mut index = 0;
+        let mut read_transferred = |amount: U128| {
+            if amount.0 == 0 {
+                return 0;
+            }
+
+            let transferred = read_ft_result(index).unwrap_or_default();
+            index += 1;
+            transferred
+        };
+
+        let deposit_transferred = read_transferred(deposit_amount);
+        let sale_transferred = read_transferred(sale_amount);
+
+        self.liquidity.deposit_amount.0 += deposit_transferred;
+        self.liquidity.sale_amount.0 += sale_transferred;
+
+        if deposit_transferred == deposit_amount.0 && sale_transferred == sale_amount.0 {
+            self.continue_liquidity_seeding()
+        } else {
+            self.stop_liquidity_seeding("Transfer of the tokens to the DEX failed")
+        }
+    }
+
+    #[private]
+    pub fn finish_add_liquidity(&mut self) -> PromiseOrValue<()> {
+        // The number of shares is a JSON-quoted `U128` like an amount of NEP-141 tokens.
+        match read_ft_result(0) {
+            Some(shares) => {
+                near_sdk::log!("{shares} LP shares have been received for the added liquidity");
+                self.liquidity.shares = shares.into();
+                self.liquidity.is_added = true;
+                self.continue_liquidity_seeding()
+            }
+            None => self.stop_liquidity_seeding("Adding the liquidity failed"),
+        }
+    }
+
+    #[private]
+    pub fn finish_shares_transfer(&mut self) {
+        if env::promise_result_checked(0, 0).is_ok() {
+            self.liquidity.is_shares_transferred = true;
+            self.liquidity.last_failure = None;
+            self.liquidity.is_ongoing = false;
+            near_sdk::log!("LP shares have been transferred to the shares receiver");
+        } else {
+            let _ = self.stop_liquidity_seeding("Transfer of the LP shares failed");
+        }
+    }
+}
+
+impl AuroraLaunchpadContract {
+    /// Returns the number of deposit tokens added to the pool, or zero if the liquidity
+    /// seeding isn't configured.
+    pub(crate) fn liquidity_deposit_amount(&self) -> Result<u128, &'static str> {
+        self.config
+            .distribution_proportions
+            .liquidity
+            .as_ref()
+            .map_or(Ok(0), |liquidity| {
+                liquidity_amounts(liquidity, self.total_deposited, &self.config)
+                    .map(|(deposit_amount, _)| deposit_amount)
+            })
+    }
+
+    /// Returns the number of sale tokens of the liquidity allocation which aren't added to
+    /// the pool. The tokens are considered unsold once the liquidity is added.
+    pub(crate) fn unused_liquidity_allocation(&self) -> u128 {
+        match &self.config.distribution_proportions.liquidity {
+            Some(liquidity) if self.liquidity.is_added => liquidity
+                .sale_allocation
+                .0
+                .saturating_sub(self.liquidity.sale_amount.0),
+            _ => 0,
+        }
+    }
+
+    /// Continues the liquidity seeding with the next step from a callback. The seeding is
+    /// stopped instead of panicking on errors, so it could be retried.
+    pub(crate) fn continue_liquidity_seeding(&mut self) -> PromiseOrValue<()> {
+        self.liquidity.last_failure = None;
+
+        let Some(liquidity) = self.config.distribution_proportions.liquidity.clone() else {
+            return self
+                .stop_liquidity_seeding(&LaunchpadError::LiquidityNotConfigured.to_string());
+        };
+
+        match self.next_liquidity_step(&liquidity) {
+            Ok(Some(promise)) => PromiseOrValue::Promise(promise),
+            Ok(None) => {
+                self.liquidity.is_ongoing = false;
+                near_sdk::log!("The liquidity has been seeded successfully");
+                PromiseOrValue::Value(())
+            }
+            Err(e) => self.stop_liquidity_seeding(&e),
+        }
+    }
+
+    /// Stops the liquidity seeding and records the reason, so the failed step could be found
+    /// with `get_liquidity_seeding` before retrying.
+    fn stop_liquidity_seeding(&mut self, reason: &str) -> PromiseOrValue<()> {
+        near_sdk::log!("{reason}");
+        self.liquidity.last_failure = Some(reason.to_string());
+        self.liquidity.is_ongoing = false;
+        PromiseOrValue::Value(())
+    }
+
+    /// Returns the promise of the next step of the liquidity seeding, or `None` if the liquidity
+    /// is seeded. Returns the error message with the code if the step couldn't be made.
+    fn next_liquidity_step(
+        &self,
+        liquidity: &LiquidityProportion,
+    ) -> Result<Option<Promise>, String> {
+        let (DepositToken::Nep141(deposit_token), SaleToken::Nep141(sale_token)) =
+            (&self.config.deposit_token, &self.config.sale_token)
+        else {
+            return Err(LaunchpadError::LiquidityUnsupportedTokens.to_string());
+        };
+        let tokens = vec![deposit_token.clone(), sale_token.clone()];
+
+        let Some(pool_id) = self.liquidity.pool_id else {
+            return Ok(Some(Self::create_pool(liquidity, tokens)));
+        };
+
+        if !self.liquidity.is_added {
+            let (deposit_amount, sale_amount) =
+                liquidity_amounts(liquidity, self.total_deposited, &self.config)
+                    .map_err(|e| LaunchpadError::LiquidityFailed.with_message(e))?;
+            let deposit_amount = deposit_amount.saturating_sub(self.liquidity.deposit_amount.0);
+            let sale_amount = sale_amount.saturating_sub(self.liquidity.sale_amount.0);
+
+            return Ok(Some(if deposit_amount > 0 || sale_amount > 0 {
+                Self::transfer_liquidity_tokens(
+                    &liquidity.dex_account_id,
+                    deposit_token,
+                    sale_token,
+                    deposit_amount,
+                    sale_amount,
+                )
+            } else {
+                self.add_liquidity(&liquidity.dex_account_id, pool_id)
+            }));
+        }
+
+        Ok(match &liquidity.shares_receiver_id {
+            Some(receiver_id) if !self.liquidity.is_shares_transferred => {
+                Some(self.transfer_shares(&liquidity.dex_account_id, pool_id, receiver_id))
+            }
+            _ => None,
+        })
+    }
+
+    fn create_pool(liquidity: &LiquidityProportion, tokens: Vec<AccountId>) -> Promise {
+        Promise::new(liquidity.dex_account_id.clone())
+            .function_call(
+                "storage_deposit".to_string(),
+                json!({ "account_id": env::current_account_id() })
+                    .to_string()
+                    .into_bytes(),
+                NEAR_FOR_DEX_STORAGE,
+                GAS_FOR_STORAGE_DEPOSIT,
+            )
+            .function_call(
+                "register_tokens".to_string(),
+                json!({ "token_ids": tokens }).to_string().into_bytes(),
+                ONE_YOCTO,
+                GAS_FOR_REGISTER_TOKENS,
+            )
+            .function_call(
+                "add_simple_pool".to_string(),
+                json!({ "tokens": tokens, "fee": liquidity.pool_fee })
+                    .to_string()
+                    .into_bytes(),
+                NEAR_FOR_POOL_CREATION,
+                GAS_FOR_ADD_SIMPLE_POOL,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_POOL_CREATION)
+                    .finish_pool_creation(),
+            )
+    }
+
+    fn transfer_liquidity_tokens(
+        dex_account_id: &AccountId,
+        deposit_token: &AccountId,
+        sale_token: &AccountId,
+        deposit_amount: u128,
+        sale_amount: u128,
+    ) -> Promise {
+        let transfer = |token_account_id: &AccountId, amount: u128| {
+            (amount > 0).then(|| {
+                ext_ft::ext(token_account_id.clone())
+                    .with_attached_deposit(ONE_YOCTO)
+                    .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
+                    .ft_transfer_call(dex_account_id.clone(), amount.into(), String::new(), None)
+            })
+        };
+
+        match (
+            transfer(deposit_token, deposit_amount),
+            transfer(sale_token, sale_amount),
+        ) {
+            (Some(deposit), Some(sale)) => deposit.and(sale),
+            (Some(deposit), None) => deposit,
+            (None, Some(sale)) => sale,
+            (None, None) => LaunchpadError::Internal.panic_with("No tokens to transfer to the DEX"),
+        }
+        .then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_FINISH_TOKENS_TRANSFER)
+                .finish_liquidity_tokens_transfer(deposit_amount.into(), sale_amount.into()),
+        )
+    }
+
+    fn add_liquidity(&self, dex_account_id: &AccountId, pool_id: u64) -> Promise {
+        ext_dex::ext(dex_account_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_ADD_LIQUIDITY)
+            .add_liquidity(
+                pool_id,
+                vec![self.liquidity.deposit_amount, self.liquidity.sale_amount],
+                None,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_ADD_LIQUIDITY)
+                    .finish_add_liquidity(),
+            )
+    }
+
+    fn transfer_shares(
+        &self,
+        dex_account_id: &AccountId,
+        pool_id: u64,
+        receiver_id: &AccountId,
+    ) -> Promise {
+        ext_dex::ext(dex_account_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_MFT_TRANSFER)
+            .mft_transfer(
+                format!(":{pool_id}"),
+                receiver_id.clone(),
+                self.liquidity.shares,
+                None,
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_SHARES_TRANSFER)
+                    .finish_shares_transfer(),
+            )
+    }
+}
@@ -1,9 +1,15 @@
 use alloy_primitives::ruint::aliases::U256;
 use aurora_launchpad_types::InvestmentAmount;
-use aurora_launchpad_types::config::{LaunchpadConfig, Mechanics, VestingSchedule, VestingScheme};
+use aurora_launchpad_types::config::{
+    BundledSaleToken, LaunchpadConfig, Mechanics, VestingSchedule,
+};
 use aurora_launchpad_types::utils::to_u128;
 
 /// Calculates the total assets for user allocation based on the mechanics and vesting schedule.
+///
+/// # Errors
+///
+/// Returns an error if the allocation overflows.
 pub fn user_allocation(
     weight: u128,
     total_sold_tokens: u128,
@@ -27,6 +33,10 @@ pub fn user_allocation(
 
 /// Calculates the available assets for claim based on the mechanics and vesting schedule.
 /// Notice that the function doesn't subtract already claimed tokens.
+///
+/// # Errors
+///
+/// Returns an error if the allocation or the unlocked amount overflows.
 pub fn available_for_claim(
     investment: &InvestmentAmount,
     total_sold_tokens: u128,
@@ -43,61 +53,80 @@ pub fn available_for_claim(
     )
 }
 
+/// Calculates the available assets of the bundled sale token for claim based on the allocation
+/// of the main sale token and the vesting schedule of the bundled token.
+/// Notice that the function doesn't subtract already claimed tokens.
+///
+/// # Errors
+///
+/// Returns an error if the bundled allocation or the unlocked amount overflows.
+pub fn available_for_bundled_claim(
+    sale_allocation: u128,
+    bundled: &BundledSaleToken,
+    vesting_start: u64,
+    timestamp: u64,
+) -> Result<u128, &'static str> {
+    available_for_individual_vesting_claim(
+        bundled.allocation(sale_allocation)?,
+        bundled.vesting_schedule.as_ref(),
+        vesting_start,
+        timestamp,
+    )
+}
+
 /// Returns the available assets for individual vesting claim based on the allocation and vesting
 /// schedule. Notice that the function doesn't subtract already claimed tokens.
+///
+/// # Errors
+///
+/// Returns an error if the unlocked amount overflows.
 pub fn available_for_individual_vesting_claim(
     allocation: u128,
     vesting: Option<&VestingSchedule>,
     vesting_start: u64,
     timestamp: u64,
 ) -> Result<u128, &'static str> {
-    if let Some(vesting) = &vesting {
-        let after_cliff_start = vesting_start + vesting.cliff_period.as_nanos();
-        let instant_claim = vesting.get_instant_claim_amount(allocation)?;
-
-        if timestamp < after_cliff_start {
-            return Ok(instant_claim);
-        } else if timestamp >= vesting_start + vesting.vesting_period.as_nanos() {
-            return Ok(allocation);
-        }
-
-        let (claim_increasing_start, distribution_period) = match vesting.vesting_scheme {
-            VestingScheme::Immediate => (vesting_start, vesting.vesting_period.as_nanos()),
-            VestingScheme::AfterCliff => (
-                after_cliff_start,
-                vesting.vesting_period.as_nanos() - vesting.cliff_period.as_nanos(),
-            ),
-        };
-
-        let elapsed = timestamp.saturating_sub(claim_increasing_start);
+    vesting.map_or(Ok(allocation), |vesting| {
+        vesting.unlocked_amount(allocation, vesting_start, timestamp)
+    })
+}
 
-        U256::from(
-            allocation
-                .checked_sub(instant_claim)
-                .ok_or("Instant claim is more than total allocation")?,
+/// Returns the unlocked assets of the allocation at the start of the vesting, at the end of
+/// the cliff and at equal steps until the end of the vesting.
+///
+/// # Errors
+///
+/// Returns an error if an unlocked amount overflows.
+pub fn vesting_schedule(
+    allocation: u128,
+    vesting: Option<&VestingSchedule>,
+    vesting_start: u64,
+    steps: u64,
+) -> Result<Vec<(u64, u128)>, &'static str> {
+    vesting
+        .map_or_else(
+            || vec![vesting_start],
+            |vesting| vesting.unlock_timestamps(vesting_start, steps),
         )
-        .checked_mul(U256::from(elapsed))
-        .ok_or("Multiplication overflow")
-        .and_then(|result| {
-            result
-                .checked_div(U256::from(distribution_period))
-                .ok_or("Division by zero")
+        .into_iter()
+        .map(|timestamp| {
+            available_for_individual_vesting_claim(allocation, vesting, vesting_start, timestamp)
+                .map(|unlocked| (timestamp, unlocked))
         })
-        .and_then(to_u128)
-        .and_then(|v| v.checked_add(instant_claim).ok_or("Addition overflow"))
-    } else {
-        Ok(allocation)
-    }
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use crate::mechanics::claim::{
-        available_for_claim, available_for_individual_vesting_claim, user_allocation,
+        available_for_bundled_claim, available_for_claim, available_for_individual_vesting_claim,
+        user_allocation, vesting_schedule,
     };
     use crate::tests::utils::price_discovery_config;
     use aurora_launchpad_types::InvestmentAmount;
-    use aurora_launchpad_types::config::{VestingSchedule, VestingScheme};
+    use aurora_launchpad_types::config::{
+        BundleRatio, BundledSaleToken, SaleToken, VestingSchedule, VestingScheme,
+    };
     use near_sdk::json_types::U128;
 
     #[test]
@@ -728,4 +757,71 @@ mod tests {
             + expected_calc_instant_claim;
         assert_eq!(res, expected_calc);
     }
+
+    #[test]
+    fn test_bundled_sale_token_vesting() {
+        let config = price_discovery_config();
+        let vesting_period = 2_000_000.into();
+        let bundled = BundledSaleToken {
+            token: SaleToken::Nep141("utility.near".parse().unwrap()),
+            total_sale_amount: 0.into(),
+            ratio: BundleRatio {
+                sale_token: 4.into(),
+                bundled_token: 1.into(),
+            },
+            vesting_schedule: Some(VestingSchedule {
+                cliff_period: 0.into(),
+                vesting_period,
+                instant_claim_percentage: None,
+                vesting_scheme: VestingScheme::Immediate,
+            }),
+        };
+
+        let sale_allocation = 80_000_000;
+        let current_timestamp = config.end_date + vesting_period.as_nanos() / 2;
+
+        let res = available_for_bundled_claim(
+            sale_allocation,
+            &bundled,
+            config.end_date,
+            current_timestamp,
+        )
+        .unwrap();
+        assert_eq!(res, 10_000_000);
+
+        let res = available_for_bundled_claim(
+            sale_allocation,
+            &bundled,
+            config.end_date,
+            config.end_date + vesting_period.as_nanos(),
+        )
+        .unwrap();
+        assert_eq!(res, 20_000_000);
+    }
+
+    #[test]
+    fn test_vesting_schedule() {
+        let vesting = VestingSchedule {
+            cliff_period: 100.into(),
+            vesting_period: 500.into(),
+            instant_claim_percentage: Some(1000),
+            vesting_scheme: VestingScheme::AfterCliff,
+        };
+
+        let res = vesting_schedule(1000, Some(&vesting), 1000, 4).unwrap();
+        assert_eq!(
+            res,
+            vec![
+                (1000, 100),
+                (1100, 100),
+                (1200, 325),
+                (1300, 550),
+                (1400, 775),
+                (1500, 1000)
+            ]
+        );
+
+        let res = vesting_schedule(1000, None, 1000, 4).unwrap();
+        assert_eq!(res, vec![(1000, 1000)]);
+    }
 }
@@ -9,6 +9,10 @@ use aurora_launchpad_types::utils::to_u128;
 ///    If the total sold tokens exceed the total sale amount, it adjusts the investment and returns
 ///    the excess amount.
 /// 2. For `PriceDiscovery`, the weight is calculated based on the current discount.
+///
+/// # Errors
+///
+/// Returns an error if the distribution doesn't match the mechanics or the weight overflows.
 pub fn deposit(
     investment: &mut InvestmentAmount,
     amount: u128,
@@ -85,6 +89,10 @@ pub fn deposit(
 }
 
 /// Calculates the number of sale tokens based on the amount of deposit and price fraction.
+///
+/// # Errors
+///
+/// Returns an error if the number of sale tokens overflows.
 pub fn calculate_amount_of_sale_tokens(
     amount: u128,
     deposit_token: u128,
@@ -98,6 +106,10 @@ pub fn calculate_amount_of_sale_tokens(
 }
 
 /// Calculates the deposit(weight) based on the amount of sale tokens and price fraction.
+///
+/// # Errors
+///
+/// Returns an error if the weight overflows.
 pub fn calculate_weight_from_sale_tokens(
     amount: u128,
     deposit_token: u128,
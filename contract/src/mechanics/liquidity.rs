@@ -0,0 +1,104 @@
+use alloy_primitives::ruint::aliases::U256;
+use aurora_launchpad_types::config::{LaunchpadConfig, LiquidityProportion, Mechanics};
+use aurora_launchpad_types::utils::to_u128;
+
+/// Calculates the numbers of the deposit and sale tokens added to the pool at the sale price.
+/// The sale price is the fixed price for the fixed price mechanics and the ratio of the total
+/// deposited amount to the sale amount for the price discovery mechanics. If the sale tokens
+/// at the sale price exceed the liquidity allocation, both amounts are reduced in proportion.
+///
+/// # Errors
+///
+/// Returns an error if the total deposited amount is zero or the amounts overflow.
+pub fn liquidity_amounts(
+    liquidity: &LiquidityProportion,
+    total_deposited: u128,
+    config: &LaunchpadConfig,
+) -> Result<(u128, u128), &'static str> {
+    // The price as a fraction of the deposit and sale tokens.
+    let (deposit_price, sale_price) = match config.mechanics {
+        Mechanics::FixedPrice {
+            deposit_token,
+            sale_token,
+        } => (deposit_token.0, sale_token.0),
+        Mechanics::PriceDiscovery => (total_deposited, config.sale_amount.0),
+    };
+
+    let deposit_amount = mul_div(
+        total_deposited,
+        u128::from(liquidity.deposit_percentage),
+        10_000,
+    )?;
+    let sale_amount = mul_div(deposit_amount, sale_price, deposit_price)?;
+
+    if sale_amount <= liquidity.sale_allocation.0 {
+        Ok((deposit_amount, sale_amount))
+    } else {
+        let sale_amount = liquidity.sale_allocation.0;
+        Ok((
+            mul_div(sale_amount, deposit_price, sale_price)?,
+            sale_amount,
+        ))
+    }
+}
+
+fn mul_div(value: u128, numerator: u128, denominator: u128) -> Result<u128, &'static str> {
+    U256::from(value)
+        .checked_mul(U256::from(numerator))
+        .ok_or("Multiplication overflow")
+        .and_then(|result| {
+            result
+                .checked_div(U256::from(denominator))
+                .ok_or("Division by zero")
+        })
+        .and_then(to_u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use aurora_launchpad_types::config::{LiquidityProportion, Mechanics};
+    use near_sdk::json_types::U128;
+
+    use crate::mechanics::liquidity::liquidity_amounts;
+    use crate::tests::utils::{fixed_price_config, price_discovery_config};
+
+    fn liquidity(sale_allocation: u128) -> LiquidityProportion {
+        LiquidityProportion {
+            dex_account_id: "dex.near".parse().unwrap(),
+            pool_fee: 30,
+            deposit_percentage: 2000,
+            sale_allocation: U128(sale_allocation),
+            shares_receiver_id: None,
+        }
+    }
+
+    #[test]
+    fn test_liquidity_amounts_fixed_price() {
+        let mut config = fixed_price_config();
+        config.mechanics = Mechanics::FixedPrice {
+            deposit_token: U128(2),
+            sale_token: U128(1),
+        };
+
+        let amounts = liquidity_amounts(&liquidity(1_000_000), 1_000_000, &config).unwrap();
+        assert_eq!(amounts, (200_000, 100_000));
+    }
+
+    #[test]
+    fn test_liquidity_amounts_price_discovery() {
+        let mut config = price_discovery_config();
+        config.sale_amount = U128(4_000_000);
+
+        let amounts = liquidity_amounts(&liquidity(1_000_000), 1_000_000, &config).unwrap();
+        assert_eq!(amounts, (200_000, 800_000));
+    }
+
+    #[test]
+    fn test_liquidity_amounts_limited_by_allocation() {
+        let mut config = price_discovery_config();
+        config.sale_amount = U128(4_000_000);
+
+        let amounts = liquidity_amounts(&liquidity(400_000), 1_000_000, &config).unwrap();
+        assert_eq!(amounts, (100_000, 400_000));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod claim;
 pub mod deposit;
+pub mod liquidity;
+pub mod status;
 pub mod withdraw;
 
 #[cfg(test)]
@@ -8,7 +10,7 @@ mod tests {
     use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
     use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
 
-    use crate::discount::DiscountState;
+    use crate::discount::{DiscountPhases, DiscountState};
     use crate::mechanics::deposit::deposit;
     use crate::mechanics::withdraw::withdraw;
     use crate::tests::utils::{NOW, TEN_DAYS, fixed_price_config, price_discovery_config};
@@ -73,12 +75,14 @@ mod tests {
                 sale_token,
             } = self.config.mechanics
             {
-                self.discount_state.update(
-                    &self.account,
-                    &deposit_distribution,
-                    deposit_token.0,
-                    sale_token.0,
-                );
+                self.discount_state
+                    .update(
+                        &self.account,
+                        &deposit_distribution,
+                        deposit_token.0,
+                        sale_token.0,
+                    )
+                    .expect("Discount state update failed");
             }
 
             refund
@@ -0,0 +1,92 @@
+use aurora_launchpad_types::config::{LaunchpadConfig, LaunchpadStatus, Mechanics};
+
+/// Returns the status of the sale at the given time. The launchpad is expected to be initialized,
+/// unlocked and not archived, since these states don't depend on the sale progress.
+#[must_use]
+pub fn sale_status(
+    config: &LaunchpadConfig,
+    total_deposited: u128,
+    total_sold_tokens: u128,
+    timestamp: u64,
+) -> LaunchpadStatus {
+    if timestamp < config.start_date {
+        LaunchpadStatus::NotStarted
+    } else if timestamp >= config.start_date && timestamp < config.end_date {
+        if total_sold_tokens >= config.sale_amount.0
+            && matches!(config.mechanics, Mechanics::FixedPrice { .. })
+        {
+            // If TGE is present, transition to PreTGE even when all tokens are sold early,
+            // since TGE is always > end_date (validated at initialization and
+            // in the `update_tge` transaction). Therefore, checking for the presence of TGE
+            // is enough here.
+            if config.tge.is_some() {
+                LaunchpadStatus::PreTGE
+            } else {
+                LaunchpadStatus::Success
+            }
+        } else {
+            LaunchpadStatus::Ongoing
+        }
+    } else if timestamp >= config.end_date && total_deposited >= config.soft_cap.0 {
+        if config.tge.is_some_and(|tge| timestamp < tge) {
+            LaunchpadStatus::PreTGE
+        } else {
+            LaunchpadStatus::Success
+        }
+    } else {
+        LaunchpadStatus::Failed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aurora_launchpad_types::config::LaunchpadStatus;
+
+    use crate::mechanics::status::sale_status;
+    use crate::tests::utils::{NOW, TEN_DAYS, fixed_price_config, price_discovery_config};
+
+    #[test]
+    fn test_sale_status_by_time() {
+        let mut config = price_discovery_config();
+        let soft_cap = config.soft_cap.0;
+
+        assert_eq!(
+            sale_status(&config, 0, 0, NOW - 1),
+            LaunchpadStatus::NotStarted
+        );
+        assert_eq!(sale_status(&config, 0, 0, NOW), LaunchpadStatus::Ongoing);
+        assert_eq!(
+            sale_status(&config, soft_cap - 1, 0, NOW + TEN_DAYS),
+            LaunchpadStatus::Failed
+        );
+        assert_eq!(
+            sale_status(&config, soft_cap, 0, NOW + TEN_DAYS),
+            LaunchpadStatus::Success
+        );
+
+        config.tge = Some(NOW + 2 * TEN_DAYS);
+        assert_eq!(
+            sale_status(&config, soft_cap, 0, NOW + TEN_DAYS),
+            LaunchpadStatus::PreTGE
+        );
+        assert_eq!(
+            sale_status(&config, soft_cap, 0, NOW + 2 * TEN_DAYS),
+            LaunchpadStatus::Success
+        );
+    }
+
+    #[test]
+    fn test_sale_status_of_sold_out_fixed_price() {
+        let config = fixed_price_config();
+        let sale_amount = config.sale_amount.0;
+
+        assert_eq!(
+            sale_status(&config, 0, sale_amount - 1, NOW + 1),
+            LaunchpadStatus::Ongoing
+        );
+        assert_eq!(
+            sale_status(&config, 0, sale_amount, NOW + 1),
+            LaunchpadStatus::Success
+        );
+    }
+}
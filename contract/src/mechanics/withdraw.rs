@@ -1,9 +1,13 @@
 use aurora_launchpad_types::InvestmentAmount;
 use aurora_launchpad_types::config::{LaunchpadConfig, Mechanics};
 use aurora_launchpad_types::discount::DepositDistribution;
-use near_sdk::require;
 
 /// Withdraw state modification, adjusting the weight and discount if adjusted.
+///
+/// # Errors
+///
+/// Returns an error if the amount exceeds the deposit, the distribution doesn't match the
+/// mechanics or the weight overflows.
 pub fn withdraw(
     investment: &mut InvestmentAmount,
     amount: u128,
@@ -32,7 +36,7 @@ pub fn withdraw(
 
             let weight = investment.weight;
             // Recalculate the weight according to the current discount distribution
-            investment.weight = recalculate_weight_on_price_discovery(deposit_distribution);
+            investment.weight = recalculate_weight_on_price_discovery(deposit_distribution)?;
             // Recalculate the total sold tokens
             if weight > investment.weight {
                 // If the discount decreased
@@ -50,28 +54,30 @@ pub fn withdraw(
     Ok(())
 }
 
-fn recalculate_weight_on_price_discovery(deposit_distribution: &DepositDistribution) -> u128 {
+fn recalculate_weight_on_price_discovery(
+    deposit_distribution: &DepositDistribution,
+) -> Result<u128, &'static str> {
     match deposit_distribution {
         DepositDistribution::WithDiscount {
             phase_weights,
             public_sale_weight,
             refund,
         } => {
-            require!(
-                *refund == 0,
-                "Refund in withdrawal with mechanic PriceDiscovery is not supported"
-            );
-            phase_weights
+            if *refund != 0 {
+                return Err("Refund in withdrawal with mechanic PriceDiscovery is not supported");
+            }
+
+            Ok(phase_weights
                 .iter()
                 .map(|(_, weight)| *weight)
                 .sum::<u128>()
-                .saturating_add(*public_sale_weight)
+                .saturating_add(*public_sale_weight))
         }
         // A `DepositDistribution::Refund` occurs when there are no active discount phases for
         // the user and no public sale has started yet. In the case of withdrawal, we simply reduce
         // the user's weight by the refund amount, since in this case the refund == withdrawal amount.
         DepositDistribution::WithoutDiscount(weight) | DepositDistribution::Refund(weight) => {
-            *weight
+            Ok(*weight)
         }
     }
 }
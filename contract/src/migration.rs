@@ -0,0 +1,251 @@
+use aurora_launchpad_types::admin_withdraw::WithdrawnUnsoldTokens;
+use aurora_launchpad_types::config::{
+    DepositDistributionProportion, DepositRecipient, DepositToken, DistributionAccount,
+    DistributionProportions, LaunchpadConfig, Mechanics, SaleToken, StakeholderProportion,
+    VestingSchedule,
+};
+use aurora_launchpad_types::discount::DiscountParams;
+use aurora_launchpad_types::distribution::{DepositsDistribution, LiquiditySeeding};
+use aurora_launchpad_types::error::LaunchpadError;
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::borsh::BorshDeserialize;
+use near_sdk::json_types::{Base58CryptoHash, U128};
+use near_sdk::store::{LookupMap, LookupSet};
+use near_sdk::{AccountId, Gas, IntoStorageKey, NearToken, Promise, env, near};
+
+use crate::bundle::Bundle;
+use crate::cleanup::Cleanup;
+use crate::discount::DiscountState;
+use crate::error::PanicWithCode;
+use crate::registration::RecipientRegistration;
+use crate::storage_key::StorageKey;
+use crate::storage_management::StorageManagement;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
+
+/// The version of the state layout of the contracts deployed before the state was versioned
+/// (0.7.x and earlier). Such contracts have no state version in the storage.
+pub const LEGACY_STATE_VERSION: u16 = 0;
+/// The version of the current state layout. It must be incremented every time the layout
+/// of `AuroraLaunchpadContract` changes in a released version, and the migration from
+/// the previous layout must be added to `migrate`.
+pub const STATE_VERSION: u16 = 1;
+/// The amount of gas for migrating the state after switching to the global contract code.
+const MIGRATE_GAS: Gas = Gas::from_tgas(20);
+
+/// The layout of the deposits distribution proportion of the launchpad contract 0.7.x with
+/// a single intents fee account.
+#[near(serializers = [borsh])]
+pub struct DepositDistributionProportionV0 {
+    pub solver_percentage: u16,
+    pub fee_account: IntentsAccount,
+    pub fee_percentage: u16,
+}
+
+impl From<DepositDistributionProportionV0> for DepositDistributionProportion {
+    fn from(proportion: DepositDistributionProportionV0) -> Self {
+        Self {
+            solver_percentage: proportion.solver_percentage,
+            recipients: vec![DepositRecipient {
+                account: proportion.fee_account.into(),
+                percentage: proportion.fee_percentage,
+            }],
+        }
+    }
+}
+
+/// The layout of the distribution proportions of the launchpad contract 0.7.x.
+#[near(serializers = [borsh])]
+pub struct DistributionProportionsV0 {
+    pub solver_account_id: DistributionAccount,
+    pub solver_allocation: U128,
+    pub stakeholder_proportions: Vec<StakeholderProportion>,
+    pub deposits: Option<DepositDistributionProportionV0>,
+}
+
+impl From<DistributionProportionsV0> for DistributionProportions {
+    fn from(proportions: DistributionProportionsV0) -> Self {
+        Self {
+            solver_account_id: proportions.solver_account_id,
+            solver_allocation: proportions.solver_allocation,
+            stakeholder_proportions: proportions.stakeholder_proportions,
+            deposits: proportions.deposits.map(Into::into),
+            liquidity: None,
+        }
+    }
+}
+
+/// The layout of the launchpad configuration of the launchpad contract 0.7.x.
+#[near(serializers = [borsh])]
+pub struct LaunchpadConfigV0 {
+    pub deposit_token: DepositToken,
+    pub min_deposit: U128,
+    pub sale_token_account_id: AccountId,
+    pub intents_account_id: AccountId,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub tge: Option<u64>,
+    pub soft_cap: U128,
+    pub mechanics: Mechanics,
+    pub sale_amount: U128,
+    pub total_sale_amount: U128,
+    pub vesting_schedule: Option<VestingSchedule>,
+    pub distribution_proportions: DistributionProportionsV0,
+    pub discounts: Option<DiscountParams>,
+}
+
+impl From<LaunchpadConfigV0> for LaunchpadConfig {
+    fn from(config: LaunchpadConfigV0) -> Self {
+        Self {
+            deposit_token: config.deposit_token,
+            min_deposit: config.min_deposit,
+            sale_token: SaleToken::Nep141(config.sale_token_account_id),
+            intents_account_id: config.intents_account_id,
+            start_date: config.start_date,
+            end_date: config.end_date,
+            tge: config.tge,
+            soft_cap: config.soft_cap,
+            mechanics: config.mechanics,
+            sale_amount: config.sale_amount,
+            total_sale_amount: config.total_sale_amount,
+            vesting_schedule: config.vesting_schedule,
+            distribution_proportions: config.distribution_proportions.into(),
+            discounts: config.discounts,
+            bundled_sale_tokens: Vec::new(),
+            allow_unreachable_soft_cap: false,
+        }
+    }
+}
+
+/// The state of the deposits distribution of the launchpad contract 0.7.x with a single fee
+/// account.
+#[near(serializers = [borsh])]
+pub struct DepositsDistributionV0 {
+    pub solver_amount: u128,
+    pub fee_amount: u128,
+    pub is_ongoing: bool,
+}
+
+/// The distributed amount of the fee account becomes the amount of the first recipient.
+impl From<DepositsDistributionV0> for DepositsDistribution {
+    fn from(distribution: DepositsDistributionV0) -> Self {
+        Self {
+            solver_amount: distribution.solver_amount,
+            recipient_amounts: vec![distribution.fee_amount],
+            is_ongoing: distribution.is_ongoing,
+        }
+    }
+}
+
+/// The state layout of the launchpad contract 0.7.x.
+#[near(serializers = [borsh])]
+pub struct AuroraLaunchpadContractV0 {
+    pub config: LaunchpadConfigV0,
+    pub participants_count: u64,
+    pub total_deposited: u128,
+    pub total_sold_tokens: u128,
+    pub investments: LookupMap<IntentsAccount, InvestmentAmount>,
+    pub vestings: LookupMap<IntentsAccount, u128>,
+    pub individual_vesting_claimed: LookupMap<DistributionAccount, u128>,
+    pub is_sale_token_set: bool,
+    pub is_locked: bool,
+    pub distributed_accounts: LookupMap<DistributionAccount, (u128, bool)>,
+    pub locked_withdraw: LookupSet<IntentsAccount>,
+    pub deposits_distribution: DepositsDistributionV0,
+    pub withdrawn_unsold_tokens: WithdrawnUnsoldTokens,
+    pub discount_state: Option<DiscountState>,
+}
+
+/// Participants of the launchpads 0.7.x aren't tracked, so their entries aren't removed by
+/// the cleanup, and the launchpads are never archived.
+impl From<AuroraLaunchpadContractV0> for AuroraLaunchpadContract {
+    fn from(state: AuroraLaunchpadContractV0) -> Self {
+        Self {
+            config: LaunchpadConfig::from(state.config),
+            participants_count: state.participants_count,
+            total_deposited: state.total_deposited,
+            total_sold_tokens: state.total_sold_tokens,
+            investments: state.investments,
+            vestings: state.vestings,
+            individual_vesting_claimed: state.individual_vesting_claimed,
+            is_sale_token_set: state.is_sale_token_set,
+            is_locked: state.is_locked,
+            distributed_accounts: state.distributed_accounts,
+            distribution_failures: LookupMap::new(StorageKey::DistributionFailures),
+            locked_withdraw: state.locked_withdraw,
+            deposits_distribution: state.deposits_distribution.into(),
+            withdrawn_unsold_tokens: state.withdrawn_unsold_tokens,
+            discount_state: state.discount_state,
+            registration: RecipientRegistration::default(),
+            storage: StorageManagement::default(),
+            cleanup: Cleanup::new(false),
+            bundle: Bundle::new(state.is_sale_token_set, 0),
+            liquidity: LiquiditySeeding::default(),
+        }
+    }
+}
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Migrates the state of the contract to the current layout after upgrading the code.
+    /// The layout of the existing state is detected by the state version stored in the contract.
+    #[private]
+    #[init(ignore_state)]
+    #[must_use]
+    pub fn migrate() -> Self {
+        let contract = match read_state_version() {
+            LEGACY_STATE_VERSION => Self::from(read_state::<AuroraLaunchpadContractV0>()),
+            STATE_VERSION => read_state::<Self>(),
+            version => LaunchpadError::UnsupportedStateVersion
+                .panic_with(&format!("Unsupported state version: {version}")),
+        };
+
+        write_state_version();
+
+        contract
+    }
+
+    /// Switches the contract to the global contract code with the provided hash and migrates
+    /// the state to the layout of the new code. It allows upgrading the contract without
+    /// transferring and storing the code in the contract account.
+    #[access_control_any(roles(Role::Admin, Role::Upgrader))]
+    pub fn upgrade_to_global_code(&mut self, code_hash: Base58CryptoHash) -> Promise {
+        Promise::new(env::current_account_id())
+            .use_global_contract(code_hash)
+            .function_call(
+                "migrate".to_string(),
+                vec![],
+                NearToken::from_yoctonear(0),
+                MIGRATE_GAS,
+            )
+    }
+
+    /// Returns the version of the state layout of the contract.
+    #[must_use]
+    pub fn get_state_version() -> u16 {
+        read_state_version()
+    }
+}
+
+/// Stores the current state version in the contract storage.
+pub fn write_state_version() {
+    env::storage_write(
+        &StorageKey::StateVersion.into_storage_key(),
+        &STATE_VERSION.to_le_bytes(),
+    );
+}
+
+fn read_state_version() -> u16 {
+    env::storage_read(&StorageKey::StateVersion.into_storage_key()).map_or(
+        LEGACY_STATE_VERSION,
+        |bytes| {
+            u16::try_from_slice(&bytes).unwrap_or_else(|_| {
+                LaunchpadError::StateReadFailed.panic_with("Failed to read the state version")
+            })
+        },
+    )
+}
+
+fn read_state<T: BorshDeserialize>() -> T {
+    env::state_read().unwrap_or_else(|| LaunchpadError::StateReadFailed.panic())
+}
@@ -0,0 +1,205 @@
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::store::IterableSet;
+use near_sdk::{AccountId, Gas, NearToken, Promise, env, near};
+
+use crate::storage_key::StorageKey;
+use crate::traits::{ext_ft, read_storage_balance_result};
+use crate::{
+    AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER, ONE_YOCTO, Role,
+};
+
+const GAS_FOR_STORAGE_BALANCE_OF: Gas = Gas::from_tgas(3);
+const GAS_FOR_STORAGE_DEPOSIT: Gas = Gas::from_tgas(5);
+/// The default deposit for registering a recipient in the token. It equals the minimal storage
+/// balance of the reference NEP-141 implementation. The excess is refunded by the token.
+const DEFAULT_STORAGE_DEPOSIT: NearToken = NearToken::from_yoctonear(1_250_000_000_000_000_000_000);
+
+/// Registration of NEAR recipients in the tokens transferred by the launchpad.
+#[near(serializers = [borsh])]
+pub struct RecipientRegistration {
+    /// The amount of NEAR attached to `storage_deposit` for registering a recipient.
+    deposit: u128,
+    /// The remaining amount of NEAR which could be spent on registrations. The contract balance
+    /// is used if `None`.
+    budget: Option<u128>,
+    /// Recipients which couldn't be registered because of the insufficient budget or balance.
+    unregistered: IterableSet<AccountId>,
+}
+
+impl Default for RecipientRegistration {
+    fn default() -> Self {
+        Self {
+            deposit: DEFAULT_STORAGE_DEPOSIT.as_yoctonear(),
+            budget: None,
+            unregistered: IterableSet::new(StorageKey::UnregisteredRecipients),
+        }
+    }
+}
+
+/// Settings of the registration of NEAR recipients in the tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct StorageDepositSettings {
+    /// The amount of NEAR attached to `storage_deposit` for registering a recipient.
+    pub deposit: U128,
+    /// The remaining amount of NEAR which could be spent on registrations. The contract balance
+    /// is used if `None`.
+    pub budget: Option<U128>,
+}
+
+/// The result of the registration check of a NEAR recipient.
+pub enum Registration {
+    /// The recipient is registered in the token.
+    Registered,
+    /// The recipient must be registered with the provided deposit before the transfer.
+    Required(NearToken),
+    /// The recipient isn't registered, and there are no funds for the registration.
+    Unfunded,
+}
+
+impl Registration {
+    /// Returns the deposit attached to `storage_deposit` before the transfer, if any.
+    pub const fn storage_deposit(&self) -> Option<NearToken> {
+        match self {
+            Self::Required(deposit) => Some(*deposit),
+            Self::Registered | Self::Unfunded => None,
+        }
+    }
+}
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Sets the deposit attached for registering NEAR recipients in the tokens and the budget
+    /// for the registrations. The contract balance is used for the registrations if the budget
+    /// is `None`.
+    #[access_control_any(roles(Role::Admin))]
+    pub fn set_storage_deposit_settings(&mut self, deposit: U128, budget: Option<U128>) {
+        self.registration.deposit = deposit.0;
+        self.registration.budget = budget.map(|budget| budget.0);
+    }
+
+    /// Returns the settings of the registration of NEAR recipients in the tokens.
+    pub fn get_storage_deposit_settings(&self) -> StorageDepositSettings {
+        StorageDepositSettings {
+            deposit: U128(self.registration.deposit),
+            budget: self.registration.budget.map(U128),
+        }
+    }
+
+    /// Returns NEAR recipients which couldn't be registered in the tokens. The transfers to them
+    /// are retried by repeating the distribution, the claim, or the withdrawal once the budget
+    /// is increased or the recipients are registered.
+    pub fn get_unregistered_recipients(&self) -> Vec<AccountId> {
+        self.registration.unregistered.iter().cloned().collect()
+    }
+}
+
+impl AuroraLaunchpadContract {
+    /// Checks whether the NEAR recipient is registered in the token.
+    pub(crate) fn check_registration(
+        token_account_id: &AccountId,
+        account_id: &AccountId,
+    ) -> Promise {
+        ext_ft::ext(token_account_id.clone())
+            .with_static_gas(GAS_FOR_STORAGE_BALANCE_OF)
+            .storage_balance_of(account_id.clone())
+    }
+
+    /// Returns the remaining budget for the registrations of NEAR recipients.
+    pub(crate) fn storage_deposit_budget(&self) -> u128 {
+        self.registration.budget.unwrap_or_default()
+    }
+
+    /// Returns the deposit for the registration to the budget after the failed transfer batch.
+    /// The failed batch is rolled back, so the deposit attached to `storage_deposit` is refunded.
+    pub(crate) fn restore_storage_deposit_budget(&mut self, storage_deposit: Option<NearToken>) {
+        if let (Some(budget), Some(deposit)) = (&mut self.registration.budget, storage_deposit) {
+            *budget = budget.saturating_add(deposit.as_yoctonear());
+        }
+    }
+
+    /// Resolves the registration of the NEAR recipient by the result of `storage_balance_of` at
+    /// promise result `index`. The deposit for the registration is taken from the budget, or
+    /// from the contract balance without the `reserved` deposits of the other recipients of
+    /// the same batch. Recipients without funds for the registration are tracked as unregistered.
+    pub(crate) fn resolve_registration(
+        &mut self,
+        account_id: &AccountId,
+        index: u64,
+        reserved: u128,
+    ) -> Registration {
+        // If the registration can't be checked, the transfer is tried as is.
+        if read_storage_balance_result(index).unwrap_or(true) {
+            self.registration.unregistered.remove(account_id);
+            return Registration::Registered;
+        }
+
+        let deposit = self.registration.deposit;
+        let is_funded = match &mut self.registration.budget {
+            Some(budget) if *budget >= deposit => {
+                *budget -= deposit;
+                true
+            }
+            Some(_) => false,
+            None => available_balance().saturating_sub(reserved) >= deposit,
+        };
+
+        if is_funded {
+            self.registration.unregistered.remove(account_id);
+            Registration::Required(NearToken::from_yoctonear(deposit))
+        } else {
+            near_sdk::log!("No funds to register {account_id} in the token");
+            self.registration.unregistered.insert(account_id.clone());
+            Registration::Unfunded
+        }
+    }
+}
+
+/// Adds `ft_transfer` of `amount` tokens to `receiver_id` into the batch `promise`, preceded by
+/// `storage_deposit` if the receiver must be registered.
+pub fn add_ft_transfer(
+    promise: Promise,
+    receiver_id: &AccountId,
+    amount: U128,
+    storage_deposit: Option<NearToken>,
+) -> Promise {
+    let promise = match storage_deposit {
+        Some(deposit) => promise.function_call(
+            "storage_deposit".to_string(),
+            json!({
+                "account_id": receiver_id,
+                "registration_only": true,
+            })
+            .to_string()
+            .into_bytes(),
+            deposit,
+            GAS_FOR_STORAGE_DEPOSIT,
+        ),
+        None => promise,
+    };
+
+    promise.function_call(
+        "ft_transfer".to_string(),
+        json!({
+            "receiver_id": receiver_id,
+            "amount": amount,
+        })
+        .to_string()
+        .into_bytes(),
+        ONE_YOCTO,
+        GAS_FOR_FT_TRANSFER,
+    )
+}
+
+/// Returns the balance of the contract which isn't locked for the storage.
+fn available_balance() -> u128 {
+    let storage_cost = env::storage_byte_cost()
+        .as_yoctonear()
+        .saturating_mul(u128::from(env::storage_usage()));
+
+    env::account_balance()
+        .as_yoctonear()
+        .saturating_sub(storage_cost)
+}
@@ -0,0 +1,75 @@
+use aurora_launchpad_types::config::{DepositToken, SaleToken};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_sdk::{AccountId, Gas, NearToken, Promise};
+
+use crate::registration::add_ft_transfer;
+use crate::traits::{ext_ft, ext_mt, read_ft_result, read_mt_result};
+use crate::{GAS_FOR_FT_TRANSFER_CALL, GAS_FOR_MT_TRANSFER_CALL, ONE_YOCTO};
+
+const GAS_FOR_MT_TRANSFER: Gas = Gas::from_tgas(5);
+
+/// Transfers `amount` of the sale tokens to `receiver_id` with `ft_transfer_call` or
+/// `mt_transfer_call` depending on the standard of the sale token.
+pub fn transfer_call(
+    sale_token: &SaleToken,
+    receiver_id: AccountId,
+    amount: U128,
+    msg: String,
+) -> Promise {
+    match sale_token {
+        SaleToken::Nep141(token_account_id) => ext_ft::ext(token_account_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
+            .ft_transfer_call(receiver_id, amount, msg, None),
+        SaleToken::Nep245((token_account_id, token_id)) => ext_mt::ext(token_account_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_MT_TRANSFER_CALL)
+            .mt_transfer_call(receiver_id, token_id.clone(), amount, None, None, msg),
+    }
+}
+
+/// Adds the transfer of `amount` of the sale tokens to `receiver_id` into the batch `promise`
+/// created for the sale token account. NEP-141 transfers are preceded by `storage_deposit` if
+/// the receiver must be registered. NEP-245 tokens don't require the registration.
+pub fn add_transfer(
+    sale_token: &SaleToken,
+    promise: Promise,
+    receiver_id: &AccountId,
+    amount: U128,
+    storage_deposit: Option<NearToken>,
+) -> Promise {
+    match sale_token {
+        SaleToken::Nep141(_) => add_ft_transfer(promise, receiver_id, amount, storage_deposit),
+        SaleToken::Nep245((_, token_id)) => promise.function_call(
+            "mt_transfer".to_string(),
+            json!({
+                "receiver_id": receiver_id,
+                "token_id": token_id,
+                "amount": amount,
+            })
+            .to_string()
+            .into_bytes(),
+            ONE_YOCTO,
+            GAS_FOR_MT_TRANSFER,
+        ),
+    }
+}
+
+/// Reads promise result `index` of `transfer_call` and returns the amount of the used tokens.
+/// Returns `None` when the promise failed or its result couldn't be parsed.
+pub fn read_transfer_call_result(sale_token: &SaleToken, index: u64) -> Option<u128> {
+    match sale_token {
+        SaleToken::Nep141(_) => read_ft_result(index),
+        SaleToken::Nep245(_) => read_mt_result(index),
+    }
+}
+
+/// Returns the deposit token as the sale token of the same standard, so the transfer helpers
+/// of the sale token could be used for the deposit token.
+pub fn transferable_token(deposit_token: &DepositToken) -> SaleToken {
+    match deposit_token {
+        DepositToken::Nep141(token_account_id) => SaleToken::Nep141(token_account_id.clone()),
+        DepositToken::Nep245(token) => SaleToken::Nep245(token.clone()),
+    }
+}
@@ -0,0 +1,117 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::LaunchpadStatus;
+use aurora_launchpad_types::error::LaunchpadError;
+use aurora_launchpad_types::simulation::{DepositSimulation, WithdrawSimulation};
+use near_sdk::json_types::U128;
+use near_sdk::{env, near};
+
+use crate::error::{PanicWithCode, require, require_with};
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, mechanics};
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Returns the outcome of the deposit of the given amount by the account at the given time
+    /// or at the current block time if the time is not provided. The view panics with the same
+    /// errors as the deposit itself.
+    pub fn simulate_deposit(
+        &self,
+        account: IntentsAccount,
+        amount: U128,
+        timestamp: Option<u64>,
+    ) -> DepositSimulation {
+        let timestamp = timestamp.unwrap_or_else(env::block_timestamp);
+
+        require(
+            self.get_status_at(timestamp) == LaunchpadStatus::Ongoing,
+            LaunchpadError::NotOngoing,
+        );
+        require(
+            amount >= self.config.min_deposit,
+            LaunchpadError::DepositTooLow,
+        );
+        require_with(
+            !self.locked_withdraw.contains(&account),
+            LaunchpadError::WithdrawInProgress,
+            "Withdrawal is in progress for this account",
+        );
+
+        let storage_fee = self.storage_fee(&account, amount.0);
+        let amount = amount.0 - storage_fee;
+        let distribution = self.get_deposit_distribution(&account, amount, timestamp);
+        let mut investment = self.investments.get(&account).copied().unwrap_or_default();
+        let mut total_deposited = self.total_deposited;
+        let mut total_sold_tokens = self.total_sold_tokens;
+
+        let refund = mechanics::deposit::deposit(
+            &mut investment,
+            amount,
+            &mut total_deposited,
+            &mut total_sold_tokens,
+            &self.config,
+            &distribution,
+        )
+        .unwrap_or(amount);
+
+        // The storage fee is returned together with the whole deposit.
+        let (storage_fee, refund) = if refund == amount {
+            (0, refund + storage_fee)
+        } else {
+            (storage_fee, refund)
+        };
+
+        DepositSimulation {
+            distribution: distribution.into(),
+            storage_fee: U128(storage_fee),
+            deposit: U128(amount.saturating_sub(refund)),
+            refund: U128(refund),
+            weight: U128(investment.weight),
+            allocation: U128(self.simulated_allocation(investment.weight, total_sold_tokens)),
+        }
+    }
+
+    /// Returns the outcome of the withdrawal of the given amount by the account at the given
+    /// time or at the current block time if the time is not provided. The view doesn't check
+    /// whether the withdrawal is allowed, since it depends on the provided intents.
+    pub fn simulate_withdraw(
+        &self,
+        account: IntentsAccount,
+        amount: U128,
+        timestamp: Option<u64>,
+    ) -> WithdrawSimulation {
+        let timestamp = timestamp.unwrap_or_else(env::block_timestamp);
+        let mut investment = self.investments.get(&account).copied().unwrap_or_else(|| {
+            LaunchpadError::NoDeposit.panic_with("No deposit for the intents account")
+        });
+        let remain_deposit = investment
+            .amount
+            .checked_sub(amount.0)
+            .unwrap_or_else(|| LaunchpadError::WithdrawAmountTooHigh.panic());
+        let distribution = self.get_deposit_distribution(&account, remain_deposit, timestamp);
+        let mut total_deposited = self.total_deposited;
+        let mut total_sold_tokens = self.total_sold_tokens;
+
+        mechanics::withdraw::withdraw(
+            &mut investment,
+            amount.0,
+            &mut total_deposited,
+            &mut total_sold_tokens,
+            &self.config,
+            &distribution,
+        )
+        .unwrap_or_else(|err| {
+            LaunchpadError::WithdrawFailed.panic_with(&format!("Withdraw failed: {err}"))
+        });
+
+        WithdrawSimulation {
+            distribution: distribution.into(),
+            deposit: U128(investment.amount),
+            weight: U128(investment.weight),
+            allocation: U128(self.simulated_allocation(investment.weight, total_sold_tokens)),
+        }
+    }
+
+    fn simulated_allocation(&self, weight: u128, total_sold_tokens: u128) -> u128 {
+        mechanics::claim::user_allocation(weight, total_sold_tokens, &self.config)
+            .unwrap_or_else(|e| LaunchpadError::Overflow.panic_with(e))
+    }
+}
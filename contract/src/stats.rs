@@ -0,0 +1,103 @@
+use alloy_primitives::ruint::aliases::U256;
+use aurora_launchpad_types::config::Mechanics;
+use aurora_launchpad_types::stats::{DiscountPhaseState, Price, SaleStats};
+use aurora_launchpad_types::utils::to_u128;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near};
+
+use crate::discount::DiscountPhases;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt};
+
+const MAX_PROGRESS: u16 = 10_000;
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Returns the global statistics of the sale: totals, participants, status, the progress of
+    /// reaching the soft cap, the current price and the remaining sale tokens.
+    pub fn get_sale_stats(&self) -> SaleStats {
+        let (price, remaining_sale_tokens) = match self.config.mechanics {
+            Mechanics::FixedPrice {
+                deposit_token,
+                sale_token,
+            } => (
+                Some(Price {
+                    deposit_token,
+                    sale_token,
+                }),
+                self.config
+                    .sale_amount
+                    .0
+                    .saturating_sub(self.total_sold_tokens),
+            ),
+            Mechanics::PriceDiscovery => (
+                (self.total_deposited > 0).then_some(Price {
+                    deposit_token: U128(self.total_deposited),
+                    sale_token: self.config.sale_amount,
+                }),
+                if self.total_sold_tokens == 0 {
+                    self.config.sale_amount.0
+                } else {
+                    0
+                },
+            ),
+        };
+
+        SaleStats {
+            status: self.get_status(),
+            participants_count: self.participants_count,
+            total_deposited: U128(self.total_deposited),
+            total_sold_tokens: U128(self.total_sold_tokens),
+            sale_amount: self.config.sale_amount,
+            remaining_sale_tokens: U128(remaining_sale_tokens),
+            soft_cap: self.config.soft_cap,
+            soft_cap_progress: self.soft_cap_progress(),
+            price,
+        }
+    }
+
+    /// Returns the progress of every discount phase: sold tokens, limits and the size of
+    /// the whitelist.
+    pub fn get_discount_phases_state(&self) -> Vec<DiscountPhaseState> {
+        let (Some(discounts), Some(state)) = (&self.config.discounts, &self.discount_state) else {
+            return vec![];
+        };
+        let timestamp = env::block_timestamp();
+
+        discounts
+            .phases
+            .iter()
+            .map(|phase| {
+                let total_sale_tokens = state.total_sale_tokens(phase.id);
+                let effective_limit = state.effective_limit_per_phase(phase.id);
+
+                DiscountPhaseState {
+                    id: phase.id,
+                    percentage: phase.percentage,
+                    start_time: phase.start_time,
+                    end_time: phase.end_time,
+                    is_active: phase.start_time <= timestamp && timestamp < phase.end_time,
+                    total_sale_tokens: U128(total_sale_tokens),
+                    limit: state.limit_per_phase(phase.id).map(U128),
+                    effective_limit: effective_limit.map(U128),
+                    available_sale_tokens: effective_limit
+                        .map(|limit| U128(limit.saturating_sub(total_sale_tokens))),
+                    whitelist_size: state.whitelist_len(phase.id),
+                }
+            })
+            .collect()
+    }
+
+    fn soft_cap_progress(&self) -> u16 {
+        if self.total_deposited >= self.config.soft_cap.0 {
+            return MAX_PROGRESS;
+        }
+
+        // The total deposit is less than the soft cap, so the progress fits `u16`.
+        U256::from(self.total_deposited)
+            .checked_mul(U256::from(MAX_PROGRESS))
+            .map(|result| result / U256::from(self.config.soft_cap.0))
+            .and_then(|result| to_u128(result).ok())
+            .and_then(|result| u16::try_from(result).ok())
+            .unwrap_or(MAX_PROGRESS)
+    }
+}
@@ -11,6 +11,13 @@ pub enum StorageKey {
     LinkedPhases,
     DiscountWhitelist { id: u16 },
     SaleTokensPerUser { id: u16 },
+    StateVersion,
+    UnregisteredRecipients,
+    StorageBalances,
+    Participants,
+    PendingClaims,
+    BundleClaimed,
+    DistributionFailures,
 }
 
 impl IntoStorageKey for StorageKey {
@@ -25,6 +32,13 @@ impl IntoStorageKey for StorageKey {
             Self::LinkedPhases => b"linked_phases".to_vec(),
             Self::DiscountWhitelist { id } => to_vec("whitelist", id),
             Self::SaleTokensPerUser { id } => to_vec("tokens_per_user", id),
+            Self::StateVersion => b"state_version".to_vec(),
+            Self::UnregisteredRecipients => b"unregistered_recipients".to_vec(),
+            Self::StorageBalances => b"storage_balances".to_vec(),
+            Self::Participants => b"participants".to_vec(),
+            Self::PendingClaims => b"pending_claims".to_vec(),
+            Self::BundleClaimed => b"bundle_claimed".to_vec(),
+            Self::DistributionFailures => b"distribution_failures".to_vec(),
         }
     }
 }
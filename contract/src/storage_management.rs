@@ -0,0 +1,256 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::error::LaunchpadError;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::U128;
+use near_sdk::store::LookupMap;
+use near_sdk::{AccountId, NearToken, Promise, assert_one_yocto, env, near};
+
+use crate::error::{PanicWithCode, require};
+use crate::storage_key::StorageKey;
+use crate::traits::StorageBalance;
+use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, Role};
+
+/// The maximum number of bytes of the storage taken by a participant: the investment, the vesting
+/// and the sale tokens bought in discount phases.
+const STORAGE_BYTES_PER_ACCOUNT: u128 = 1_000;
+
+/// The way the storage taken by new participants is paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[near(serializers = [borsh, json])]
+pub enum StorageMode {
+    /// The storage is paid from the launchpad balance.
+    #[default]
+    Disabled,
+    /// Deposits of accounts which are not registered with `storage_deposit` are rejected.
+    Required,
+    /// The fee in the deposit tokens is deducted from the first deposit of accounts which are not
+    /// registered with `storage_deposit`.
+    DepositTokenFee(U128),
+}
+
+/// NEP-145 storage balance bounds.
+#[near(serializers = [json])]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+    pub max: Option<U128>,
+}
+
+/// NEP-145 storage management of the launchpad participants.
+#[near(serializers = [borsh])]
+pub struct StorageManagement {
+    /// The way the storage of new participants is paid.
+    mode: StorageMode,
+    /// Storage balances of the registered accounts.
+    balances: LookupMap<IntentsAccount, u128>,
+    /// The total storage balance of the registered accounts.
+    total_balance: u128,
+    /// The amount of the deposit tokens collected as storage fees.
+    collected_fees: u128,
+}
+
+impl Default for StorageManagement {
+    fn default() -> Self {
+        Self {
+            mode: StorageMode::default(),
+            balances: LookupMap::new(StorageKey::StorageBalances),
+            total_balance: 0,
+            collected_fees: 0,
+        }
+    }
+}
+
+#[near]
+impl AuroraLaunchpadContract {
+    /// Sets the way the storage taken by new participants is paid.
+    #[access_control_any(roles(Role::Admin))]
+    pub fn set_storage_mode(&mut self, mode: StorageMode) {
+        self.storage.mode = mode;
+    }
+
+    /// Returns the way the storage taken by new participants is paid.
+    pub fn get_storage_mode(&self) -> StorageMode {
+        self.storage.mode
+    }
+
+    /// Returns the amount of the deposit tokens collected as storage fees.
+    pub fn get_collected_storage_fees(&self) -> U128 {
+        U128(self.storage.collected_fees)
+    }
+
+    /// Registers the account for participating in the sale. The account is the predecessor if
+    /// `account_id` is missing. The deposit above the storage cost of an account is refunded.
+    #[payable]
+    pub fn storage_deposit(
+        &mut self,
+        account_id: Option<AccountId>,
+        registration_only: Option<bool>,
+    ) -> StorageBalance {
+        let _ = registration_only;
+        let amount = env::attached_deposit().as_yoctonear();
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let account = IntentsAccount::from(account_id);
+        let min_balance = storage_balance_min();
+        let balance = self
+            .storage
+            .balances
+            .get(&account)
+            .copied()
+            .unwrap_or_default();
+        let deposit = min_balance.saturating_sub(balance);
+
+        require(amount >= deposit, LaunchpadError::StorageDepositTooLow);
+
+        if deposit > 0 {
+            self.storage.balances.insert(account.clone(), min_balance);
+            self.storage.total_balance += deposit;
+        }
+
+        let refund = amount - deposit;
+        if refund > 0 {
+            let _ = Promise::new(env::predecessor_account_id())
+                .transfer(NearToken::from_yoctonear(refund));
+        }
+
+        self.storage_balance(&account, min_balance)
+    }
+
+    /// Withdraws the storage balance of the predecessor once its participation is finished:
+    /// the account has never deposited, or its entry is removed by the cleanup.
+    #[payable]
+    pub fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
+        assert_one_yocto();
+        let account = IntentsAccount::from(env::predecessor_account_id());
+        let Some(balance) = self.storage.balances.get(&account).copied() else {
+            LaunchpadError::NotRegistered.panic();
+        };
+
+        let available = self.storage_balance(&account, balance).available.0;
+        let amount = amount.map_or(available, |amount| amount.0);
+
+        require(amount <= available, LaunchpadError::StorageWithdrawTooHigh);
+
+        let balance = balance - amount;
+        self.storage.total_balance = self.storage.total_balance.saturating_sub(amount);
+
+        if balance == 0 {
+            self.storage.balances.remove(&account);
+        } else {
+            self.storage.balances.insert(account.clone(), balance);
+        }
+
+        if amount > 0 {
+            let _ = Promise::new(env::predecessor_account_id())
+                .transfer(NearToken::from_yoctonear(amount));
+        }
+
+        self.storage_balance(&account, balance)
+    }
+
+    /// Returns the storage balance of the account or `None` if the account is not registered.
+    pub fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
+        let account = IntentsAccount::from(account_id);
+
+        self.storage
+            .balances
+            .get(&account)
+            .map(|balance| self.storage_balance(&account, *balance))
+    }
+
+    /// Returns the minimum and the maximum storage balance of an account.
+    pub fn storage_balance_bounds() -> StorageBalanceBounds {
+        let balance = U128(storage_balance_min());
+
+        StorageBalanceBounds {
+            min: balance,
+            max: Some(balance),
+        }
+    }
+}
+
+impl AuroraLaunchpadContract {
+    /// Checks the storage registration of a new participant. Returns the storage fee which must
+    /// be deducted from the deposit.
+    pub(crate) fn charge_storage_fee(&mut self, account: &IntentsAccount, amount: u128) -> u128 {
+        let fee = self.storage_fee(account, amount);
+        self.storage.collected_fees += fee;
+        fee
+    }
+
+    /// Returns the storage fee of the deposit without charging it.
+    pub(crate) fn storage_fee(&self, account: &IntentsAccount, amount: u128) -> u128 {
+        if self.investments.contains_key(account) || self.storage.balances.contains_key(account) {
+            return 0;
+        }
+
+        match self.storage.mode {
+            StorageMode::Disabled => 0,
+            StorageMode::Required => LaunchpadError::NotRegistered.panic(),
+            StorageMode::DepositTokenFee(fee) => {
+                require(
+                    amount.saturating_sub(fee.0) >= self.config.min_deposit.0,
+                    LaunchpadError::DepositTooLowForStorageFee,
+                );
+                fee.0
+            }
+        }
+    }
+
+    /// Returns the total storage balance of the registered accounts, which must be kept on
+    /// the contract balance.
+    pub(crate) const fn storage_balances_total(&self) -> u128 {
+        self.storage.total_balance
+    }
+
+    /// Returns the storage fee charged by `charge_storage_fee` if the whole deposit is refunded.
+    pub(crate) fn refund_storage_fee(&mut self, fee: u128) {
+        self.storage.collected_fees = self.storage.collected_fees.saturating_sub(fee);
+    }
+
+    /// Takes the collected storage fees for the withdrawal by the admin. All collected fees are
+    /// taken if `amount` is `None`.
+    pub(crate) fn take_storage_fees(&mut self, amount: Option<U128>) -> U128 {
+        let collected_fees = self.storage.collected_fees;
+        let amount = amount.map_or(collected_fees, |amount| amount.0);
+
+        require(amount > 0, LaunchpadError::NothingToWithdraw);
+        require(
+            amount <= collected_fees,
+            LaunchpadError::StorageFeesExceeded,
+        );
+
+        self.storage.collected_fees -= amount;
+
+        U128(amount)
+    }
+
+    /// Returns the storage fees which weren't withdrawn by the admin.
+    pub(crate) fn return_storage_fees(&mut self, amount: u128) {
+        self.storage.collected_fees = self.storage.collected_fees.saturating_add(amount);
+    }
+
+    fn storage_balance(&self, account: &IntentsAccount, balance: u128) -> StorageBalance {
+        let available = if self.is_participation_finished(account) {
+            balance
+        } else {
+            0
+        };
+
+        StorageBalance {
+            total: U128(balance),
+            available: U128(available),
+        }
+    }
+
+    /// The participation is finished once the account has no investment entry. The entry of
+    /// a fully withdrawn deposit is kept until the cleanup, so the storage isn't released while
+    /// the account could deposit again without paying for the storage.
+    fn is_participation_finished(&self, account: &IntentsAccount) -> bool {
+        !self.investments.contains_key(account)
+    }
+}
+
+fn storage_balance_min() -> u128 {
+    env::storage_byte_cost()
+        .as_yoctonear()
+        .saturating_mul(STORAGE_BYTES_PER_ACCOUNT)
+}
@@ -0,0 +1,163 @@
+use aurora_launchpad_types::account::{AccountAction, AccountDiscountPhase};
+use aurora_launchpad_types::config::{
+    LaunchpadConfig, LaunchpadStatus, VestingSchedule, VestingScheme,
+};
+use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
+use aurora_launchpad_types::duration::Duration;
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::testing_env;
+use std::collections::HashSet;
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, TEN_DAYS, price_discovery_config};
+
+#[test]
+fn account_summary_during_sale() {
+    let mut contract = prepare_contract(price_discovery_config());
+    let alice = add_investment(&mut contract, "alice.near", 1_000_000);
+
+    let summary = contract.get_account_summary(alice.clone());
+
+    assert_eq!(summary.deposit, U128(1_000_000));
+    assert_eq!(summary.weight, U128(1_000_000));
+    assert_eq!(summary.allocation, contract.config.sale_amount);
+    assert_eq!(summary.claimed, U128(0));
+    assert_eq!(summary.next_unlock, Some(NOW + TEN_DAYS));
+    assert!(!summary.is_withdraw_in_progress);
+    assert!(summary.discount_phases.is_empty());
+    assert_eq!(
+        summary.allowed_actions,
+        vec![AccountAction::Deposit, AccountAction::WithdrawWithIntents]
+    );
+
+    contract.locked_withdraw.insert(alice.clone());
+
+    let summary = contract.get_account_summary(alice);
+
+    assert!(summary.is_withdraw_in_progress);
+    assert!(summary.allowed_actions.is_empty());
+}
+
+#[test]
+fn account_summary_after_success_with_vesting() {
+    let mut contract = prepare_contract(price_discovery_config());
+    let soft_cap = contract.config.soft_cap.0;
+    let alice = add_investment(&mut contract, "alice.near", soft_cap);
+    contract.config.vesting_schedule = Some(VestingSchedule {
+        cliff_period: Duration::from_nanos(100),
+        vesting_period: Duration::from_nanos(1000),
+        instant_claim_percentage: Some(1000),
+        vesting_scheme: VestingScheme::AfterCliff,
+    });
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 10)
+            .current_account_id(bob())
+            .build()
+    );
+    assert_eq!(contract.get_status(), LaunchpadStatus::Success);
+
+    let summary = contract.get_account_summary(alice);
+    let sale_amount = contract.config.sale_amount.0;
+
+    assert_eq!(summary.allocation, U128(sale_amount));
+    assert_eq!(summary.available_for_claim, U128(sale_amount / 10));
+    assert_eq!(
+        summary.remaining_vesting,
+        U128(sale_amount - sale_amount / 10)
+    );
+    assert_eq!(summary.next_unlock, Some(NOW + TEN_DAYS + 100));
+    assert_eq!(summary.allowed_actions, vec![AccountAction::Claim]);
+}
+
+#[test]
+fn account_summary_discount_phases() {
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+    let mut config = price_discovery_config();
+    config.discounts = Some(DiscountParams {
+        phases: vec![
+            DiscountPhase {
+                id: 0,
+                start_time: NOW,
+                end_time: NOW + 100,
+                percentage: 1000,
+                whitelist: Some(HashSet::from([alice.clone()])),
+                ..Default::default()
+            },
+            DiscountPhase {
+                id: 1,
+                start_time: NOW + 100,
+                end_time: NOW + TEN_DAYS,
+                percentage: 500,
+                ..Default::default()
+            },
+        ],
+        public_sale_start_time: None,
+    });
+    let contract = prepare_contract(config);
+    let bob = IntentsAccount::try_from("bob.near").unwrap();
+
+    assert_eq!(
+        contract.get_account_summary(alice).discount_phases,
+        vec![
+            AccountDiscountPhase {
+                id: 0,
+                sale_tokens: U128(0),
+                is_active: true,
+                is_allowed: true,
+            },
+            AccountDiscountPhase {
+                id: 1,
+                sale_tokens: U128(0),
+                is_active: false,
+                is_allowed: true,
+            },
+        ]
+    );
+    assert!(
+        !contract.get_account_summary(bob).discount_phases[0].is_allowed,
+        "bob is not in the whitelist of the first phase"
+    );
+}
+
+fn prepare_contract(config: LaunchpadConfig) -> AuroraLaunchpadContract {
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + 10)
+            .current_account_id(bob())
+            .build()
+    );
+
+    let mut contract = AuroraLaunchpadContract::new(config, None);
+    contract.is_sale_token_set = true;
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Ongoing);
+
+    contract
+}
+
+fn add_investment(
+    contract: &mut AuroraLaunchpadContract,
+    account: &str,
+    amount: u128,
+) -> IntentsAccount {
+    let account = IntentsAccount::try_from(account).unwrap();
+
+    contract.investments.insert(
+        account.clone(),
+        InvestmentAmount {
+            amount,
+            weight: amount,
+            claimed: 0,
+        },
+    );
+    contract.participants_count += 1;
+    contract.total_deposited += amount;
+    contract.total_sold_tokens += amount;
+
+    account
+}
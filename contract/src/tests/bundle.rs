@@ -0,0 +1,156 @@
+use aurora_launchpad_types::config::{
+    BundleRatio, BundledSaleToken, LaunchpadConfig, LaunchpadStatus, SaleToken, VestingSchedule,
+    VestingScheme,
+};
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::testing_env;
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, SALE_TOKEN_ID, price_discovery_config};
+
+const BUNDLED_TOKEN_ID: &str = "utility.token.near";
+
+#[test]
+fn test_funding_bundled_sale_tokens() {
+    let config = bundled_config();
+    let mut contract = AuroraLaunchpadContract::new(config.clone(), Some(bob()));
+
+    fund(&mut contract, SALE_TOKEN_ID, config.total_sale_amount);
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::NotInitialized);
+    assert_eq!(
+        contract.get_funded_sale_tokens(),
+        vec![config.sale_token.clone()]
+    );
+
+    fund(
+        &mut contract,
+        BUNDLED_TOKEN_ID,
+        config.bundled_sale_tokens[0].total_sale_amount,
+    );
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Ongoing);
+    assert_eq!(contract.get_funded_sale_tokens().len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Wrong total sale amount of the bundled sale token")]
+fn test_funding_bundled_sale_token_wrong_amount() {
+    let mut contract = AuroraLaunchpadContract::new(bundled_config(), Some(bob()));
+    fund(&mut contract, BUNDLED_TOKEN_ID, U128(1));
+}
+
+#[test]
+#[should_panic(expected = "The bundled sale token is already funded")]
+fn test_funding_bundled_sale_token_twice() {
+    let config = bundled_config();
+    let amount = config.bundled_sale_tokens[0].total_sale_amount;
+    let mut contract = AuroraLaunchpadContract::new(config, Some(bob()));
+
+    fund(&mut contract, BUNDLED_TOKEN_ID, amount);
+    fund(&mut contract, BUNDLED_TOKEN_ID, amount);
+}
+
+#[test]
+fn test_bundled_allocations() {
+    let mut contract = prepare_successful_contract();
+    let alice = add_participant(&mut contract, "alice.near");
+    let sale_amount = contract.config.sale_amount.0;
+
+    let allocations = contract.get_bundled_allocations(&alice);
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(
+        allocations[0].token,
+        contract.config.bundled_sale_tokens[0].token
+    );
+    assert_eq!(allocations[0].allocation, U128(sale_amount / 2));
+    assert_eq!(allocations[0].claimed, U128(0));
+    // A half of the vesting period has passed.
+    assert_eq!(allocations[0].available_for_claim, U128(sale_amount / 4));
+}
+
+#[test]
+fn test_cleanup_waits_for_bundled_claims() {
+    let mut contract = prepare_successful_contract();
+    let alice = add_participant(&mut contract, "alice.near");
+    let sale_amount = contract.config.sale_amount.0;
+
+    contract.investments.get_mut(&alice).unwrap().claimed = sale_amount;
+
+    assert_eq!(contract.cleanup(10), 0);
+    assert_eq!(contract.get_participants_to_cleanup(), 1);
+
+    contract.bundle.set_claimed(0, &alice, sale_amount / 2);
+
+    assert_eq!(contract.cleanup(10), 1);
+    assert_eq!(contract.bundle.claimed(0, &alice), 0);
+}
+
+fn bundled_config() -> LaunchpadConfig {
+    let mut config = price_discovery_config();
+    config.bundled_sale_tokens = vec![BundledSaleToken {
+        token: SaleToken::Nep141(BUNDLED_TOKEN_ID.parse().unwrap()),
+        total_sale_amount: U128(config.sale_amount.0 / 2),
+        ratio: BundleRatio {
+            sale_token: 2.into(),
+            bundled_token: 1.into(),
+        },
+        vesting_schedule: Some(VestingSchedule {
+            cliff_period: 0.into(),
+            vesting_period: 20.into(),
+            instant_claim_percentage: None,
+            vesting_scheme: VestingScheme::Immediate,
+        }),
+    }];
+
+    config
+}
+
+fn fund(contract: &mut AuroraLaunchpadContract, token_account_id: &str, amount: U128) {
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + 10)
+            .predecessor_account_id(token_account_id.parse().unwrap())
+            .build()
+    );
+
+    let _ = contract.ft_on_transfer(bob(), amount, String::new(), None);
+}
+
+fn prepare_successful_contract() -> AuroraLaunchpadContract {
+    let config = bundled_config();
+    let vesting_start = config.end_date;
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(vesting_start + 10)
+            .build()
+    );
+
+    let mut contract = AuroraLaunchpadContract::new(config, Some(bob()));
+    contract.is_sale_token_set = true;
+    contract.total_deposited = contract.config.soft_cap.0;
+
+    contract
+}
+
+fn add_participant(contract: &mut AuroraLaunchpadContract, account: &str) -> IntentsAccount {
+    let account = IntentsAccount::try_from(account).unwrap();
+
+    contract.investments.insert(
+        account.clone(),
+        InvestmentAmount {
+            amount: 100_000,
+            weight: 100_000,
+            claimed: 0,
+        },
+    );
+    contract.total_sold_tokens = 100_000;
+    contract.participants_count += 1;
+    contract.cleanup.add_participant(account.clone());
+
+    account
+}
@@ -0,0 +1,164 @@
+use aurora_launchpad_types::admin_withdraw::{AdminWithdrawDirection, WithdrawalToken};
+use aurora_launchpad_types::config::{DistributionAccount, LaunchpadStatus};
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::{NearToken, testing_env};
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, SOLVER_ACCOUNT_ID, TEN_DAYS, price_discovery_config};
+
+#[test]
+#[should_panic(expected = "Cleanup is allowed after the sale has concluded only")]
+fn test_cleanup_ongoing_sale() {
+    let mut contract = prepare_contract(NOW + 10);
+    contract.cleanup(10);
+}
+
+#[test]
+fn test_cleanup_failed_sale() {
+    let mut contract = prepare_contract(NOW + TEN_DAYS + 1);
+    let alice = add_participant(&mut contract, "alice.near", 0, 0);
+    let carol = add_participant(&mut contract, "carol.near", 100_000, 0);
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Failed);
+    assert_eq!(contract.cleanup(10), 1);
+    assert!(contract.investments.get(&alice).is_none());
+    assert_eq!(contract.get_participants_to_cleanup(), 1);
+    assert!(!contract.is_archived());
+
+    // The deposit is withdrawn.
+    contract.investments.get_mut(&carol).unwrap().amount = 0;
+
+    assert_eq!(contract.cleanup(10), 1);
+    assert_eq!(contract.get_participants_to_cleanup(), 0);
+    assert_eq!(contract.get_status(), LaunchpadStatus::Archived);
+    assert_eq!(contract.get_participants_count(), 2);
+}
+
+#[test]
+fn test_withdraw_storage_fees_after_archiving_failed_sale() {
+    let mut contract = prepare_contract(NOW + TEN_DAYS + 1);
+    add_participant(&mut contract, "alice.near", 0, 0);
+    contract.return_storage_fees(1_000);
+
+    assert_eq!(contract.cleanup(10), 1);
+    assert!(contract.is_archived());
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .predecessor_account_id(bob())
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build()
+    );
+    let _ = contract.admin_withdraw(
+        WithdrawalToken::StorageFees,
+        AdminWithdrawDirection::Near(bob()),
+        None,
+    );
+
+    assert_eq!(contract.get_collected_storage_fees(), U128(0));
+}
+
+#[test]
+fn test_cleanup_limit() {
+    let mut contract = prepare_contract(NOW + TEN_DAYS + 1);
+
+    for account in ["alice.near", "carol.near", "dave.near"] {
+        add_participant(&mut contract, account, 0, 0);
+    }
+
+    assert_eq!(contract.cleanup(2), 2);
+    assert_eq!(contract.get_participants_to_cleanup(), 1);
+    assert!(!contract.is_archived());
+
+    assert_eq!(contract.cleanup(2), 1);
+    assert!(contract.is_archived());
+}
+
+#[test]
+fn test_cleanup_successful_sale() {
+    let mut contract = prepare_contract(NOW + TEN_DAYS + 1);
+    let sale_amount = contract.config.sale_amount.0;
+    let alice = add_participant(&mut contract, "alice.near", 100_000, sale_amount);
+    let carol = add_participant(&mut contract, "carol.near", 100_000, 0);
+
+    contract.total_deposited = contract.config.soft_cap.0;
+    contract.total_sold_tokens = 200_000;
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Success);
+    assert_eq!(contract.cleanup(10), 1);
+    assert!(contract.investments.get(&alice).is_none());
+    assert!(contract.investments.get(&carol).is_some());
+
+    // The bought tokens are claimed.
+    contract.investments.get_mut(&carol).unwrap().claimed = sale_amount;
+
+    assert_eq!(contract.cleanup(10), 1);
+    // The sale tokens aren't distributed yet.
+    assert!(!contract.is_archived());
+
+    let proportions = contract.config.distribution_proportions.clone();
+    contract.distributed_accounts.insert(
+        DistributionAccount::new_near(SOLVER_ACCOUNT_ID).unwrap(),
+        (proportions.solver_allocation.0, false),
+    );
+    for proportion in proportions.stakeholder_proportions {
+        contract
+            .distributed_accounts
+            .insert(proportion.account, (proportion.allocation.0, false));
+    }
+
+    assert_eq!(contract.cleanup(10), 0);
+    assert!(contract.is_archived());
+    assert!(
+        contract
+            .distributed_accounts
+            .get(&DistributionAccount::new_near(SOLVER_ACCOUNT_ID).unwrap())
+            .is_none()
+    );
+}
+
+#[test]
+fn test_cleanup_skips_pending_claims() {
+    let mut contract = prepare_contract(NOW + TEN_DAYS + 1);
+    let alice = add_participant(&mut contract, "alice.near", 0, 0);
+
+    contract.cleanup.start_claim(&alice);
+    assert_eq!(contract.cleanup(10), 0);
+
+    contract.cleanup.finish_claim(&alice);
+    assert_eq!(contract.cleanup(10), 1);
+}
+
+fn prepare_contract(timestamp: u64) -> AuroraLaunchpadContract {
+    testing_env!(VMContextBuilder::new().block_timestamp(timestamp).build());
+    let mut contract = AuroraLaunchpadContract::new(price_discovery_config(), Some(bob()));
+    contract.is_sale_token_set = true;
+
+    contract
+}
+
+fn add_participant(
+    contract: &mut AuroraLaunchpadContract,
+    account: &str,
+    amount: u128,
+    claimed: u128,
+) -> IntentsAccount {
+    let account = IntentsAccount::try_from(account).unwrap();
+
+    contract.investments.insert(
+        account.clone(),
+        InvestmentAmount {
+            amount,
+            weight: amount,
+            claimed,
+        },
+    );
+    contract.participants_count += 1;
+    contract.cleanup.add_participant(account.clone());
+
+    account
+}
@@ -1,14 +1,17 @@
 use aurora_launchpad_types::config::{
-    DepositToken, DistributionProportions, LaunchpadStatus, Mechanics,
+    DepositToken, DistributionProportions, LaunchpadStatus, Mechanics, SaleToken, VestingSchedule,
+    VestingScheme,
 };
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
 use chrono::DateTime;
+use near_plugins::AccessControllable;
 use near_sdk::json_types::U128;
 use near_sdk::test_utils::VMContextBuilder;
 use near_sdk::test_utils::test_env::bob;
-use near_sdk::{NearToken, testing_env};
+use near_sdk::{AccountId, NearToken, testing_env};
 
-use crate::AuroraLaunchpadContract;
 use crate::tests::utils::{NOW, base_config};
+use crate::{AuroraLaunchpadContract, Role};
 
 #[test]
 fn test_nep141_deposit_token() {
@@ -55,6 +58,30 @@ fn test_nep141_deposit_token_more_token_ids() {
     ));
 }
 
+#[test]
+fn test_nep245_sale_token() {
+    let mut config = base_config(Mechanics::PriceDiscovery);
+    config.sale_token = SaleToken::Nep245((
+        "intents.near".parse().unwrap(),
+        "nep141:sale.near".to_string(),
+    ));
+    let contract = AuroraLaunchpadContract::new(config, None);
+
+    assert!(contract.is_nep245_sale_token(
+        &"intents.near".parse().unwrap(),
+        &["nep141:sale.near".to_string()]
+    ));
+    assert!(!contract.is_nep245_sale_token(
+        &"intents.near".parse().unwrap(),
+        &["nep141:other.near".to_string()]
+    ));
+    assert!(!contract.is_nep141_sale_token(&"intents.near".parse().unwrap()));
+    assert_eq!(
+        contract.get_sale_token_account_id(),
+        "intents.near".parse::<near_sdk::AccountId>().unwrap()
+    );
+}
+
 #[test]
 fn test_lock() {
     let mut contract = prepare_contract();
@@ -141,6 +168,7 @@ fn unsold_amount_of_tokens_fixed_price() {
             solver_allocation: 0.into(),
             stakeholder_proportions: vec![],
             deposits: None,
+            liquidity: None,
         };
 
         config.soft_cap = 1000.into();
@@ -197,6 +225,119 @@ fn set_tge_in_the_past() {
     ));
 }
 
+#[test]
+fn status_at() {
+    let mut contract = prepare_contract();
+    let end_date = contract.config.end_date;
+
+    assert_eq!(contract.get_status_at(NOW - 1), LaunchpadStatus::NotStarted);
+    assert_eq!(contract.get_status_at(NOW + 10), LaunchpadStatus::Ongoing);
+    assert_eq!(
+        contract.get_status_at(end_date + 1),
+        LaunchpadStatus::Success
+    );
+
+    contract.config.tge = Some(end_date + 100);
+
+    assert_eq!(
+        contract.get_status_at(end_date + 1),
+        LaunchpadStatus::PreTGE
+    );
+    assert_eq!(
+        contract.get_status_at(end_date + 100),
+        LaunchpadStatus::Success
+    );
+
+    contract.total_deposited -= 1;
+
+    assert_eq!(
+        contract.get_status_at(end_date + 1),
+        LaunchpadStatus::Failed
+    );
+    // The current status is not affected.
+    assert_eq!(contract.get_status(), LaunchpadStatus::Ongoing);
+
+    // The lock overrides the current status only.
+    contract.is_locked = true;
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Locked);
+    assert_eq!(
+        contract.get_status_at(end_date + 1),
+        LaunchpadStatus::Failed
+    );
+}
+
+#[test]
+fn available_for_claim_at() {
+    let mut contract = prepare_contract();
+    let account = IntentsAccount::try_from("alice.near").unwrap();
+    let end_date = contract.config.end_date;
+    let sale_amount = contract.config.sale_amount.0;
+
+    contract.investments.insert(
+        account.clone(),
+        InvestmentAmount {
+            amount: 1000,
+            weight: 1000,
+            claimed: 0,
+        },
+    );
+    contract.total_sold_tokens = 1000;
+    contract.config.vesting_schedule = Some(VestingSchedule {
+        cliff_period: 0.into(),
+        vesting_period: 1000.into(),
+        instant_claim_percentage: None,
+        vesting_scheme: VestingScheme::Immediate,
+    });
+
+    assert_eq!(
+        contract.get_available_for_claim_at(&account, end_date - 1),
+        U128(0)
+    );
+    assert_eq!(
+        contract.get_available_for_claim_at(&account, end_date + 500),
+        U128(sale_amount / 2)
+    );
+    assert_eq!(
+        contract.get_available_for_claim_at(&account, end_date + 1000),
+        U128(sale_amount)
+    );
+    assert_eq!(
+        contract.get_available_for_claim(&account),
+        contract.get_available_for_claim_at(&account, NOW + 10)
+    );
+    assert_eq!(
+        contract.get_remaining_vesting_at(&account, end_date + 500),
+        U128(sale_amount / 2)
+    );
+    assert_eq!(
+        contract.get_remaining_vesting(&account),
+        contract.get_remaining_vesting_at(&account, NOW + 10)
+    );
+}
+
+#[test]
+fn upgrader_role_is_granted_to_parent_account() {
+    let factory: AccountId = "factory.near".parse().unwrap();
+    let alice: AccountId = "alice.near".parse().unwrap();
+    let has_upgrader_role = |current_account_id: &str, predecessor_account_id: &AccountId| {
+        testing_env!(
+            VMContextBuilder::new()
+                .current_account_id(current_account_id.parse().unwrap())
+                .predecessor_account_id(predecessor_account_id.clone())
+                .build()
+        );
+        let contract = AuroraLaunchpadContract::new(base_config(Mechanics::PriceDiscovery), None);
+
+        contract.acl_has_role(Role::Upgrader.into(), predecessor_account_id.clone())
+    };
+
+    assert!(has_upgrader_role("lp-1.factory.near", &factory));
+    assert!(!has_upgrader_role("lp-1.factory.near", &alice));
+    assert!(!has_upgrader_role("lp-1.sub.factory.near", &factory));
+    assert!(!has_upgrader_role("factory.near", &factory));
+}
+
 fn prepare_contract() -> AuroraLaunchpadContract {
     let context = VMContextBuilder::new()
         .block_timestamp(NOW + 10)
@@ -18,7 +18,12 @@ struct TestContext {
 }
 
 impl TestContext {
-    pub fn new(config: LaunchpadConfig) -> Self {
+    pub fn new(mut config: LaunchpadConfig) -> Self {
+        // The discount phases of the tests use small timestamps, so the sale starts from the very
+        // beginning. The soft cap of the fixed price sales is irrelevant for the discounts.
+        config.start_date = 0;
+        config.allow_unreachable_soft_cap = true;
+
         let context = VMContextBuilder::new()
             .block_timestamp(NOW + 10)
             .current_account_id(bob())
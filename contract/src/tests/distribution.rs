@@ -0,0 +1,109 @@
+use aurora_launchpad_types::config::{DistributionAccount, LaunchpadStatus};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::{NearToken, PromiseResult, RuntimeFeesConfig, test_vm_config, testing_env};
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, SOLVER_ACCOUNT_ID, TEN_DAYS, price_discovery_config};
+
+#[test]
+fn test_distribution_progress() {
+    let mut contract = prepare_successful_contract();
+    let solver = DistributionAccount::new_near(SOLVER_ACCOUNT_ID).unwrap();
+    let team = DistributionAccount::new_near("team.near").unwrap();
+
+    let progress = contract.get_distribution_progress();
+    assert_eq!(progress.len(), 2);
+    assert_eq!(progress[0].account, solver);
+    assert_eq!(
+        progress[0].target,
+        contract.config.distribution_proportions.solver_allocation
+    );
+    assert_eq!(progress[1].account, team);
+    assert!(progress.iter().all(|progress| {
+        progress.distributed == U128(0) && !progress.is_busy && progress.last_failure.is_none()
+    }));
+
+    let _ = contract.distribute_sale_tokens(Some(1));
+
+    let progress = contract.get_distribution_progress();
+    assert!(progress[0].is_busy);
+    assert!(!progress[1].is_busy);
+
+    let _ = contract.distribute_sale_tokens(None);
+
+    let progress = contract.get_distribution_progress();
+    assert!(progress.iter().all(|progress| progress.is_busy));
+}
+
+#[test]
+#[should_panic(expected = "The batch size must be between 1 and 7")]
+fn test_distribute_sale_tokens_with_zero_batch_size() {
+    let mut contract = prepare_successful_contract();
+    let _ = contract.distribute_sale_tokens(Some(0));
+}
+
+#[test]
+#[should_panic(expected = "The batch size must be between 1 and 7")]
+fn test_distribute_sale_tokens_with_too_big_batch_size() {
+    let mut contract = prepare_successful_contract();
+    let _ = contract.distribute_sale_tokens(Some(8));
+}
+
+#[test]
+fn test_registrations_of_batch_are_funded_from_contract_balance() {
+    let mut contract = prepare_successful_contract();
+    let solver = DistributionAccount::new_near(SOLVER_ACCOUNT_ID).unwrap();
+    let team = DistributionAccount::new_near("team.near").unwrap();
+    let deposit = contract.get_storage_deposit_settings().deposit.0;
+
+    // The balance is enough for one registration only.
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .account_balance(NearToken::from_yoctonear(deposit * 3 / 2))
+            .storage_usage(0)
+            .build(),
+        test_vm_config(),
+        RuntimeFeesConfig::test(),
+        Default::default(),
+        vec![
+            PromiseResult::Successful(b"null".to_vec()),
+            PromiseResult::Successful(b"null".to_vec()),
+        ],
+    );
+
+    let _ = contract.continue_distribution(vec![(solver, U128(100)), (team.clone(), U128(100))]);
+
+    assert_eq!(
+        contract.get_unregistered_recipients(),
+        vec!["team.near".parse().unwrap()]
+    );
+    let progress = contract.get_distribution_progress();
+    assert!(progress[0].last_failure.is_none());
+    assert_eq!(progress[1].account, team);
+    assert!(!progress[1].is_busy);
+    assert_eq!(
+        progress[1].last_failure.as_deref(),
+        Some("No funds to register the account in the token")
+    );
+}
+
+fn prepare_successful_contract() -> AuroraLaunchpadContract {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let mut contract = AuroraLaunchpadContract::new(price_discovery_config(), Some(bob()));
+    contract.is_sale_token_set = true;
+    contract.total_deposited = contract.config.soft_cap.0;
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build()
+    );
+    assert_eq!(contract.get_status(), LaunchpadStatus::Success);
+
+    contract
+}
@@ -0,0 +1,133 @@
+use aurora_launchpad_types::config::{LaunchpadConfig, LaunchpadStatus, LiquidityProportion};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::{NearToken, PromiseOrValue, testing_env};
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, TEN_DAYS, price_discovery_config};
+
+const LIQUIDITY_ALLOCATION: u128 = 1_000_000;
+
+#[test]
+fn test_liquidity_seeding_state() {
+    let mut contract = prepare_successful_contract(liquidity_config());
+
+    assert!(!contract.is_liquidity_seeded());
+    assert_eq!(contract.unsold_amount_of_tokens(), 0);
+
+    contract.liquidity.pool_id = Some(0);
+    contract.liquidity.sale_amount = U128(LIQUIDITY_ALLOCATION - 100);
+    contract.liquidity.is_added = true;
+
+    // The shares aren't transferred to the receiver yet.
+    assert!(!contract.is_liquidity_seeded());
+    // The sale tokens of the allocation which aren't added to the pool are unsold.
+    assert_eq!(contract.unsold_amount_of_tokens(), 100);
+
+    contract.liquidity.is_shares_transferred = true;
+    assert!(contract.is_liquidity_seeded());
+}
+
+#[test]
+fn test_liquidity_seeding_without_configuration() {
+    let contract = prepare_successful_contract(price_discovery_config());
+    assert!(contract.is_liquidity_seeded());
+}
+
+#[test]
+#[should_panic(expected = "Liquidity seeding is not configured")]
+fn test_seed_liquidity_without_configuration() {
+    let mut contract = prepare_successful_contract(price_discovery_config());
+    let _ = contract.seed_liquidity();
+}
+
+#[test]
+#[should_panic(expected = "Liquidity could be seeded after success only")]
+fn test_seed_liquidity_before_success() {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let mut contract = AuroraLaunchpadContract::new(liquidity_config(), Some(bob()));
+    contract.is_sale_token_set = true;
+
+    assert_eq!(contract.get_status(), LaunchpadStatus::Ongoing);
+    let _ = contract.seed_liquidity();
+}
+
+#[test]
+#[should_panic(expected = "Not enough NEAR attached for the pool creation")]
+fn test_seed_liquidity_without_deposit() {
+    let mut contract = prepare_successful_contract(liquidity_config());
+    let _ = contract.seed_liquidity();
+}
+
+#[test]
+#[should_panic(expected = "The liquidity has been already seeded")]
+fn test_seed_liquidity_twice() {
+    let mut contract = prepare_successful_contract(liquidity_config());
+    contract.liquidity.pool_id = Some(0);
+    contract.liquidity.is_added = true;
+    contract.liquidity.is_shares_transferred = true;
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .attached_deposit(NearToken::from_yoctonear(1))
+            .build()
+    );
+
+    let _ = contract.seed_liquidity();
+}
+
+#[test]
+fn test_failed_liquidity_step_stops_seeding() {
+    let mut contract = prepare_successful_contract(liquidity_config());
+    contract.liquidity.pool_id = Some(0);
+    contract.liquidity.is_ongoing = true;
+    // The sale price couldn't be calculated without deposits.
+    contract.total_deposited = 0;
+
+    assert!(matches!(
+        contract.continue_liquidity_seeding(),
+        PromiseOrValue::Value(())
+    ));
+    let seeding = contract.get_liquidity_seeding();
+    assert!(!seeding.is_ongoing);
+    assert!(
+        seeding
+            .last_failure
+            .is_some_and(|reason| reason.contains("Liquidity seeding failed"))
+    );
+    assert!(!contract.is_liquidity_seeded());
+}
+
+fn liquidity_config() -> LaunchpadConfig {
+    let mut config = price_discovery_config();
+    config.distribution_proportions.liquidity = Some(LiquidityProportion {
+        dex_account_id: "dex.near".parse().unwrap(),
+        pool_fee: 30,
+        deposit_percentage: 2000,
+        sale_allocation: U128(LIQUIDITY_ALLOCATION),
+        shares_receiver_id: Some(bob()),
+    });
+    config.total_sale_amount = U128(config.total_sale_amount.0 + LIQUIDITY_ALLOCATION);
+
+    config
+}
+
+fn prepare_successful_contract(config: LaunchpadConfig) -> AuroraLaunchpadContract {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let mut contract = AuroraLaunchpadContract::new(config, Some(bob()));
+    contract.is_sale_token_set = true;
+    contract.total_deposited = contract.config.soft_cap.0;
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .build()
+    );
+    assert_eq!(contract.get_status(), LaunchpadStatus::Success);
+
+    contract
+}
@@ -0,0 +1,111 @@
+use aurora_launchpad_types::config::LaunchpadStatus;
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{borsh, env, testing_env};
+
+use crate::AuroraLaunchpadContract;
+use crate::migration::{LEGACY_STATE_VERSION, STATE_VERSION};
+use crate::storage_management::StorageMode;
+use crate::tests::utils::{NOW, TEN_DAYS, fixed_price_config};
+
+/// The state of the launchpad 0.7.x with the fixed price config, one participant and
+/// the sale token set.
+const STATE_V0_7: &[u8] = include_bytes!("fixtures/state_v0_7.borsh");
+
+#[test]
+fn test_migrate_from_v0_7() {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+    let investment = InvestmentAmount {
+        amount: 500_000,
+        weight: 10,
+        claimed: 0,
+    };
+
+    env::storage_write(b"STATE", STATE_V0_7);
+    env::storage_write(
+        &[b"investments".as_slice(), &borsh::to_vec(&alice).unwrap()].concat(),
+        &borsh::to_vec(&investment).unwrap(),
+    );
+    assert_eq!(
+        AuroraLaunchpadContract::get_state_version(),
+        LEGACY_STATE_VERSION
+    );
+
+    let contract = AuroraLaunchpadContract::migrate();
+
+    assert_eq!(AuroraLaunchpadContract::get_state_version(), STATE_VERSION);
+    assert_eq!(contract.get_config(), fixed_price_config());
+    assert_eq!(contract.get_status(), LaunchpadStatus::Ongoing);
+    assert_eq!(contract.get_participants_count(), 1);
+    assert_eq!(contract.total_deposited, 500_000);
+    assert_eq!(contract.total_sold_tokens, 10);
+    assert_eq!(contract.get_investments(&alice), Some(U128(500_000)));
+    assert!(contract.is_sale_token_set);
+    assert!(!contract.is_locked());
+    assert_eq!(contract.get_storage_deposit_settings().budget, None);
+    assert!(contract.get_unregistered_recipients().is_empty());
+    assert_eq!(contract.get_storage_mode(), StorageMode::Disabled);
+}
+
+#[test]
+fn test_cleanup_after_migration_from_v0_7() {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+    let investment = InvestmentAmount {
+        amount: 500_000,
+        weight: 10,
+        claimed: 0,
+    };
+
+    env::storage_write(b"STATE", STATE_V0_7);
+    env::storage_write(
+        &[b"investments".as_slice(), &borsh::to_vec(&alice).unwrap()].concat(),
+        &borsh::to_vec(&investment).unwrap(),
+    );
+
+    let mut contract = AuroraLaunchpadContract::migrate();
+
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + TEN_DAYS + 1)
+            .build()
+    );
+    assert_eq!(contract.get_status(), LaunchpadStatus::Failed);
+
+    // The participants of 0.7.x aren't tracked, so the launchpad isn't archived while they
+    // could still withdraw their deposits.
+    assert_eq!(contract.cleanup(10), 0);
+    assert_eq!(contract.get_status(), LaunchpadStatus::Failed);
+    assert_eq!(contract.get_investments(&alice), Some(U128(500_000)));
+}
+
+#[test]
+fn test_migrate_current_state() {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    let contract = AuroraLaunchpadContract::new(fixed_price_config(), None);
+    assert_eq!(AuroraLaunchpadContract::get_state_version(), STATE_VERSION);
+
+    env::state_write(&contract);
+
+    let contract = AuroraLaunchpadContract::migrate();
+
+    assert_eq!(AuroraLaunchpadContract::get_state_version(), STATE_VERSION);
+    assert_eq!(contract.get_config(), fixed_price_config());
+    assert_eq!(contract.get_participants_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Unsupported state version: 2")]
+fn test_migrate_unsupported_state_version() {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+
+    env::storage_write(b"STATE", STATE_V0_7);
+    env::storage_write(b"state_version", &2u16.to_le_bytes());
+
+    let _ = AuroraLaunchpadContract::migrate();
+}
@@ -1,3 +1,12 @@
+mod account;
+mod bundle;
+mod cleanup;
 mod contract;
 mod discount;
+mod distribution;
+mod liquidity;
+mod migration;
+mod simulation;
+mod stats;
+mod storage_management;
 pub mod utils;
@@ -0,0 +1,120 @@
+use aurora_launchpad_types::config::LaunchpadConfig;
+use aurora_launchpad_types::simulation::DepositDistributionView;
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::testing_env;
+
+use crate::AuroraLaunchpadContract;
+use crate::tests::utils::{NOW, TEN_DAYS, fixed_price_config, price_discovery_config};
+
+#[test]
+fn simulate_deposit_price_discovery() {
+    let contract = prepare_contract(price_discovery_config());
+    let account = IntentsAccount::try_from("alice.near").unwrap();
+
+    let simulation = contract.simulate_deposit(account.clone(), U128(1_000_000), None);
+
+    assert_eq!(
+        simulation.distribution,
+        DepositDistributionView::WithoutDiscount(U128(1_000_000))
+    );
+    assert_eq!(simulation.storage_fee, U128(0));
+    assert_eq!(simulation.deposit, U128(1_000_000));
+    assert_eq!(simulation.refund, U128(0));
+    assert_eq!(simulation.weight, U128(1_000_000));
+    assert_eq!(simulation.allocation, contract.config.sale_amount);
+
+    // The state of the contract is not changed.
+    assert_eq!(contract.get_investments(&account), None);
+    assert_eq!(contract.get_participants_count(), 0);
+    assert_eq!(contract.get_total_deposited(), U128(0));
+}
+
+#[test]
+fn simulate_deposit_fixed_price_with_refund() {
+    let mut contract = prepare_contract(fixed_price_config());
+    // 1000 sale tokens are left.
+    contract.total_sold_tokens = contract.config.sale_amount.0 - 1000;
+    let account = IntentsAccount::try_from("alice.near").unwrap();
+
+    // The deposit for 2000 sale tokens.
+    let simulation = contract.simulate_deposit(account, U128(100_000_000), None);
+
+    assert_eq!(simulation.deposit, U128(50_000_000));
+    assert_eq!(simulation.refund, U128(50_000_000));
+    assert_eq!(simulation.weight, U128(1000));
+    assert_eq!(simulation.allocation, U128(1000));
+}
+
+#[test]
+#[should_panic(expected = "ERR_2001: Launchpad is not ongoing")]
+fn simulate_deposit_after_sale() {
+    let contract = prepare_contract(price_discovery_config());
+    let account = IntentsAccount::try_from("alice.near").unwrap();
+
+    let _ = contract.simulate_deposit(account, U128(1_000_000), Some(NOW + TEN_DAYS + 1));
+}
+
+#[test]
+fn simulate_withdraw_price_discovery() {
+    let mut contract = prepare_contract(price_discovery_config());
+    let account = add_investment(&mut contract, "alice.near", 1_000_000);
+
+    let simulation = contract.simulate_withdraw(account.clone(), U128(400_000), None);
+
+    assert_eq!(
+        simulation.distribution,
+        DepositDistributionView::WithoutDiscount(U128(600_000))
+    );
+    assert_eq!(simulation.deposit, U128(600_000));
+    assert_eq!(simulation.weight, U128(600_000));
+    assert_eq!(simulation.allocation, contract.config.sale_amount);
+    assert_eq!(contract.get_investments(&account), Some(U128(1_000_000)));
+}
+
+#[test]
+#[should_panic(expected = "ERR_3006: Withdraw amount is greater than the deposit amount")]
+fn simulate_withdraw_more_than_deposit() {
+    let mut contract = prepare_contract(price_discovery_config());
+    let account = add_investment(&mut contract, "alice.near", 1_000_000);
+
+    let _ = contract.simulate_withdraw(account, U128(1_000_001), None);
+}
+
+fn prepare_contract(config: LaunchpadConfig) -> AuroraLaunchpadContract {
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + 10)
+            .current_account_id(bob())
+            .build()
+    );
+
+    let mut contract = AuroraLaunchpadContract::new(config, None);
+    contract.is_sale_token_set = true;
+
+    contract
+}
+
+fn add_investment(
+    contract: &mut AuroraLaunchpadContract,
+    account: &str,
+    amount: u128,
+) -> IntentsAccount {
+    let account = IntentsAccount::try_from(account).unwrap();
+
+    contract.investments.insert(
+        account.clone(),
+        InvestmentAmount {
+            amount,
+            weight: amount,
+            claimed: 0,
+        },
+    );
+    contract.participants_count += 1;
+    contract.total_deposited += amount;
+    contract.total_sold_tokens += amount;
+
+    account
+}
@@ -0,0 +1,155 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::{LaunchpadConfig, LaunchpadStatus};
+use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
+use aurora_launchpad_types::stats::{DiscountPhaseState, Price};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::testing_env;
+use std::collections::HashSet;
+
+use crate::AuroraLaunchpadContract;
+use crate::discount::DiscountPhases;
+use crate::tests::utils::{NOW, TEN_DAYS, fixed_price_config, price_discovery_config};
+
+#[test]
+fn sale_stats_price_discovery() {
+    let mut contract = prepare_contract(price_discovery_config());
+
+    let stats = contract.get_sale_stats();
+
+    assert_eq!(stats.status, LaunchpadStatus::Ongoing);
+    assert_eq!(stats.remaining_sale_tokens, contract.config.sale_amount);
+    assert_eq!(stats.soft_cap_progress, 0);
+    assert_eq!(stats.price, None);
+
+    contract.participants_count = 3;
+    contract.total_deposited = contract.config.soft_cap.0 / 4;
+    contract.total_sold_tokens = contract.total_deposited;
+
+    let stats = contract.get_sale_stats();
+
+    assert_eq!(stats.participants_count, 3);
+    assert_eq!(stats.total_deposited, U128(contract.total_deposited));
+    assert_eq!(stats.remaining_sale_tokens, U128(0));
+    assert_eq!(stats.soft_cap_progress, 2500);
+    assert_eq!(
+        stats.price,
+        Some(Price {
+            deposit_token: U128(contract.total_deposited),
+            sale_token: contract.config.sale_amount,
+        })
+    );
+
+    contract.total_deposited = contract.config.soft_cap.0 * 2;
+
+    assert_eq!(contract.get_sale_stats().soft_cap_progress, 10_000);
+}
+
+#[test]
+fn sale_stats_fixed_price() {
+    let mut contract = prepare_contract(fixed_price_config());
+    contract.total_sold_tokens = 1000;
+
+    let stats = contract.get_sale_stats();
+
+    assert_eq!(
+        stats.remaining_sale_tokens,
+        U128(contract.config.sale_amount.0 - 1000)
+    );
+    assert_eq!(
+        stats.price,
+        Some(Price {
+            deposit_token: U128(50_000),
+            sale_token: U128(1),
+        })
+    );
+}
+
+#[test]
+fn discount_phases_state() {
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+    let bob = IntentsAccount::try_from("bob.near").unwrap();
+    let mut config = fixed_price_config();
+    config.discounts = Some(DiscountParams {
+        phases: vec![
+            DiscountPhase {
+                id: 0,
+                start_time: NOW,
+                end_time: NOW + 100,
+                percentage: 1000,
+                phase_sale_limit: Some(U128(1000)),
+                remaining_go_to_phase_id: Some(1),
+                whitelist: Some(HashSet::from([alice.clone(), bob])),
+                ..Default::default()
+            },
+            DiscountPhase {
+                id: 1,
+                start_time: NOW + 100,
+                end_time: NOW + TEN_DAYS,
+                percentage: 500,
+                phase_sale_limit: Some(U128(500)),
+                ..Default::default()
+            },
+        ],
+        public_sale_start_time: None,
+    });
+    let mut contract = prepare_contract(config);
+    contract
+        .discount_state
+        .as_mut()
+        .unwrap()
+        .add_sale_tokens(0, &alice, 600);
+
+    assert_eq!(
+        contract.get_discount_phases_state(),
+        vec![
+            DiscountPhaseState {
+                id: 0,
+                percentage: 1000,
+                start_time: NOW,
+                end_time: NOW + 100,
+                is_active: true,
+                total_sale_tokens: U128(600),
+                limit: Some(U128(1000)),
+                effective_limit: Some(U128(1000)),
+                available_sale_tokens: Some(U128(400)),
+                whitelist_size: Some(2),
+            },
+            DiscountPhaseState {
+                id: 1,
+                percentage: 500,
+                start_time: NOW + 100,
+                end_time: NOW + TEN_DAYS,
+                is_active: false,
+                total_sale_tokens: U128(0),
+                limit: Some(U128(500)),
+                // The unsold tokens of the first phase are moved to the second one.
+                effective_limit: Some(U128(900)),
+                available_sale_tokens: Some(U128(900)),
+                whitelist_size: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn discount_phases_state_without_discounts() {
+    let contract = prepare_contract(price_discovery_config());
+
+    assert!(contract.get_discount_phases_state().is_empty());
+}
+
+fn prepare_contract(config: LaunchpadConfig) -> AuroraLaunchpadContract {
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + 10)
+            .current_account_id(bob())
+            .build()
+    );
+
+    let mut contract = AuroraLaunchpadContract::new(config, None);
+    contract.is_sale_token_set = true;
+
+    contract
+}
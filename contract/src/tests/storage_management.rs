@@ -0,0 +1,173 @@
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::test_utils::test_env::bob;
+use near_sdk::{NearToken, testing_env};
+
+use crate::AuroraLaunchpadContract;
+use crate::storage_management::StorageMode;
+use crate::tests::utils::{NOW, price_discovery_config};
+
+#[test]
+fn test_storage_deposit() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let min = AuroraLaunchpadContract::storage_balance_bounds().min;
+
+    assert!(contract.storage_balance_of(bob()).is_none());
+
+    let balance = contract.storage_deposit(None, None);
+    assert_eq!(balance.total, min);
+    assert_eq!(balance.available, min);
+
+    let balance = contract.storage_balance_of(bob()).unwrap();
+    assert_eq!(balance.total, min);
+}
+
+#[test]
+#[should_panic(expected = "The attached deposit is less than the minimum storage balance")]
+fn test_storage_deposit_less_than_min() {
+    let mut contract = prepare_contract(NearToken::from_yoctonear(1));
+    contract.storage_deposit(None, None);
+}
+
+#[test]
+#[should_panic(expected = "The amount is greater than the available storage balance")]
+fn test_storage_withdraw_during_participation() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let min = AuroraLaunchpadContract::storage_balance_bounds().min;
+
+    contract.storage_deposit(None, None);
+    contract.investments.insert(
+        IntentsAccount::from(bob()),
+        InvestmentAmount {
+            amount: 100_000,
+            weight: 100_000,
+            claimed: 0,
+        },
+    );
+
+    let balance = contract.storage_balance_of(bob()).unwrap();
+    assert_eq!(balance.available, U128(0));
+
+    set_attached_deposit(NearToken::from_yoctonear(1));
+    contract.storage_withdraw(Some(min));
+}
+
+#[test]
+#[should_panic(expected = "The amount is greater than the available storage balance")]
+fn test_storage_withdraw_after_withdrawing_deposit() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let min = AuroraLaunchpadContract::storage_balance_bounds().min;
+
+    contract.storage_deposit(None, None);
+    // The entry of the fully withdrawn deposit is kept until the cleanup.
+    contract.investments.insert(
+        IntentsAccount::from(bob()),
+        InvestmentAmount {
+            amount: 0,
+            weight: 0,
+            claimed: 0,
+        },
+    );
+
+    set_attached_deposit(NearToken::from_yoctonear(1));
+    contract.storage_withdraw(Some(min));
+}
+
+#[test]
+fn test_storage_withdraw() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+
+    contract.storage_deposit(None, None);
+
+    set_attached_deposit(NearToken::from_yoctonear(1));
+    let balance = contract.storage_withdraw(None);
+    assert_eq!(balance.total, U128(0));
+    assert!(contract.storage_balance_of(bob()).is_none());
+}
+
+#[test]
+#[should_panic(expected = "The account is not registered")]
+fn test_required_storage_registration() {
+    let mut contract = prepare_contract(NearToken::from_yoctonear(0));
+
+    contract.set_storage_mode(StorageMode::Required);
+    contract.charge_storage_fee(&IntentsAccount::from(bob()), 200_000);
+}
+
+#[test]
+fn test_deposit_token_storage_fee() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+
+    contract.set_storage_mode(StorageMode::DepositTokenFee(U128(1_000)));
+
+    assert_eq!(contract.charge_storage_fee(&alice, 200_000), 1_000);
+    assert_eq!(contract.get_collected_storage_fees(), U128(1_000));
+
+    // Registered accounts don't pay the fee.
+    contract.storage_deposit(None, None);
+    assert_eq!(
+        contract.charge_storage_fee(&IntentsAccount::from(bob()), 200_000),
+        0
+    );
+
+    contract.refund_storage_fee(1_000);
+    assert_eq!(contract.get_collected_storage_fees(), U128(0));
+}
+
+#[test]
+fn test_take_storage_fees() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+
+    contract.set_storage_mode(StorageMode::DepositTokenFee(U128(1_000)));
+    contract.charge_storage_fee(&alice, 200_000);
+
+    assert_eq!(contract.take_storage_fees(Some(U128(400))), U128(400));
+    assert_eq!(contract.get_collected_storage_fees(), U128(600));
+
+    // The fees which weren't withdrawn are returned.
+    contract.return_storage_fees(400);
+    assert_eq!(contract.take_storage_fees(None), U128(1_000));
+    assert_eq!(contract.get_collected_storage_fees(), U128(0));
+}
+
+#[test]
+#[should_panic(expected = "The amount is greater than the collected storage fees")]
+fn test_take_storage_fees_above_collected() {
+    let mut contract = prepare_contract(NearToken::from_near(1));
+    let alice = IntentsAccount::try_from("alice.near").unwrap();
+
+    contract.set_storage_mode(StorageMode::DepositTokenFee(U128(1_000)));
+    contract.charge_storage_fee(&alice, 200_000);
+    contract.take_storage_fees(Some(U128(1_001)));
+}
+
+#[test]
+#[should_panic(expected = "Deposit amount is too low to pay the storage fee")]
+fn test_deposit_token_storage_fee_with_low_deposit() {
+    let mut contract = prepare_contract(NearToken::from_yoctonear(0));
+
+    contract.set_storage_mode(StorageMode::DepositTokenFee(U128(1_000)));
+    contract.charge_storage_fee(&IntentsAccount::from(bob()), 100_500);
+}
+
+fn prepare_contract(attached_deposit: NearToken) -> AuroraLaunchpadContract {
+    testing_env!(VMContextBuilder::new().block_timestamp(NOW + 10).build());
+    let contract = AuroraLaunchpadContract::new(price_discovery_config(), Some(bob()));
+
+    set_attached_deposit(attached_deposit);
+
+    contract
+}
+
+fn set_attached_deposit(attached_deposit: NearToken) {
+    testing_env!(
+        VMContextBuilder::new()
+            .block_timestamp(NOW + 10)
+            .predecessor_account_id(bob())
+            .attached_deposit(attached_deposit)
+            .build()
+    );
+}
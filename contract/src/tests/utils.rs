@@ -1,6 +1,6 @@
 use aurora_launchpad_types::config::{
     DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig, Mechanics,
-    StakeholderProportion,
+    SaleToken, StakeholderProportion,
 };
 use near_sdk::json_types::U128;
 
@@ -18,7 +18,7 @@ pub fn base_config(mechanics: Mechanics) -> LaunchpadConfig {
     LaunchpadConfig {
         deposit_token: DepositToken::Nep141(DEPOSIT_TOKEN_ID.parse().unwrap()),
         min_deposit: 100_000.into(),
-        sale_token_account_id: SALE_TOKEN_ID.parse().unwrap(),
+        sale_token: SaleToken::Nep141(SALE_TOKEN_ID.parse().unwrap()),
         intents_account_id: INTENTS_ACCOUNT_ID.parse().unwrap(),
         start_date: NOW,
         end_date: NOW + TEN_DAYS,
@@ -38,8 +38,11 @@ pub fn base_config(mechanics: Mechanics) -> LaunchpadConfig {
                 vesting: None,
             }],
             deposits: None,
+            liquidity: None,
         },
         discounts: None,
+        bundled_sale_tokens: vec![],
+        allow_unreachable_soft_cap: false,
     }
 }
 
@@ -48,10 +51,13 @@ pub fn price_discovery_config() -> LaunchpadConfig {
 }
 
 pub fn fixed_price_config() -> LaunchpadConfig {
-    base_config(Mechanics::FixedPrice {
+    let mut config = base_config(Mechanics::FixedPrice {
         // Deposit - 24 decimals
         deposit_token: U128(50_000),
         // Deposit - 18 decimals
         sale_token: U128(1),
-    })
+    });
+    // The soft cap must be reachable by selling the sale amount at the fixed price.
+    config.soft_cap = U128(100_000 * MULTIPLIER_24);
+    config
 }
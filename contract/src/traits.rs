@@ -2,7 +2,7 @@
 use aurora_launchpad_types::config::TokenId;
 use defuse::core::crypto::PublicKey;
 use near_sdk::json_types::U128;
-use near_sdk::{AccountId, PromiseOrValue, env, ext_contract};
+use near_sdk::{AccountId, PromiseOrValue, env, ext_contract, near};
 
 /// Maximum byte length of a NEP-141 promise result accepted by [`read_ft_result`].
 ///
@@ -19,6 +19,27 @@ pub const MAX_FT_RESULT_LENGTH: usize = 41;
 /// `[` + `"` + 39 digits + `"` + `]` = 43 bytes. Longer payloads are rejected by the bounded read.
 pub const MAX_MT_RESULT_LENGTH: usize = 43;
 
+/// Maximum byte length of a NEP-145 `storage_balance_of` result accepted by
+/// [`read_storage_balance_result`].
+///
+/// The result is either `null` or a `StorageBalance` object, e.g. `{"total":"1","available":"0"}`.
+/// With two `u128::MAX` values (39 digits each) the longest canonical encoding is
+/// `{"total":"` + 39 digits + `","available":"` + 39 digits + `"}` = 105 bytes.
+pub const MAX_STORAGE_BALANCE_LENGTH: usize = 105;
+
+/// Maximum byte length of a pool id returned by the DEX and accepted by [`read_pool_id_result`].
+///
+/// The result is a JSON `u64`, e.g. `123`. The longest possible value is `u64::MAX` =
+/// `18446744073709551615` (20 digits), so the longest canonical encoding is 20 bytes.
+pub const MAX_POOL_ID_LENGTH: usize = 20;
+
+/// NEP-145 storage balance of an account.
+#[near(serializers = [json])]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
+}
+
 #[ext_contract(ext_ft)]
 trait FungibleToken {
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
@@ -31,6 +52,8 @@ trait FungibleToken {
     ) -> PromiseOrValue<U128>;
     /// Returns the balance of a specific account.
     fn ft_balance_of(&self, account_id: AccountId) -> U128;
+    /// Returns the NEP-145 storage balance of the account or `None` if it isn't registered.
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
 }
 
 #[ext_contract(ext_mt)]
@@ -56,6 +79,29 @@ trait MultiToken {
     fn mt_balance_of(&self, account_id: AccountId, token_id: TokenId) -> U128;
 }
 
+/// A Ref-Finance-style DEX.
+#[ext_contract(ext_dex)]
+trait Dex {
+    fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>);
+    fn register_tokens(&mut self, token_ids: Vec<AccountId>);
+    /// Creates a new pool of the tokens and returns its id.
+    fn add_simple_pool(&mut self, tokens: Vec<AccountId>, fee: u32) -> u64;
+    /// Adds the deposited tokens to the pool and returns the number of minted LP shares.
+    fn add_liquidity(
+        &mut self,
+        pool_id: u64,
+        amounts: Vec<U128>,
+        min_amounts: Option<Vec<U128>>,
+    ) -> U128;
+    fn mft_transfer(
+        &mut self,
+        token_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    );
+}
+
 #[ext_contract(ext_defuse)]
 trait Defuse {
     fn has_public_key(&mut self, account_id: AccountId, public_key: &PublicKey) -> bool;
@@ -84,3 +130,24 @@ pub fn read_mt_result(index: u64) -> Option<u128> {
         .and_then(|amounts| amounts.first().copied())
         .map(|amount| amount.0)
 }
+
+/// Reads promise result `index` of `storage_balance_of` and returns whether the account is
+/// registered in the token. Returns `None` when the promise failed, its result exceeded the bound,
+/// or the payload did not parse as an optional `StorageBalance`.
+#[must_use]
+pub fn read_storage_balance_result(index: u64) -> Option<bool> {
+    env::promise_result_checked(index, MAX_STORAGE_BALANCE_LENGTH)
+        .ok()
+        .and_then(|bytes| near_sdk::serde_json::from_slice::<Option<StorageBalance>>(&bytes).ok())
+        .map(|balance| balance.is_some())
+}
+
+/// Reads promise result `index` of `add_simple_pool` and returns the id of the created pool.
+/// Returns `None` when the promise failed, its result exceeded the bound, or the payload did not
+/// parse as a `u64`.
+#[must_use]
+pub fn read_pool_id_result(index: u64) -> Option<u64> {
+    env::promise_result_checked(index, MAX_POOL_ID_LENGTH)
+        .ok()
+        .and_then(|bytes| near_sdk::serde_json::from_slice::<u64>(&bytes).ok())
+}
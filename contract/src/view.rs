@@ -1,11 +1,14 @@
 use aurora_launchpad_types::IntentsAccount;
 use aurora_launchpad_types::config::{
-    DepositToken, DistributionProportions, LaunchpadConfig, LaunchpadStatus, Mechanics,
+    DepositToken, DistributionProportions, LaunchpadConfig, LaunchpadStatus, Mechanics, SaleToken,
     VestingSchedule,
 };
+use aurora_launchpad_types::error::LaunchpadError;
 use near_sdk::json_types::U128;
 use near_sdk::{AccountId, env, near};
 
+use crate::error::PanicWithCode;
+use crate::mechanics::status::sale_status;
 use crate::{AuroraLaunchpadContract, AuroraLaunchpadContractExt, VERSION};
 
 #[near]
@@ -45,49 +48,40 @@ impl AuroraLaunchpadContract {
         matches!(self.get_status(), LaunchpadStatus::Locked)
     }
 
+    /// Return `true` if the contract is archived.
+    pub fn is_archived(&self) -> bool {
+        matches!(self.get_status(), LaunchpadStatus::Archived)
+    }
+
     /// Return the current status of the launchpad.
     pub fn get_status(&self) -> LaunchpadStatus {
+        self.get_status_at(env::block_timestamp())
+    }
+
+    /// Return the status of the launchpad at the given time projected from the current totals
+    /// of deposited and sold tokens. The `Locked` and `Archived` statuses are returned for
+    /// the current block time only.
+    pub fn get_status_at(&self, timestamp: u64) -> LaunchpadStatus {
         if !self.is_sale_token_set {
             return LaunchpadStatus::NotInitialized;
         }
 
-        if self.is_locked {
+        let is_current = timestamp == env::block_timestamp();
+
+        if is_current && self.is_locked {
             return LaunchpadStatus::Locked;
         }
 
-        let current_timestamp = env::block_timestamp();
-
-        if current_timestamp < self.config.start_date {
-            LaunchpadStatus::NotStarted
-        } else if current_timestamp >= self.config.start_date
-            && current_timestamp < self.config.end_date
-        {
-            if self.total_sold_tokens >= self.config.sale_amount.0
-                && matches!(self.config.mechanics, Mechanics::FixedPrice { .. })
-            {
-                // If TGE is present, transition to PreTGE even when all tokens are sold early,
-                // since TGE is always > end_date (validated at initialization and
-                // in the `update_tge` transaction). Therefore, checking for the presence of TGE
-                // is enough here.
-                if self.config.tge.is_some() {
-                    LaunchpadStatus::PreTGE
-                } else {
-                    LaunchpadStatus::Success
-                }
-            } else {
-                LaunchpadStatus::Ongoing
-            }
-        } else if current_timestamp >= self.config.end_date
-            && self.total_deposited >= self.config.soft_cap.0
-        {
-            if self.config.tge.is_some_and(|tge| current_timestamp < tge) {
-                LaunchpadStatus::PreTGE
-            } else {
-                LaunchpadStatus::Success
-            }
-        } else {
-            LaunchpadStatus::Failed
+        if is_current && self.cleanup.is_archived() {
+            return LaunchpadStatus::Archived;
         }
+
+        sale_status(
+            &self.config,
+            self.total_deposited,
+            self.total_sold_tokens,
+            timestamp,
+        )
     }
 
     /// Return the launchpad configuration.
@@ -143,7 +137,12 @@ impl AuroraLaunchpadContract {
 
     /// Return the sale token account ID.
     pub fn get_sale_token_account_id(&self) -> AccountId {
-        self.config.sale_token_account_id.clone()
+        self.config.sale_token.account_id().clone()
+    }
+
+    /// Return the sale token.
+    pub fn get_sale_token(&self) -> SaleToken {
+        self.config.sale_token.clone()
     }
 
     /// Return the total number of tokens that should be sold during the launchpad.
@@ -175,8 +174,9 @@ impl AuroraLaunchpadContract {
     /// `i64::MAX` nanoseconds value, which is the maximum value that can be represented by
     /// `chrono::DateTime<chrono::Utc>`.
     pub fn get_tge(&self) -> Option<chrono::DateTime<chrono::Utc>> {
-        let tge_nanoseconds = i64::try_from(self.config.tge?)
-            .unwrap_or_else(|_| env::panic_str("TGE nanoseconds value exceeds i64::MAX"));
+        let tge_nanoseconds = i64::try_from(self.config.tge?).unwrap_or_else(|_| {
+            LaunchpadError::InvalidTge.panic_with("TGE nanoseconds value exceeds i64::MAX")
+        });
 
         Some(chrono::DateTime::from_timestamp_nanos(tge_nanoseconds))
     }
@@ -1,12 +1,14 @@
 use aurora_launchpad_types::config::{DepositToken, LaunchpadStatus, Mechanics};
+use aurora_launchpad_types::error::LaunchpadError;
 use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
 use defuse::core::crypto::SignedPayload;
 use defuse::core::payload::multi::MultiPayload;
 use defuse::tokens::{DepositAction, DepositMessage, ExecuteIntents};
 use near_plugins::{Pausable, pause};
 use near_sdk::json_types::U128;
-use near_sdk::{Gas, Promise, PromiseError, assert_one_yocto, env, near, require};
+use near_sdk::{Gas, Promise, PromiseError, assert_one_yocto, env, near};
 
+use crate::error::{PanicWithCode, require, require_with};
 use crate::traits::{MAX_FT_RESULT_LENGTH, ext_defuse, ext_ft, ext_mt};
 use crate::{
     AuroraLaunchpadContract, AuroraLaunchpadContractExt, GAS_FOR_FT_TRANSFER_CALL,
@@ -41,9 +43,9 @@ impl AuroraLaunchpadContract {
     ) -> Promise {
         assert_one_yocto();
 
-        require!(
+        require(
             !self.locked_withdraw.contains(&account),
-            "Withdraw is still in progress"
+            LaunchpadError::WithdrawInProgress,
         );
 
         if let Some(intents) = intents {
@@ -54,9 +56,9 @@ impl AuroraLaunchpadContract {
                     .do_withdraw_with_intents(amount, &account, intents, refund_if_fails),
             )
         } else {
-            require!(
+            require(
                 self.is_withdrawal_allowed(WithdrawIntents::NotPresent),
-                "Withdraw is not allowed"
+                LaunchpadError::WithdrawNotAllowed,
             );
             let msg = DepositMessage::new(account.clone().into()).to_string();
 
@@ -65,14 +67,14 @@ impl AuroraLaunchpadContract {
     }
 
     fn validate_intents(&self, intents: &[MultiPayload], account: &IntentsAccount) -> Promise {
-        require!(!intents.is_empty(), "No intent provided");
-        require!(intents.len() <= MAX_INTENTS, "Too much intent provided");
+        require(!intents.is_empty(), LaunchpadError::NoIntents);
+        require(intents.len() <= MAX_INTENTS, LaunchpadError::TooManyIntents);
 
         let mut promises = intents.iter().map(|intent| {
             let public_key = intent.verify();
 
             public_key.map_or_else(
-                || env::panic_str("Intent verification failed"),
+                || LaunchpadError::IntentVerificationFailed.panic(),
                 |public_key| {
                     ext_defuse::ext(self.config.intents_account_id.clone())
                         .with_static_gas(GAS_FOR_CHECK_PUBLIC_KEY)
@@ -83,7 +85,7 @@ impl AuroraLaunchpadContract {
 
         let first = promises
             .next()
-            .unwrap_or_else(|| env::panic_str("No promises"));
+            .unwrap_or_else(|| LaunchpadError::Internal.panic_with("No promises"));
 
         promises.fold(first, Promise::and)
     }
@@ -97,15 +99,15 @@ impl AuroraLaunchpadContract {
         execute_intents: Vec<MultiPayload>,
         refund_if_fails: Option<bool>,
     ) -> Promise {
-        require!(
+        require(
             !self.locked_withdraw.contains(account),
-            "Withdraw is still in progress"
+            LaunchpadError::WithdrawInProgress,
         );
 
         let withdraw_intents = validate_intents_results(execute_intents.len());
-        require!(
+        require(
             self.is_withdrawal_allowed(withdraw_intents),
-            "Withdraw is not allowed"
+            LaunchpadError::WithdrawNotAllowed,
         );
         let refund_if_fails = if self.is_ongoing() {
             // We always want to get a refund in case of ongoing status.
@@ -127,19 +129,20 @@ impl AuroraLaunchpadContract {
     }
 
     fn do_withdraw(&mut self, amount: U128, account: &IntentsAccount, msg: String) -> Promise {
-        let deposited = self
-            .get_investments(account)
-            .unwrap_or_else(|| env::panic_str("No deposit for the intents account"));
-        let remain_deposit = deposited.0.checked_sub(amount.0).unwrap_or_else(|| {
-            env::panic_str("Withdraw amount is greater than the deposit amount")
+        let deposited = self.get_investments(account).unwrap_or_else(|| {
+            LaunchpadError::NoDeposit.panic_with("No deposit for the intents account")
         });
+        let remain_deposit = deposited
+            .0
+            .checked_sub(amount.0)
+            .unwrap_or_else(|| LaunchpadError::WithdrawAmountTooHigh.panic());
         let timestamp = env::block_timestamp();
         // Recalculating the remaining deposit based on the actual discount phases.
         let deposit_distribution =
             self.get_deposit_distribution(account, remain_deposit, timestamp);
 
         let Some(investment) = self.investments.get_mut(account) else {
-            env::panic_str("No deposits were found for the intents account");
+            LaunchpadError::NoDeposit.panic_with("No deposits were found for the intents account");
         };
 
         // Store the state before the withdrawal to allow rollback in case of failure.
@@ -155,15 +158,21 @@ impl AuroraLaunchpadContract {
             &self.config,
             &deposit_distribution,
         )
-        .unwrap_or_else(|err| env::panic_str(&format!("Withdraw failed: {err}")));
+        .unwrap_or_else(|err| {
+            LaunchpadError::WithdrawFailed.panic_with(&format!("Withdraw failed: {err}"))
+        });
 
         before_withdraw.update_deltas(
             total_deposited_before
                 .checked_sub(self.total_deposited)
-                .unwrap_or_else(|| env::panic_str("Total deposited underflow")),
+                .unwrap_or_else(|| {
+                    LaunchpadError::Overflow.panic_with("Total deposited underflow")
+                }),
             total_sold_tokens_before
                 .checked_sub(self.total_sold_tokens)
-                .unwrap_or_else(|| env::panic_str("Total sold token underflow")),
+                .unwrap_or_else(|| {
+                    LaunchpadError::Overflow.panic_with("Total sold token underflow")
+                }),
         );
 
         // Set a lock on the withdrawal to prevent reentrancy.
@@ -207,9 +216,9 @@ impl AuroraLaunchpadContract {
         timestamp: u64,
         #[callback_result] result: &Result<U128, PromiseError>,
     ) {
-        require!(
+        require(
             env::promise_results_count() == 1,
-            "Expected one promise result"
+            LaunchpadError::PromiseResultsCount,
         );
 
         // Remove the lock on the withdrawal.
@@ -233,9 +242,9 @@ impl AuroraLaunchpadContract {
         timestamp: u64,
         #[callback_result] result: &Result<Vec<U128>, PromiseError>,
     ) {
-        require!(
+        require(
             env::promise_results_count() == 1,
-            "Expected one promise result"
+            LaunchpadError::PromiseResultsCount,
         );
 
         // Remove the lock on the withdrawal.
@@ -247,7 +256,7 @@ impl AuroraLaunchpadContract {
             Ok(&[value]) => {
                 self.return_part_of_deposit(account, amount.0.checked_sub(value.0), timestamp);
             }
-            Ok(_) => env::panic_str("Unexpected amount of tokens withdrawn"),
+            Ok(_) => LaunchpadError::Internal.panic_with("Unexpected amount of tokens withdrawn"),
         }
     }
 
@@ -276,7 +285,7 @@ impl AuroraLaunchpadContract {
         } = before_withdraw;
 
         let Some(state_investment) = self.investments.get_mut(account) else {
-            env::panic_str("Missing investment during rollback");
+            LaunchpadError::Internal.panic_with("Missing investment during rollback");
         };
 
         state_investment.amount = investment.amount;
@@ -285,11 +294,11 @@ impl AuroraLaunchpadContract {
         self.total_deposited = self
             .total_deposited
             .checked_add(total_deposited_delta)
-            .unwrap_or_else(|| env::panic_str("Total deposited overflow"));
+            .unwrap_or_else(|| LaunchpadError::Overflow.panic_with("Total deposited overflow"));
         self.total_sold_tokens = self
             .total_sold_tokens
             .checked_add(total_sold_tokens_delta)
-            .unwrap_or_else(|| env::panic_str("Total sold token overflow"));
+            .unwrap_or_else(|| LaunchpadError::Overflow.panic_with("Total sold token overflow"));
     }
 
     fn return_part_of_deposit(
@@ -298,10 +307,10 @@ impl AuroraLaunchpadContract {
         amount: Option<u128>,
         timestamp: u64,
     ) {
-        let amount = amount.unwrap_or_else(|| env::panic_str("Wrong refund amount"));
+        let amount = amount.unwrap_or_else(|| LaunchpadError::InvalidRefund.panic());
         let deposit_distribution = self.get_deposit_distribution(account, amount, timestamp);
         let Some(investment) = self.investments.get_mut(account) else {
-            env::panic_str("No deposits were found for the intents account");
+            LaunchpadError::NoDeposit.panic_with("No deposits were found for the intents account");
         };
 
         let refund = mechanics::deposit::deposit(
@@ -312,23 +321,28 @@ impl AuroraLaunchpadContract {
             &self.config,
             &deposit_distribution,
         )
-        .unwrap_or_else(|e| env::panic_str(&format!("Failed to return part of the deposit: {e}")));
+        .unwrap_or_else(|e| {
+            LaunchpadError::WithdrawFailed
+                .panic_with(&format!("Failed to return part of the deposit: {e}"))
+        });
 
         // It should never happen because withdrawals are only allowed when the status is `Ongoing`
         // for `PriceDiscovery`. The `PriceDiscovery` mechanic does not assume any refunds.
         // For the `FixedPrice` mechanic, withdrawals are permitted once the sale has finished.
         // This means that nobody else will be able to make a deposit and reach the sale limit,
         // which could otherwise trigger a refund.
-        require!(refund == 0, "Unexpected refund");
+        require_with(refund == 0, LaunchpadError::Internal, "Unexpected refund");
     }
 }
 
 fn validate_intents_results(intents_count: usize) -> WithdrawIntents {
-    let count_u64 = u64::try_from(intents_count)
-        .unwrap_or_else(|_| env::panic_str("Error while converting usize to u64"));
+    let count_u64 = u64::try_from(intents_count).unwrap_or_else(|_| {
+        LaunchpadError::Overflow.panic_with("Error while converting usize to u64")
+    });
 
-    require!(
+    require_with(
         count_u64 == env::promise_results_count(),
+        LaunchpadError::PromiseResultsCount,
         "Wrong number of promise results",
     );
 
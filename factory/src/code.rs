@@ -0,0 +1,188 @@
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::{CryptoHash, env, near, require};
+
+use crate::{AuroraLaunchpadFactory, AuroraLaunchpadFactoryExt, LAUNCHPAD_CODE, Role, VERSION};
+
+/// The launchpad code registered in the factory.
+#[derive(Debug, Clone, Copy)]
+#[near(serializers = [borsh])]
+pub struct LaunchpadCode {
+    /// SHA-256 hash of the code, which is also a key of the code in the storage.
+    pub hash: CryptoHash,
+    /// Deprecated code can't be used for creating new launchpads.
+    pub is_deprecated: bool,
+}
+
+/// Information about a version of the launchpad code.
+#[derive(Debug, Clone)]
+#[near(serializers = [json])]
+pub struct LaunchpadCodeInfo {
+    /// Version of the launchpad code.
+    pub version: String,
+    /// SHA-256 hash of the launchpad code.
+    pub hash: Base58CryptoHash,
+    /// Whether the version is used when no version is provided to `create_launchpad`.
+    pub is_default: bool,
+    /// Whether the version is deprecated and can't be used for new launchpads.
+    pub is_deprecated: bool,
+    /// Whether the code is embedded in the factory.
+    pub is_builtin: bool,
+}
+
+#[near]
+impl AuroraLaunchpadFactory {
+    /// Stages the launchpad code for the provided version. The staged code becomes available for
+    /// creating launchpads after it is registered with `register_launchpad_code`.
+    #[access_control_any(roles(Role::Dao, Role::Deployer))]
+    pub fn stage_launchpad_code(
+        &mut self,
+        #[serializer(borsh)] version: String,
+        #[serializer(borsh)] code: Vec<u8>,
+    ) {
+        require!(!code.is_empty(), "The launchpad code is empty");
+        require!(
+            !self.launchpad_codes.contains_key(&version),
+            "The launchpad code version is already registered"
+        );
+
+        self.staged_launchpad_codes.insert(version, code);
+    }
+
+    /// Registers the staged launchpad code for the provided version. The SHA-256 hash of the staged
+    /// code must be equal to the provided hash.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn register_launchpad_code(&mut self, version: String, hash: Base58CryptoHash) {
+        require!(
+            !self.launchpad_codes.contains_key(&version),
+            "The launchpad code version is already registered"
+        );
+
+        let code = self
+            .staged_launchpad_codes
+            .remove(&version)
+            .unwrap_or_else(|| env::panic_str("No staged launchpad code for the version"));
+        let hash = CryptoHash::from(hash);
+
+        require!(
+            env::sha256_array(&code) == hash,
+            "The hash of the staged launchpad code doesn't match the provided one"
+        );
+
+        near_sdk::log!("Launchpad code version {version} is registered");
+
+        self.launchpad_code_blobs.insert(hash, code);
+        self.launchpad_codes.insert(
+            version,
+            LaunchpadCode {
+                hash,
+                is_deprecated: false,
+            },
+        );
+    }
+
+    /// Sets the version of the launchpad code used by default. If the version is `None`,
+    /// the code embedded in the factory is used by default.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn set_default_launchpad_version(&mut self, version: Option<String>) {
+        if let Some(version) = &version {
+            let code = self
+                .launchpad_codes
+                .get(version)
+                .unwrap_or_else(|| env::panic_str("Unknown launchpad code version"));
+            require!(
+                !code.is_deprecated,
+                "Deprecated version can't be used by default"
+            );
+        }
+
+        self.default_launchpad_version = version;
+    }
+
+    /// Deprecates the version of the launchpad code, so it can't be used for new launchpads.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn deprecate_launchpad_version(&mut self, version: String) {
+        require!(
+            self.default_launchpad_version.as_ref() != Some(&version),
+            "The default version can't be deprecated"
+        );
+
+        let code = self
+            .launchpad_codes
+            .get_mut(&version)
+            .unwrap_or_else(|| env::panic_str("Unknown launchpad code version"));
+
+        code.is_deprecated = true;
+    }
+
+    /// Returns the version of the launchpad code used by default.
+    pub fn get_default_launchpad_version(&self) -> String {
+        self.default_launchpad_version
+            .clone()
+            .unwrap_or_else(|| VERSION.to_string())
+    }
+
+    /// Returns all available versions of the launchpad code with their hashes.
+    pub fn get_launchpad_code_versions(&self) -> Vec<LaunchpadCodeInfo> {
+        let mut versions = self
+            .launchpad_codes
+            .iter()
+            .map(|(version, code)| LaunchpadCodeInfo {
+                version: version.clone(),
+                hash: code.hash.into(),
+                is_default: self.default_launchpad_version.as_ref() == Some(version),
+                is_deprecated: code.is_deprecated,
+                is_builtin: false,
+            })
+            .collect::<Vec<_>>();
+
+        if !self.launchpad_codes.contains_key(VERSION) {
+            versions.push(LaunchpadCodeInfo {
+                version: VERSION.to_string(),
+                hash: env::sha256_array(LAUNCHPAD_CODE).into(),
+                is_default: self.default_launchpad_version.is_none(),
+                is_deprecated: false,
+                is_builtin: true,
+            });
+        }
+
+        versions
+    }
+
+    /// Returns the SHA-256 hash of the launchpad code of the provided version.
+    pub(crate) fn launchpad_code_hash(&self, version: &str) -> CryptoHash {
+        match self.launchpad_codes.get(version) {
+            Some(code) => {
+                require!(
+                    !code.is_deprecated,
+                    "The launchpad code version is deprecated"
+                );
+                code.hash
+            }
+            None if version == VERSION => env::sha256_array(LAUNCHPAD_CODE),
+            None => env::panic_str("Unknown launchpad code version"),
+        }
+    }
+
+    /// Returns the launchpad code of the provided version or of the default version if `None`.
+    pub(crate) fn launchpad_code(&self, version: Option<&str>) -> Vec<u8> {
+        let Some(version) = version.or(self.default_launchpad_version.as_deref()) else {
+            return LAUNCHPAD_CODE.to_vec();
+        };
+
+        match self.launchpad_codes.get(version) {
+            Some(code) => {
+                require!(
+                    !code.is_deprecated,
+                    "The launchpad code version is deprecated"
+                );
+                self.launchpad_code_blobs
+                    .get(&code.hash)
+                    .unwrap_or_else(|| env::panic_str("Missing launchpad code"))
+                    .clone()
+            }
+            None if version == VERSION => LAUNCHPAD_CODE.to_vec(),
+            None => env::panic_str("Unknown launchpad code version"),
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use aurora_launchpad_types::config::{LaunchpadConfig, SaleToken};
+use near_plugins::{Pausable, pause};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json;
+use near_sdk::{
+    AccountId, Gas, NearToken, Promise, PromiseOrValue, env, ext_contract, log, near, require,
+};
+
+use crate::partner::{CreationFee, FeeToken};
+use crate::{
+    AuroraLaunchpadFactory, AuroraLaunchpadFactoryExt, LAUNCHPAD_MIN_DEPOSIT, PendingLaunchpad,
+};
+
+const GAS_FOR_STORAGE_BALANCE_BOUNDS: Gas = Gas::from_tgas(3);
+const GAS_FOR_STORAGE_DEPOSIT: Gas = Gas::from_tgas(5);
+const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas::from_tgas(50);
+const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+/// The deposit charged from the creation deposit for registering the launchpad in the sale
+/// token. The minimal storage balance of the token is attached to `storage_deposit`, and
+/// the rest is credited back to the creator.
+const SALE_TOKEN_STORAGE_DEPOSIT: NearToken = NearToken::from_millinear(10);
+/// The maximum length of a single NEP-141 `U128` amount.
+const MAX_FT_RESULT_LENGTH: u64 = 64;
+/// The maximum length of the `StorageBalanceBounds` object with two `U128` amounts.
+const MAX_STORAGE_BALANCE_BOUNDS_LENGTH: u64 = 97;
+/// The maximum length of the `StorageBalance` object with two `U128` amounts.
+const MAX_STORAGE_BALANCE_LENGTH: u64 = 105;
+
+#[ext_contract(ext_sale_token)]
+trait FungibleToken {
+    fn storage_balance_bounds(&self) -> StorageBalanceBounds;
+    fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>);
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> U128;
+}
+
+/// The storage balance bounds of the token. Only the minimal balance is used.
+#[near(serializers = [json])]
+pub struct StorageBalanceBounds {
+    pub min: U128,
+}
+
+/// The message of `ft_transfer_call` for creating the launchpad funded with the sale tokens.
+#[derive(Debug, Clone)]
+#[near(serializers = [json])]
+pub struct CreateLaunchpadMsg {
+    /// Configuration of the launchpad.
+    pub config: LaunchpadConfig,
+    /// The admin of the launchpad. The sender of the tokens is used if missing.
+    pub admin: Option<AccountId>,
+    /// The name of the launchpad account.
+    pub name: Option<String>,
+    /// The version of the launchpad code.
+    pub version: Option<String>,
+}
+
+#[near]
+impl AuroraLaunchpadFactory {
+    /// Deposits the attached NEAR for creating launchpads with `ft_transfer_call` of the sale
+    /// tokens. The deposit of the launchpad account and its registration in the sale token are
+    /// paid from it.
+    #[payable]
+    pub fn deposit_for_creation(&mut self) {
+        let account_id = env::predecessor_account_id();
+        let amount = env::attached_deposit();
+
+        require!(
+            !amount.is_zero(),
+            "Attached deposit must be greater than zero"
+        );
+
+        self.credit_creation_deposit(account_id, amount);
+    }
+
+    /// Withdraws the NEAR deposited by the caller for creating launchpads.
+    pub fn withdraw_creation_deposit(&mut self) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .creation_deposits
+            .remove(&account_id)
+            .filter(|amount| !amount.is_zero())
+            .unwrap_or_else(|| env::panic_str("No creation deposit to withdraw"));
+
+        Promise::new(account_id).transfer(amount)
+    }
+
+    /// Returns the amount of NEAR deposited by the account for creating launchpads.
+    pub fn get_creation_deposit(&self, account_id: &AccountId) -> NearToken {
+        self.creation_deposits
+            .get(account_id)
+            .copied()
+            .unwrap_or(NearToken::from_yoctonear(0))
+    }
+
+    /// Accepts NEP-141 tokens. If `msg` is empty, the tokens are deposited for paying creation
+    /// fees. Otherwise, `msg` must contain `CreateLaunchpadMsg`, and the tokens must be the sale
+    /// tokens in the amount of `total_sale_amount`. In this case, the factory creates
+    /// the launchpad, registers it in the sale token and forwards the tokens to it. The deposit
+    /// of the launchpad account, the registration and the creation fee in NEAR are paid from
+    /// the creation deposit of the sender. The tokens are refunded if any step fails.
+    #[pause]
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let token_id = env::predecessor_account_id();
+
+        if msg.is_empty() {
+            return self.deposit_creation_fee(sender_id, token_id, amount);
+        }
+
+        let CreateLaunchpadMsg {
+            config,
+            admin,
+            name,
+            version,
+        } = serde_json::from_str(&msg)
+            .unwrap_or_else(|err| env::panic_str(&format!("Invalid message: {err}")));
+
+        require!(
+            matches!(&config.sale_token, SaleToken::Nep141(account_id) if account_id == &token_id),
+            "Only the sale token is accepted for creating the launchpad"
+        );
+        require!(
+            amount == config.total_sale_amount,
+            "The amount must be equal to the total sale amount"
+        );
+
+        let deposit = match &self.creation_fee {
+            Some(CreationFee {
+                token: FeeToken::Near,
+                amount,
+                ..
+            }) if !self.is_controller(&sender_id) => {
+                LAUNCHPAD_MIN_DEPOSIT.saturating_add(NearToken::from_yoctonear(amount.0))
+            }
+            _ => LAUNCHPAD_MIN_DEPOSIT,
+        };
+        self.charge_creation_deposit(
+            &sender_id,
+            deposit.saturating_add(SALE_TOKEN_STORAGE_DEPOSIT),
+        );
+
+        let admin = admin.unwrap_or_else(|| sender_id.clone());
+        let (promise, launchpad) = self.start_creation(
+            config,
+            Some(admin),
+            name,
+            version.as_deref(),
+            sender_id,
+            deposit,
+        );
+
+        promise
+            .then(
+                Self::ext(env::current_account_id())
+                    .finish_create_funded_launchpad(launchpad, token_id, amount),
+            )
+            .into()
+    }
+
+    /// Pays the creation fee and requests the storage balance bounds of the sale token for
+    /// registering the created launchpad in it. Returns the whole amount as unused and credits
+    /// the creation deposit back to the creator if the creation failed.
+    #[private]
+    pub fn finish_create_funded_launchpad(
+        &mut self,
+        launchpad: PendingLaunchpad,
+        token_id: AccountId,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        if env::promise_result_checked(0, 0).is_err() {
+            log!(
+                "Error while creating launchpad contract: {}. Refunding {} sale tokens to {}",
+                &launchpad.account_id,
+                amount.0,
+                &launchpad.creator
+            );
+
+            self.abort_creation(&launchpad);
+            self.credit_creation_deposit(
+                launchpad.creator,
+                launchpad.deposit.saturating_add(SALE_TOKEN_STORAGE_DEPOSIT),
+            );
+
+            return PromiseOrValue::Value(amount);
+        }
+
+        log!(
+            "Launchpad with the account id: {} created successfully",
+            &launchpad.account_id
+        );
+
+        if let Some(fee) = launchpad.fee {
+            Self::pay_creation_fee(launchpad.creator.clone(), fee);
+        }
+
+        ext_sale_token::ext(token_id.clone())
+            .with_static_gas(GAS_FOR_STORAGE_BALANCE_BOUNDS)
+            .storage_balance_bounds()
+            .then(
+                Self::ext(env::current_account_id()).register_funded_launchpad(
+                    launchpad.account_id,
+                    launchpad.creator,
+                    token_id,
+                    amount,
+                ),
+            )
+            .into()
+    }
+
+    /// Registers the launchpad in the sale token with the minimal storage balance and credits
+    /// the rest of the charged deposit back to the creator. Returns the whole amount as unused
+    /// and credits the whole charged deposit back if the bounds couldn't be read or exceed it.
+    #[private]
+    pub fn register_funded_launchpad(
+        &mut self,
+        launchpad_account_id: AccountId,
+        creator: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        let storage_deposit = env::promise_result_checked(0, MAX_STORAGE_BALANCE_BOUNDS_LENGTH)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<StorageBalanceBounds>(&bytes).ok())
+            .map(|bounds| NearToken::from_yoctonear(bounds.min.0))
+            .filter(|min| *min <= SALE_TOKEN_STORAGE_DEPOSIT);
+
+        let Some(storage_deposit) = storage_deposit else {
+            log!(
+                "Error while registering launchpad {launchpad_account_id} in the sale token. Refunding {} sale tokens",
+                amount.0
+            );
+            self.credit_creation_deposit(creator, SALE_TOKEN_STORAGE_DEPOSIT);
+
+            return PromiseOrValue::Value(amount);
+        };
+
+        self.credit_creation_deposit(
+            creator.clone(),
+            SALE_TOKEN_STORAGE_DEPOSIT.saturating_sub(storage_deposit),
+        );
+
+        ext_sale_token::ext(token_id.clone())
+            .with_attached_deposit(storage_deposit)
+            .with_static_gas(GAS_FOR_STORAGE_DEPOSIT)
+            .storage_deposit(Some(launchpad_account_id.clone()), Some(true))
+            .then(Self::ext(env::current_account_id()).fund_launchpad(
+                launchpad_account_id,
+                creator,
+                token_id,
+                amount,
+                storage_deposit,
+            ))
+            .into()
+    }
+
+    /// Forwards the sale tokens to the launchpad registered in the sale token. Returns
+    /// the whole amount as unused and credits the storage deposit back to the creator if
+    /// the registration failed.
+    #[private]
+    pub fn fund_launchpad(
+        &mut self,
+        launchpad_account_id: AccountId,
+        creator: AccountId,
+        token_id: AccountId,
+        amount: U128,
+        storage_deposit: NearToken,
+    ) -> PromiseOrValue<U128> {
+        if env::promise_result_checked(0, MAX_STORAGE_BALANCE_LENGTH).is_err() {
+            log!(
+                "Error while registering launchpad {launchpad_account_id} in the sale token. Refunding {} sale tokens",
+                amount.0
+            );
+            self.credit_creation_deposit(creator, storage_deposit);
+
+            return PromiseOrValue::Value(amount);
+        }
+
+        ext_sale_token::ext(token_id)
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER_CALL)
+            .ft_transfer_call(launchpad_account_id.clone(), amount, None, String::new())
+            .then(
+                Self::ext(env::current_account_id())
+                    .finish_fund_launchpad(launchpad_account_id, amount),
+            )
+            .into()
+    }
+
+    /// Returns the amount of the sale tokens to be refunded if the launchpad isn't funded.
+    /// The launchpad stays created in this case, and its admin could fund it with
+    /// `ft_transfer_call` of the sale tokens as usual.
+    #[private]
+    pub fn finish_fund_launchpad(&mut self, launchpad_account_id: AccountId, amount: U128) -> U128 {
+        let used = env::promise_result_checked(0, MAX_FT_RESULT_LENGTH)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<U128>(&bytes).ok())
+            .map_or(0, |used| used.0);
+
+        if used == amount.0 {
+            log!(
+                "Launchpad {launchpad_account_id} is funded with {} sale tokens",
+                amount.0
+            );
+        } else {
+            log!(
+                "Error while funding launchpad {launchpad_account_id}. Refunding {} sale tokens",
+                amount.0.saturating_sub(used)
+            );
+        }
+
+        U128(amount.0.saturating_sub(used))
+    }
+}
+
+impl AuroraLaunchpadFactory {
+    fn charge_creation_deposit(&mut self, account_id: &AccountId, amount: NearToken) {
+        let balance = self
+            .creation_deposits
+            .get_mut(account_id)
+            .filter(|balance| **balance >= amount)
+            .unwrap_or_else(|| {
+                env::panic_str(&format!(
+                    "Creation deposit must be at least {}",
+                    amount.exact_amount_display()
+                ))
+            });
+
+        *balance = balance.saturating_sub(amount);
+    }
+
+    fn credit_creation_deposit(&mut self, account_id: AccountId, amount: NearToken) {
+        let balance = self
+            .creation_deposits
+            .entry(account_id)
+            .or_insert(NearToken::from_yoctonear(0));
+        *balance = balance.saturating_add(amount);
+    }
+}
@@ -1,17 +1,31 @@
 use aurora_launchpad_types::config::LaunchpadConfig;
 use near_plugins::{
-    AccessControlRole, AccessControllable, Pausable, Upgradable, access_control,
-    access_control_any, pause,
+    AccessControlRole, AccessControllable, Pausable, Upgradable, access_control, pause,
 };
 use near_sdk::borsh::BorshDeserialize;
+use near_sdk::store::{IterableMap, LookupMap, LookupSet};
 use near_sdk::{
-    AccountId, Gas, NearToken, PanicOnDefault, Promise, PromiseOrValue, env, log, near, require,
+    AccountId, CryptoHash, Gas, NearToken, PanicOnDefault, Promise, env, log, near, require,
 };
 
+use crate::code::LaunchpadCode;
+use crate::partner::{CreationFee, Partner};
+use crate::storage_key::StorageKey;
+use crate::upgrade::LaunchpadUpgrade;
+
+mod code;
+mod funding;
+mod partner;
+mod storage_key;
+mod template;
+mod upgrade;
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const LAUNCHPAD_CODE: &[u8] = include_bytes!("../../res/aurora_launchpad_contract.wasm");
 const LAUNCHPAD_DEPLOY_GAS: Gas = Gas::from_tgas(100);
 const LAUNCHPAD_MIN_DEPOSIT: NearToken = NearToken::from_near(9);
+/// Prefix of the automatically generated launchpad names, e.g. `lp-1`.
+const LAUNCHPAD_NAME_PREFIX: &str = "lp-";
 
 #[derive(AccessControlRole, Clone, Copy)]
 #[near(serializers = [json])]
@@ -38,6 +52,57 @@ enum Role {
 )]
 #[near(contract_state)]
 pub struct AuroraLaunchpadFactory {
+    /// The highest numeric id used for an automatically named launchpad.
+    launchpad_count: u64,
+    /// Numeric ids released after failed creations, which will be reused first.
+    released_ids: Vec<u64>,
+    /// Account ids of the launchpads which are created or being created at the moment.
+    launchpads: LookupSet<AccountId>,
+    /// Registered versions of the launchpad code.
+    launchpad_codes: IterableMap<String, LaunchpadCode>,
+    /// The launchpad code blobs by their SHA-256 hashes.
+    launchpad_code_blobs: LookupMap<CryptoHash, Vec<u8>>,
+    /// The launchpad code blobs staged for registration by their versions.
+    staged_launchpad_codes: LookupMap<String, Vec<u8>>,
+    /// The version of the launchpad code used by default. The embedded code is used if `None`.
+    default_launchpad_version: Option<String>,
+    /// Results of the latest upgrades of the launchpads.
+    launchpad_upgrades: LookupMap<AccountId, LaunchpadUpgrade>,
+    /// Hashes of the launchpad code deployed by the factory as global contracts.
+    global_launchpad_codes: LookupSet<CryptoHash>,
+    /// Partner accounts allowed to create launchpads and their quotas.
+    partners: IterableMap<AccountId, Partner>,
+    /// Creators of the live launchpads created by partners.
+    launchpad_creators: LookupMap<AccountId, AccountId>,
+    /// The fee paid by partners for creating a launchpad.
+    creation_fee: Option<CreationFee>,
+    /// NEP-141 tokens deposited by partners for paying creation fees by account and token ids.
+    creation_fee_deposits: LookupMap<(AccountId, AccountId), u128>,
+    /// NEAR deposited for creating launchpads with the sale tokens transfer by account ids.
+    creation_deposits: LookupMap<AccountId, NearToken>,
+    /// Named config templates in JSON format.
+    config_templates: IterableMap<String, String>,
+}
+
+/// The launchpad which is being created.
+#[derive(Debug, Clone)]
+#[near(serializers = [json])]
+pub struct PendingLaunchpad {
+    /// Account id of the launchpad.
+    pub account_id: AccountId,
+    /// Numeric id of the launchpad if its name is generated automatically.
+    pub launchpad_id: Option<u64>,
+    /// The account which creates the launchpad.
+    pub creator: AccountId,
+    /// The deposit attached to the creation.
+    pub deposit: NearToken,
+    /// The creation fee taken from the creator.
+    pub fee: Option<CreationFee>,
+}
+
+/// The state layout of the factory before the launchpad accounts were tracked.
+#[near(serializers = [borsh])]
+struct LegacyFactory {
     launchpad_count: u64,
 }
 
@@ -48,7 +113,7 @@ impl AuroraLaunchpadFactory {
     #[must_use]
     #[allow(clippy::use_self)]
     pub fn new(dao: Option<AccountId>) -> Self {
-        let mut contract = Self { launchpad_count: 0 };
+        let mut contract = Self::with_launchpad_count(0);
         let mut acl = contract.acl_get_or_init();
 
         acl.add_super_admin_unchecked(&env::current_account_id());
@@ -62,35 +127,112 @@ impl AuroraLaunchpadFactory {
         contract
     }
 
+    /// Migrates the state of the factory deployed with the previous layout. The launchpads created
+    /// before the migration are not tracked, but their automatically generated names stay reserved.
+    #[private]
+    #[init(ignore_state)]
+    #[must_use]
+    pub fn migrate() -> Self {
+        let legacy: LegacyFactory =
+            env::state_read().unwrap_or_else(|| env::panic_str("Failed to read the legacy state"));
+
+        Self::with_launchpad_count(legacy.launchpad_count)
+    }
+
     /// Returns the version of the factory.
     #[must_use]
     pub const fn get_version() -> &'static str {
         VERSION
     }
 
-    /// Create a new launchpad contract.
+    /// Create a new launchpad contract. The launchpad is deployed on the subaccount with
+    /// the provided `name` or on the subaccount `lp-<number>` if the name is missing.
+    /// The optional `version` selects the registered launchpad code, the default version is used
+    /// if it is missing. Partners pay the creation fee and are limited by their quotas.
     #[payable]
     #[pause]
-    #[access_control_any(roles(Role::Controller))]
     pub fn create_launchpad(
         &mut self,
         config: LaunchpadConfig,
         admin: Option<AccountId>,
-    ) -> PromiseOrValue<AccountId> {
+        name: Option<String>,
+        version: Option<String>,
+    ) -> Promise {
+        self.create(config, admin, name, version)
+    }
+
+    fn create(
+        &mut self,
+        config: LaunchpadConfig,
+        admin: Option<AccountId>,
+        name: Option<String>,
+        version: Option<String>,
+    ) -> Promise {
+        let deposit = env::attached_deposit();
+        let (promise, launchpad) = self.start_creation(
+            config,
+            admin,
+            name,
+            version.as_deref(),
+            env::predecessor_account_id(),
+            deposit,
+        );
+
+        promise.then(Self::ext(env::current_account_id()).finish_create_launchpad(launchpad))
+    }
+
+    /// Reserves the launchpad account id, charges the creator and returns the promise which
+    /// creates the launchpad account, deploys the code and initializes the launchpad.
+    fn start_creation(
+        &mut self,
+        config: LaunchpadConfig,
+        admin: Option<AccountId>,
+        name: Option<String>,
+        version: Option<&str>,
+        creator: AccountId,
+        deposit: NearToken,
+    ) -> (Promise, PendingLaunchpad) {
+        config
+            .validate_all()
+            .unwrap_or_else(|errors| env::panic_str(&format!("Invalid config: {errors}")));
+
         require!(
-            env::attached_deposit() >= LAUNCHPAD_MIN_DEPOSIT,
+            deposit >= LAUNCHPAD_MIN_DEPOSIT,
             format!(
                 "Attached deposit must be at least {}",
                 LAUNCHPAD_MIN_DEPOSIT.exact_amount_display()
             )
         );
 
-        let launchpad_account_id = self.launchpad_account_id();
+        let (launchpad_account_id, launchpad_id) = name.map_or_else(
+            || {
+                let id = self.next_launchpad_id();
+                (
+                    launchpad_account_id(&format!("{LAUNCHPAD_NAME_PREFIX}{id}")),
+                    Some(id),
+                )
+            },
+            |name| (launchpad_account_id(validate_launchpad_name(&name)), None),
+        );
+
+        require!(
+            self.launchpads.insert(launchpad_account_id.clone()),
+            "Launchpad with the same account id already exists"
+        );
 
-        Promise::new(launchpad_account_id.clone())
+        let fee = if self.is_controller(&creator) {
+            None
+        } else {
+            self.launchpad_creators
+                .insert(launchpad_account_id.clone(), creator.clone());
+            self.charge_partner(&creator, deposit)
+        };
+
+        let code = self.launchpad_code(version);
+        let promise = Promise::new(launchpad_account_id.clone())
             .create_account()
-            .transfer(env::attached_deposit())
-            .deploy_contract(LAUNCHPAD_CODE.to_vec())
+            .transfer(Self::launchpad_deposit(deposit, fee.as_ref()))
+            .deploy_contract(code)
             .function_call(
                 "new".to_string(),
                 near_sdk::serde_json::json!({
@@ -101,32 +243,134 @@ impl AuroraLaunchpadFactory {
                 .into_bytes(),
                 NearToken::from_yoctonear(0),
                 LAUNCHPAD_DEPLOY_GAS,
-            )
-            .then(
-                Self::ext(env::current_account_id()).finish_create_launchpad(launchpad_account_id),
-            )
-            .into()
+            );
+
+        (
+            promise,
+            PendingLaunchpad {
+                account_id: launchpad_account_id,
+                launchpad_id,
+                creator,
+                deposit,
+                fee,
+            },
+        )
     }
 
+    /// Returns the launchpad account id and pays the creation fee to the treasury if the creation
+    /// succeeded. Otherwise, refunds the attached deposit and the creation fee to the caller,
+    /// releases the account id and returns `None`.
     #[private]
-    pub fn finish_create_launchpad(&mut self, launchpad_account_id: AccountId) -> AccountId {
+    pub fn finish_create_launchpad(&mut self, launchpad: PendingLaunchpad) -> Option<AccountId> {
         if env::promise_result_checked(0, 0).is_ok() {
             log!(
                 "Launchpad with the account id: {} created successfully",
-                &launchpad_account_id
+                &launchpad.account_id
             );
 
-            launchpad_account_id
-        } else {
-            env::panic_str("Error while creating launchpad contract");
+            if let Some(fee) = launchpad.fee {
+                Self::pay_creation_fee(launchpad.creator, fee);
+            }
+
+            return Some(launchpad.account_id);
+        }
+
+        log!(
+            "Error while creating launchpad contract: {}. Refunding {} to {}",
+            &launchpad.account_id,
+            launchpad.deposit.exact_amount_display(),
+            &launchpad.creator
+        );
+
+        self.abort_creation(&launchpad);
+
+        let _ = Promise::new(launchpad.creator).transfer(launchpad.deposit);
+
+        None
+    }
+
+    /// Returns `true` if the launchpad with the provided account id was created by the factory
+    /// or is being created at the moment.
+    pub fn is_launchpad(&self, account_id: &AccountId) -> bool {
+        self.launchpads.contains(account_id)
+    }
+
+    /// Releases the account id and the quota taken by the launchpad which creation failed and
+    /// returns the creation fee paid in NEP-141 tokens to the creator.
+    fn abort_creation(&mut self, launchpad: &PendingLaunchpad) {
+        self.launchpads.remove(&launchpad.account_id);
+
+        if let Some(id) = launchpad.launchpad_id {
+            self.release_launchpad_id(id);
+        }
+
+        if self
+            .launchpad_creators
+            .remove(&launchpad.account_id)
+            .is_some()
+        {
+            self.refund_partner(&launchpad.creator, launchpad.fee.clone());
         }
     }
 
-    fn launchpad_account_id(&mut self) -> AccountId {
-        // TODO: Do not increment the counter if the creation fails.
-        self.launchpad_count += 1;
-        format!("lp-{}.{}", self.launchpad_count, env::current_account_id())
-            .parse()
-            .expect("Failed to parse launchpad account ID")
+    fn is_controller(&self, account_id: &AccountId) -> bool {
+        self.acl_has_role(Role::Controller.into(), account_id.clone())
     }
+
+    fn with_launchpad_count(launchpad_count: u64) -> Self {
+        Self {
+            launchpad_count,
+            released_ids: Vec::new(),
+            launchpads: LookupSet::new(StorageKey::Launchpads),
+            launchpad_codes: IterableMap::new(StorageKey::LaunchpadCodes),
+            launchpad_code_blobs: LookupMap::new(StorageKey::LaunchpadCodeBlobs),
+            staged_launchpad_codes: LookupMap::new(StorageKey::StagedLaunchpadCodes),
+            default_launchpad_version: None,
+            launchpad_upgrades: LookupMap::new(StorageKey::LaunchpadUpgrades),
+            global_launchpad_codes: LookupSet::new(StorageKey::GlobalLaunchpadCodes),
+            partners: IterableMap::new(StorageKey::Partners),
+            launchpad_creators: LookupMap::new(StorageKey::LaunchpadCreators),
+            creation_fee: None,
+            creation_fee_deposits: LookupMap::new(StorageKey::CreationFeeDeposits),
+            creation_deposits: LookupMap::new(StorageKey::CreationDeposits),
+            config_templates: IterableMap::new(StorageKey::ConfigTemplates),
+        }
+    }
+
+    fn next_launchpad_id(&mut self) -> u64 {
+        self.released_ids.pop().unwrap_or_else(|| {
+            self.launchpad_count += 1;
+            self.launchpad_count
+        })
+    }
+
+    fn release_launchpad_id(&mut self, id: u64) {
+        if id == self.launchpad_count {
+            self.launchpad_count -= 1;
+        } else {
+            self.released_ids.push(id);
+        }
+    }
+}
+
+fn launchpad_account_id(name: &str) -> AccountId {
+    format!("{name}.{}", env::current_account_id())
+        .parse()
+        .unwrap_or_else(|_| env::panic_str("Invalid launchpad name"))
+}
+
+fn validate_launchpad_name(name: &str) -> &str {
+    require!(!name.is_empty(), "Launchpad name must not be empty");
+    require!(!name.contains('.'), "Launchpad name must not contain dots");
+    require!(
+        !is_generated_name(name),
+        format!("Launchpad names in format `{LAUNCHPAD_NAME_PREFIX}<number>` are reserved")
+    );
+
+    name
+}
+
+fn is_generated_name(name: &str) -> bool {
+    name.strip_prefix(LAUNCHPAD_NAME_PREFIX)
+        .is_some_and(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
 }
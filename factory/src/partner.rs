@@ -0,0 +1,357 @@
+use aurora_launchpad_types::config::LaunchpadStatus;
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::U128;
+use near_sdk::{
+    AccountId, Gas, NearToken, Promise, PromiseOrValue, env, ext_contract, log, near, require,
+};
+
+use crate::{AuroraLaunchpadFactory, AuroraLaunchpadFactoryExt, LAUNCHPAD_MIN_DEPOSIT, Role};
+
+const GAS_FOR_FT_TRANSFER: Gas = Gas::from_tgas(5);
+const GAS_FOR_GET_STATUS: Gas = Gas::from_tgas(5);
+const GAS_FOR_FINISH_CALLBACK: Gas = Gas::from_tgas(5);
+const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+/// The maximum length of the serialized `LaunchpadStatus`.
+const MAX_STATUS_RESULT_LENGTH: u64 = 32;
+
+#[ext_contract(ext_ft)]
+trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+#[ext_contract(ext_launchpad)]
+trait Launchpad {
+    fn get_status(&self) -> LaunchpadStatus;
+}
+
+/// The token the creation fee is paid in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub enum FeeToken {
+    /// The fee is attached to the `create_launchpad` transaction in addition to the deposit.
+    Near,
+    /// The fee is transferred in advance with `ft_transfer_call` to the factory.
+    Nep141(AccountId),
+}
+
+/// The fee paid by partners for creating a launchpad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct CreationFee {
+    /// The token the fee is paid in.
+    pub token: FeeToken,
+    /// The amount of the fee.
+    pub amount: U128,
+    /// The account which receives the fees.
+    pub treasury: AccountId,
+}
+
+/// The account approved for creating launchpads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct Partner {
+    /// The maximum number of live launchpads created by the partner.
+    pub max_live_launchpads: u32,
+    /// The number of live launchpads created by the partner.
+    pub live_launchpads: u32,
+}
+
+#[near]
+impl AuroraLaunchpadFactory {
+    /// Adds the partner account allowed to create launchpads or updates the quota of the existing
+    /// one. The quota limits the number of launchpads created by the partner, which are not
+    /// finished yet.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn set_partner(&mut self, account_id: AccountId, max_live_launchpads: u32) {
+        let live_launchpads = self
+            .partners
+            .get(&account_id)
+            .map_or(0, |partner| partner.live_launchpads);
+
+        self.partners.insert(
+            account_id,
+            Partner {
+                max_live_launchpads,
+                live_launchpads,
+            },
+        );
+    }
+
+    /// Removes the partner account, so it can't create launchpads anymore.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn remove_partner(&mut self, account_id: &AccountId) {
+        require!(
+            self.partners.remove(account_id).is_some(),
+            "The account is not a partner"
+        );
+    }
+
+    /// Sets the fee paid by partners for creating a launchpad. No fee is taken if `None`.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn set_creation_fee(&mut self, fee: Option<CreationFee>) {
+        self.creation_fee = fee;
+    }
+
+    /// Returns the partner with the provided account id, if any.
+    pub fn get_partner(&self, account_id: &AccountId) -> Option<Partner> {
+        self.partners.get(account_id).copied()
+    }
+
+    /// Returns all partners with their quotas.
+    pub fn get_partners(&self) -> Vec<(AccountId, Partner)> {
+        self.partners
+            .iter()
+            .map(|(account_id, partner)| (account_id.clone(), *partner))
+            .collect()
+    }
+
+    /// Returns the fee paid by partners for creating a launchpad, if any.
+    pub fn get_creation_fee(&self) -> Option<CreationFee> {
+        self.creation_fee.clone()
+    }
+
+    /// Returns the amount of the NEP-141 tokens deposited by the account for paying creation fees.
+    pub fn get_creation_fee_deposit(&self, account_id: &AccountId, token_id: &AccountId) -> U128 {
+        U128(
+            self.creation_fee_deposits
+                .get(&(account_id.clone(), token_id.clone()))
+                .copied()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Withdraws the NEP-141 tokens deposited by the caller for paying creation fees.
+    pub fn withdraw_creation_fee_deposit(&mut self, token_id: AccountId) -> Promise {
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .creation_fee_deposits
+            .remove(&(account_id.clone(), token_id.clone()))
+            .filter(|amount| *amount > 0)
+            .unwrap_or_else(|| env::panic_str("No creation fee deposit to withdraw"));
+
+        ext_ft::ext(token_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(account_id.clone(), U128(amount), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_CALLBACK)
+                    .finish_withdraw_creation_fee_deposit(account_id, token_id, U128(amount)),
+            )
+    }
+
+    /// Restores the creation fee deposit if the withdrawal failed.
+    #[private]
+    pub fn finish_withdraw_creation_fee_deposit(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) {
+        if env::promise_result_checked(0, 0).is_err() {
+            log!(
+                "Withdrawal of the creation fee deposit failed, restoring {}",
+                amount.0
+            );
+            self.credit_creation_fee_deposit(account_id, token_id, amount.0);
+        }
+    }
+
+    /// Credits the creation fee back to the deposit of the creator if the transfer to
+    /// the treasury failed, e.g. the treasury isn't registered in the token.
+    #[private]
+    pub fn finish_pay_creation_fee(
+        &mut self,
+        creator: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) {
+        if env::promise_result_checked(0, 0).is_err() {
+            log!(
+                "Transfer of the creation fee to the treasury failed, crediting {} back to {creator}",
+                amount.0
+            );
+            self.credit_creation_fee_deposit(creator, token_id, amount.0);
+        }
+    }
+
+    /// Releases the quota taken by the launchpad if the sale of the launchpad is finished.
+    /// Could be called by anyone.
+    pub fn release_launchpad_quota(&mut self, launchpad_account_id: AccountId) -> Promise {
+        require!(
+            self.launchpad_creators.contains_key(&launchpad_account_id),
+            "The launchpad doesn't take a quota"
+        );
+
+        ext_launchpad::ext(launchpad_account_id.clone())
+            .with_static_gas(GAS_FOR_GET_STATUS)
+            .get_status()
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_FINISH_CALLBACK)
+                    .finish_release_launchpad_quota(launchpad_account_id),
+            )
+    }
+
+    /// Releases the quota if the launchpad is finished. Returns `true` if the quota is released.
+    #[private]
+    pub fn finish_release_launchpad_quota(&mut self, launchpad_account_id: AccountId) -> bool {
+        let status = env::promise_result_checked(0, MAX_STATUS_RESULT_LENGTH)
+            .ok()
+            .and_then(|bytes| near_sdk::serde_json::from_slice::<LaunchpadStatus>(&bytes).ok());
+
+        if !matches!(
+            status,
+            Some(LaunchpadStatus::Success | LaunchpadStatus::Failed | LaunchpadStatus::Archived)
+        ) {
+            log!("The launchpad {launchpad_account_id} is not finished yet");
+            return false;
+        }
+
+        if let Some(creator) = self.launchpad_creators.remove(&launchpad_account_id) {
+            self.release_partner_quota(&creator);
+        }
+
+        true
+    }
+}
+
+impl AuroraLaunchpadFactory {
+    /// Accepts the NEP-141 tokens deposited by partners for paying creation fees.
+    pub(crate) fn deposit_creation_fee(
+        &mut self,
+        sender_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        require!(
+            self.creation_fee
+                .as_ref()
+                .is_some_and(|fee| fee.token == FeeToken::Nep141(token_id.clone())),
+            "The token is not accepted for paying creation fees"
+        );
+        require!(
+            self.partners.contains_key(&sender_id),
+            "Only partners can deposit creation fees"
+        );
+
+        self.credit_creation_fee_deposit(sender_id, token_id, amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+
+    /// Takes the quota of the partner and charges the creation fee. Returns the fee to be paid
+    /// to the treasury after the launchpad is created.
+    pub(crate) fn charge_partner(
+        &mut self,
+        creator: &AccountId,
+        deposit: NearToken,
+    ) -> Option<CreationFee> {
+        let partner = self.partners.get_mut(creator).unwrap_or_else(|| {
+            env::panic_str("Only controllers and partners can create launchpads")
+        });
+
+        require!(
+            partner.live_launchpads < partner.max_live_launchpads,
+            "The quota of live launchpads is exceeded"
+        );
+
+        partner.live_launchpads += 1;
+
+        let fee = self.creation_fee.clone()?;
+
+        match &fee.token {
+            FeeToken::Near => {
+                let min_deposit =
+                    LAUNCHPAD_MIN_DEPOSIT.saturating_add(NearToken::from_yoctonear(fee.amount.0));
+                require!(
+                    deposit >= min_deposit,
+                    format!(
+                        "Attached deposit must be at least {} including the creation fee",
+                        min_deposit.exact_amount_display()
+                    )
+                );
+            }
+            FeeToken::Nep141(token_id) => {
+                let key = (creator.clone(), token_id.clone());
+                let balance = self
+                    .creation_fee_deposits
+                    .get_mut(&key)
+                    .filter(|balance| **balance >= fee.amount.0)
+                    .unwrap_or_else(|| env::panic_str("Insufficient creation fee deposit"));
+
+                *balance -= fee.amount.0;
+            }
+        }
+
+        Some(fee)
+    }
+
+    /// Returns the amount of NEAR which is attached to the launchpad account.
+    pub(crate) fn launchpad_deposit(deposit: NearToken, fee: Option<&CreationFee>) -> NearToken {
+        match fee {
+            Some(CreationFee {
+                token: FeeToken::Near,
+                amount,
+                ..
+            }) => deposit.saturating_sub(NearToken::from_yoctonear(amount.0)),
+            _ => deposit,
+        }
+    }
+
+    /// Transfers the creation fee to the treasury. The fee in NEP-141 tokens is credited back
+    /// to the creation fee deposit of the creator if the transfer fails.
+    pub(crate) fn pay_creation_fee(creator: AccountId, fee: CreationFee) {
+        match fee.token {
+            FeeToken::Near => {
+                let _ =
+                    Promise::new(fee.treasury).transfer(NearToken::from_yoctonear(fee.amount.0));
+            }
+            FeeToken::Nep141(token_id) => {
+                let _ = ext_ft::ext(token_id.clone())
+                    .with_attached_deposit(ONE_YOCTO)
+                    .with_static_gas(GAS_FOR_FT_TRANSFER)
+                    .ft_transfer(fee.treasury, fee.amount, None)
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_FINISH_CALLBACK)
+                            .finish_pay_creation_fee(creator, token_id, fee.amount),
+                    );
+            }
+        }
+    }
+
+    /// Returns the quota and the creation fee to the partner if the creation failed.
+    /// The fee in NEAR is refunded together with the deposit.
+    pub(crate) fn refund_partner(&mut self, creator: &AccountId, fee: Option<CreationFee>) {
+        self.release_partner_quota(creator);
+
+        if let Some(CreationFee {
+            token: FeeToken::Nep141(token_id),
+            amount,
+            ..
+        }) = fee
+        {
+            self.credit_creation_fee_deposit(creator.clone(), token_id, amount.0);
+        }
+    }
+
+    fn release_partner_quota(&mut self, creator: &AccountId) {
+        if let Some(partner) = self.partners.get_mut(creator) {
+            partner.live_launchpads = partner.live_launchpads.saturating_sub(1);
+        }
+    }
+
+    fn credit_creation_fee_deposit(
+        &mut self,
+        account_id: AccountId,
+        token_id: AccountId,
+        amount: u128,
+    ) {
+        let balance = self
+            .creation_fee_deposits
+            .entry((account_id, token_id))
+            .or_default();
+        *balance = balance.saturating_add(amount);
+    }
+}
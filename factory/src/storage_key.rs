@@ -0,0 +1,34 @@
+use near_sdk::IntoStorageKey;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum StorageKey {
+    Launchpads,
+    LaunchpadCodes,
+    LaunchpadCodeBlobs,
+    StagedLaunchpadCodes,
+    LaunchpadUpgrades,
+    GlobalLaunchpadCodes,
+    Partners,
+    LaunchpadCreators,
+    CreationFeeDeposits,
+    CreationDeposits,
+    ConfigTemplates,
+}
+
+impl IntoStorageKey for StorageKey {
+    fn into_storage_key(self) -> Vec<u8> {
+        match self {
+            Self::Launchpads => b"launchpads".to_vec(),
+            Self::LaunchpadCodes => b"launchpad_codes".to_vec(),
+            Self::LaunchpadCodeBlobs => b"launchpad_code_blobs".to_vec(),
+            Self::StagedLaunchpadCodes => b"staged_launchpad_codes".to_vec(),
+            Self::LaunchpadUpgrades => b"launchpad_upgrades".to_vec(),
+            Self::GlobalLaunchpadCodes => b"global_launchpad_codes".to_vec(),
+            Self::Partners => b"partners".to_vec(),
+            Self::LaunchpadCreators => b"launchpad_creators".to_vec(),
+            Self::CreationFeeDeposits => b"creation_fee_deposits".to_vec(),
+            Self::CreationDeposits => b"creation_deposits".to_vec(),
+            Self::ConfigTemplates => b"config_templates".to_vec(),
+        }
+    }
+}
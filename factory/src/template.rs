@@ -0,0 +1,87 @@
+use aurora_launchpad_types::config::LaunchpadConfig;
+use near_plugins::{AccessControllable, Pausable, access_control_any, pause};
+use near_sdk::serde_json::{self, Value};
+use near_sdk::{AccountId, Promise, env, near, require};
+
+use crate::{AuroraLaunchpadFactory, AuroraLaunchpadFactoryExt, Role};
+
+#[near]
+impl AuroraLaunchpadFactory {
+    /// Adds or replaces the named config template. The template is a JSON object with any subset
+    /// of the `LaunchpadConfig` fields, e.g. the deposit token and the intents account.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn set_config_template(&mut self, name: String, template: Value) {
+        require!(!name.is_empty(), "Template name must not be empty");
+        require!(template.is_object(), "Template must be a JSON object");
+
+        self.config_templates.insert(name, template.to_string());
+    }
+
+    /// Removes the named config template.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn remove_config_template(&mut self, name: String) {
+        require!(
+            self.config_templates.remove(&name).is_some(),
+            "Unknown config template"
+        );
+    }
+
+    /// Returns the named config template, if any.
+    pub fn get_config_template(&self, name: String) -> Option<Value> {
+        self.config_templates
+            .get(&name)
+            .map(|template| parse_template(template))
+    }
+
+    /// Returns the names of all config templates.
+    pub fn get_config_template_names(&self) -> Vec<String> {
+        self.config_templates.keys().cloned().collect()
+    }
+
+    /// Create a new launchpad contract with the config built from the named template.
+    /// The fields provided in `overrides` replace the corresponding fields of the template;
+    /// nested objects are merged recursively. The rest of the arguments are the same as for
+    /// `create_launchpad`.
+    #[payable]
+    #[pause]
+    pub fn create_launchpad_from_template(
+        &mut self,
+        template: String,
+        overrides: Value,
+        admin: Option<AccountId>,
+        name: Option<String>,
+        version: Option<String>,
+    ) -> Promise {
+        require!(overrides.is_object(), "Overrides must be a JSON object");
+
+        let mut config = self
+            .config_templates
+            .get(&template)
+            .map(|template| parse_template(template))
+            .unwrap_or_else(|| env::panic_str("Unknown config template"));
+
+        merge(&mut config, overrides);
+
+        let config: LaunchpadConfig = serde_json::from_value(config)
+            .unwrap_or_else(|err| env::panic_str(&format!("Invalid config: {err}")));
+
+        self.create(config, admin, name, version)
+    }
+}
+
+fn parse_template(template: &str) -> Value {
+    serde_json::from_str(template)
+        .unwrap_or_else(|_| env::panic_str("Failed to parse the config template"))
+}
+
+/// Merges `overrides` into `target`. Objects are merged recursively, other values are replaced.
+fn merge(target: &mut Value, overrides: Value) {
+    match (target, overrides) {
+        (Value::Object(target), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, overrides) => *target = overrides,
+    }
+}
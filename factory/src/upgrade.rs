@@ -0,0 +1,203 @@
+use near_plugins::{AccessControllable, access_control_any};
+use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::serde_json::json;
+use near_sdk::{AccountId, CryptoHash, Gas, NearToken, Promise, env, log, near, require};
+
+use crate::{AuroraLaunchpadFactory, AuroraLaunchpadFactoryExt, Role};
+
+/// The amount of gas for switching the launchpad to the global code, including the gas for
+/// the migration of its state.
+const LAUNCHPAD_UPGRADE_GAS: Gas = Gas::from_tgas(25);
+const FINISH_UPGRADE_LAUNCHPAD_GAS: Gas = Gas::from_tgas(5);
+const FINISH_DEPLOY_GLOBAL_CODE_GAS: Gas = Gas::from_tgas(5);
+/// The maximum number of launchpads upgraded in one call, so the upgrades of all of them fit
+/// into the 300 `TGas` of prepaid gas.
+const MAX_UPGRADES_PER_CALL: usize = 8;
+
+/// Status of the launchpad upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub enum UpgradeStatus {
+    /// The upgrade is in progress.
+    Pending,
+    /// The code is deployed and the state is migrated successfully.
+    Success,
+    /// The upgrade failed, and the launchpad keeps using the previous code.
+    Failed,
+}
+
+/// The result of the latest upgrade of the launchpad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [borsh, json])]
+pub struct LaunchpadUpgrade {
+    /// Version of the launchpad code the launchpad is upgraded to.
+    pub version: String,
+    /// Status of the upgrade.
+    pub status: UpgradeStatus,
+}
+
+#[near]
+impl AuroraLaunchpadFactory {
+    /// Deploys the launchpad code of the provided version as a global contract identified by
+    /// its hash, so the launchpads could be upgraded to it without transferring the code.
+    /// The cost of the global contract is paid from the balance of the factory.
+    #[payable]
+    #[access_control_any(roles(Role::Dao))]
+    pub fn deploy_global_launchpad_code(&mut self, version: String) -> Promise {
+        let code = self.launchpad_code(Some(&version));
+        let hash = env::sha256_array(&code);
+
+        require!(
+            !self.global_launchpad_codes.contains(&hash),
+            "The launchpad code is already deployed as a global contract"
+        );
+
+        Promise::new(env::current_account_id())
+            .deploy_global_contract(code)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(FINISH_DEPLOY_GLOBAL_CODE_GAS)
+                    .finish_deploy_global_launchpad_code(version, hash.into()),
+            )
+    }
+
+    /// Records the launchpad code deployed as a global contract. Returns `true` if
+    /// the deployment succeeded.
+    #[private]
+    pub fn finish_deploy_global_launchpad_code(
+        &mut self,
+        version: String,
+        hash: Base58CryptoHash,
+    ) -> bool {
+        let is_success = env::promise_result_checked(0, 0).is_ok();
+
+        if is_success {
+            log!("Launchpad code version {version} is deployed as a global contract");
+            self.global_launchpad_codes.insert(hash.into());
+        } else {
+            log!("Error while deploying launchpad code version {version} as a global contract");
+        }
+
+        is_success
+    }
+
+    /// Starts tracking the launchpads created by the factory before the launchpads were tracked,
+    /// so they could be upgraded with `upgrade_launchpads`. The launchpads must be direct
+    /// subaccounts of the factory.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn track_launchpads(&mut self, ids: Vec<AccountId>) {
+        let current_account_id = env::current_account_id();
+
+        for launchpad_account_id in ids {
+            require!(
+                launchpad_account_id
+                    .as_str()
+                    .strip_suffix(current_account_id.as_str())
+                    .and_then(|name| name.strip_suffix('.'))
+                    .is_some_and(|name| !name.is_empty() && !name.contains('.')),
+                format!("{launchpad_account_id} is not a subaccount of the factory")
+            );
+
+            self.launchpads.insert(launchpad_account_id);
+        }
+    }
+
+    /// Upgrades the provided launchpads to the registered version of the launchpad code, which
+    /// must be deployed as a global contract with `deploy_global_launchpad_code` beforehand.
+    /// Every launchpad switches to the global code and migrates its state. The result of
+    /// the upgrade is recorded for every launchpad and could be retrieved with
+    /// `get_launchpad_upgrade`. Up to `MAX_UPGRADES_PER_CALL` launchpads are upgraded in
+    /// one call.
+    #[access_control_any(roles(Role::Dao))]
+    pub fn upgrade_launchpads(&mut self, ids: Vec<AccountId>, version: String) {
+        require!(!ids.is_empty(), "No launchpads to upgrade");
+        require!(
+            ids.len() <= MAX_UPGRADES_PER_CALL,
+            format!("The number of launchpads to upgrade must not exceed {MAX_UPGRADES_PER_CALL}")
+        );
+
+        let hash = self.launchpad_code_hash(&version);
+
+        require!(
+            self.global_launchpad_codes.contains(&hash),
+            "The launchpad code version is not deployed as a global contract"
+        );
+
+        let args = json!({ "code_hash": Base58CryptoHash::from(hash) })
+            .to_string()
+            .into_bytes();
+
+        for launchpad_account_id in ids {
+            require!(
+                self.launchpads.contains(&launchpad_account_id),
+                format!("{launchpad_account_id} is not a launchpad created by the factory")
+            );
+            require!(
+                self.launchpad_upgrades
+                    .get(&launchpad_account_id)
+                    .is_none_or(|upgrade| upgrade.status != UpgradeStatus::Pending),
+                format!("The upgrade of {launchpad_account_id} is in progress")
+            );
+
+            self.launchpad_upgrades.insert(
+                launchpad_account_id.clone(),
+                LaunchpadUpgrade {
+                    version: version.clone(),
+                    status: UpgradeStatus::Pending,
+                },
+            );
+
+            let _ = Promise::new(launchpad_account_id.clone())
+                .function_call(
+                    "upgrade_to_global_code".to_string(),
+                    args.clone(),
+                    NearToken::from_yoctonear(0),
+                    LAUNCHPAD_UPGRADE_GAS,
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(FINISH_UPGRADE_LAUNCHPAD_GAS)
+                        .with_unused_gas_weight(0)
+                        .finish_upgrade_launchpad(launchpad_account_id),
+                );
+        }
+    }
+
+    /// Records the result of the launchpad upgrade. Returns `true` if the upgrade succeeded.
+    #[private]
+    pub fn finish_upgrade_launchpad(&mut self, launchpad_account_id: AccountId) -> bool {
+        let is_success = env::promise_result_checked(0, 0).is_ok();
+        let upgrade = self
+            .launchpad_upgrades
+            .get_mut(&launchpad_account_id)
+            .unwrap_or_else(|| env::panic_str("No upgrade for the launchpad"));
+
+        upgrade.status = if is_success {
+            log!(
+                "Launchpad {launchpad_account_id} is upgraded to version {}",
+                upgrade.version
+            );
+            UpgradeStatus::Success
+        } else {
+            log!(
+                "Error while upgrading launchpad {launchpad_account_id} to version {}",
+                upgrade.version
+            );
+            UpgradeStatus::Failed
+        };
+
+        is_success
+    }
+
+    /// Returns the result of the latest upgrade of the launchpad, if any.
+    pub fn get_launchpad_upgrade(&self, account_id: &AccountId) -> Option<LaunchpadUpgrade> {
+        self.launchpad_upgrades.get(account_id).cloned()
+    }
+
+    /// Returns `true` if the launchpad code with the provided hash is deployed as a global
+    /// contract by the factory.
+    pub fn is_global_launchpad_code(&self, hash: Base58CryptoHash) -> bool {
+        self.global_launchpad_codes
+            .contains(&CryptoHash::from(hash))
+    }
+}
@@ -81,6 +81,11 @@ impl FungibleTokenCore for Contract {
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        // Intentional built-in failure to test sad paths.
+        if receiver_id.as_str().starts_with("fail-funding") {
+            env::panic_str("Funding failed!");
+        }
+
         self.token.ft_transfer_call(receiver_id, amount, memo, msg)
     }
 
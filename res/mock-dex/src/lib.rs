@@ -0,0 +1,171 @@
+#![allow(clippy::needless_pass_by_value)]
+use near_sdk::json_types::U128;
+use near_sdk::store::LookupMap;
+use near_sdk::{assert_one_yocto, env, near, require, AccountId, PanicOnDefault, PromiseOrValue};
+
+#[near(serializers = [borsh, json])]
+#[derive(Clone)]
+pub struct Pool {
+    pub token_account_ids: Vec<AccountId>,
+    pub amounts: Vec<U128>,
+    pub total_fee: u32,
+    pub shares_total_supply: U128,
+}
+
+#[derive(PanicOnDefault)]
+#[near(contract_state)]
+pub struct Contract {
+    pools: Vec<Pool>,
+    /// Internal balances of the deposited tokens per account.
+    deposits: LookupMap<AccountId, Vec<(AccountId, U128)>>,
+    /// LP shares per pool and account.
+    shares: LookupMap<(u64, AccountId), U128>,
+}
+
+#[near]
+impl Contract {
+    #[init]
+    pub fn new() -> Self {
+        Self {
+            pools: Vec::new(),
+            deposits: LookupMap::new(b"d".as_slice()),
+            shares: LookupMap::new(b"s".as_slice()),
+        }
+    }
+
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>, registration_only: Option<bool>) {
+        let _ = registration_only;
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        self.deposits.entry(account_id).or_default();
+    }
+
+    #[payable]
+    pub fn register_tokens(&mut self, token_ids: Vec<AccountId>) {
+        assert_one_yocto();
+        let deposits = self
+            .deposits
+            .get_mut(&env::predecessor_account_id())
+            .unwrap_or_else(|| env::panic_str("Account is not registered"));
+
+        for token_id in token_ids {
+            if !deposits.iter().any(|(id, _)| *id == token_id) {
+                deposits.push((token_id, U128(0)));
+            }
+        }
+    }
+
+    #[payable]
+    pub fn add_simple_pool(&mut self, tokens: Vec<AccountId>, fee: u32) -> u64 {
+        require!(
+            env::attached_deposit().as_yoctonear() > 0,
+            "Storage deposit is required"
+        );
+        let amounts = vec![U128(0); tokens.len()];
+        self.pools.push(Pool {
+            token_account_ids: tokens,
+            amounts,
+            total_fee: fee,
+            shares_total_supply: U128(0),
+        });
+
+        u64::try_from(self.pools.len() - 1).unwrap()
+    }
+
+    pub fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        require!(msg.is_empty(), "Only deposits are supported");
+        let token_id = env::predecessor_account_id();
+        let deposits = self
+            .deposits
+            .get_mut(&sender_id)
+            .unwrap_or_else(|| env::panic_str("Account is not registered"));
+        let (_, balance) = deposits
+            .iter_mut()
+            .find(|(id, _)| *id == token_id)
+            .unwrap_or_else(|| env::panic_str("Token is not registered"));
+        balance.0 += amount.0;
+
+        PromiseOrValue::Value(U128(0))
+    }
+
+    #[payable]
+    pub fn add_liquidity(
+        &mut self,
+        pool_id: u64,
+        amounts: Vec<U128>,
+        min_amounts: Option<Vec<U128>>,
+    ) -> U128 {
+        let _ = min_amounts;
+        let account_id = env::predecessor_account_id();
+        let pool = &mut self.pools[usize::try_from(pool_id).unwrap()];
+        let deposits = self
+            .deposits
+            .get_mut(&account_id)
+            .unwrap_or_else(|| env::panic_str("Account is not registered"));
+
+        for ((token_id, amount), pool_amount) in pool
+            .token_account_ids
+            .iter()
+            .zip(&amounts)
+            .zip(pool.amounts.iter_mut())
+        {
+            let (_, balance) = deposits
+                .iter_mut()
+                .find(|(id, _)| id == token_id)
+                .unwrap_or_else(|| env::panic_str("Token is not registered"));
+            require!(balance.0 >= amount.0, "Not enough tokens in deposit");
+            balance.0 -= amount.0;
+            pool_amount.0 += amount.0;
+        }
+
+        // The shares of the first liquidity are equal to the amount of the first token.
+        let shares = amounts[0];
+        pool.shares_total_supply.0 += shares.0;
+        self.shares.entry((pool_id, account_id)).or_default().0 += shares.0;
+
+        shares
+    }
+
+    #[payable]
+    pub fn mft_transfer(
+        &mut self,
+        token_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        let _ = memo;
+        assert_one_yocto();
+        let pool_id = token_id
+            .strip_prefix(':')
+            .and_then(|id| id.parse::<u64>().ok())
+            .unwrap_or_else(|| env::panic_str("Wrong token id"));
+        let sender = self
+            .shares
+            .get_mut(&(pool_id, env::predecessor_account_id()))
+            .unwrap_or_else(|| env::panic_str("No shares"));
+        require!(sender.0 >= amount.0, "Not enough shares");
+        sender.0 -= amount.0;
+        self.shares.entry((pool_id, receiver_id)).or_default().0 += amount.0;
+    }
+
+    pub fn get_pool(&self, pool_id: u64) -> Pool {
+        self.pools[usize::try_from(pool_id).unwrap()].clone()
+    }
+
+    pub fn get_pool_shares(&self, pool_id: u64, account_id: AccountId) -> U128 {
+        self.shares
+            .get(&(pool_id, account_id))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn get_deposits(&self, account_id: AccountId) -> Vec<(AccountId, U128)> {
+        self.deposits.get(&account_id).cloned().unwrap_or_default()
+    }
+}
@@ -0,0 +1,94 @@
+use aurora_launchpad_types::{IntentsAccount, date_time};
+use near_sdk::json_types::U128;
+use near_sdk::near;
+use near_sdk::serde_json::{self, Value};
+
+use crate::Error;
+
+/// The header of the timeline in the CSV format.
+const CSV_HEADER: &str = "timestamp,account,action,amount";
+
+/// An action of the participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [json])]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Deposit,
+    Withdraw,
+}
+
+/// A deposit or a withdrawal of the participant at the given time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct Event {
+    /// Time of the event.
+    #[serde(
+        deserialize_with = "date_time::deserialize",
+        serialize_with = "date_time::serialize"
+    )]
+    pub timestamp: u64,
+    /// The intents account of the participant.
+    pub account: IntentsAccount,
+    /// The deposit or the withdrawal.
+    pub action: Action,
+    /// The number of deposit tokens.
+    pub amount: U128,
+}
+
+/// Parses the timeline from the JSON array of events.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is not an array of events.
+pub fn timeline_from_json(json: &str) -> Result<Vec<Event>, Error> {
+    serde_json::from_str(json).map_err(Error::Json)
+}
+
+/// Parses the timeline from CSV with the `timestamp,account,action,amount` columns. The time
+/// is either a date time in ISO 8601 format or a timestamp in nanoseconds. The header, empty
+/// lines and lines starting with `#` are skipped.
+///
+/// # Errors
+///
+/// Returns an error with the line number if a line can't be parsed.
+pub fn timeline_from_csv(csv: &str) -> Result<Vec<Event>, Error> {
+    csv.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#') && *line != CSV_HEADER)
+        .map(|(line_number, line)| {
+            parse_csv_line(line).map_err(|reason| Error::Csv {
+                line: line_number,
+                reason,
+            })
+        })
+        .collect()
+}
+
+fn parse_csv_line(line: &str) -> Result<Event, String> {
+    let columns = line.split(',').map(str::trim).collect::<Vec<_>>();
+    let [timestamp, account, action, amount] = columns.as_slice() else {
+        return Err(format!("Expected columns: {CSV_HEADER}"));
+    };
+
+    let timestamp = timestamp.parse::<u64>().or_else(|_| {
+        date_time::deserialize(Value::String((*timestamp).to_string()))
+            .map_err(|e| format!("Wrong timestamp: {e}"))
+    })?;
+    let account = IntentsAccount::try_from(*account).map_err(ToString::to_string)?;
+    let action = match *action {
+        "deposit" => Action::Deposit,
+        "withdraw" => Action::Withdraw,
+        _ => return Err(format!("Unknown action: {action}")),
+    };
+    let amount = amount
+        .parse::<u128>()
+        .map_err(|e| format!("Wrong amount: {e}"))?;
+
+    Ok(Event {
+        timestamp,
+        account,
+        action,
+        amount: U128(amount),
+    })
+}
@@ -0,0 +1,359 @@
+//! Off-chain simulator of the launchpad sale. It replays the timeline of deposits and withdrawals
+//! with the same mechanics as the launchpad contract, so issuers could check the allocations,
+//! refunds, claim schedules and fill levels of the discount phases before the deployment.
+
+use aurora_launchpad_contract::DiscountPhases;
+use aurora_launchpad_contract::mechanics::claim::{available_for_claim, user_allocation};
+use aurora_launchpad_contract::mechanics::status::sale_status;
+use aurora_launchpad_contract::mechanics::{deposit, withdraw};
+use aurora_launchpad_types::config::{LaunchpadConfig, LaunchpadStatus, Mechanics};
+use aurora_launchpad_types::discount::DepositDistribution;
+use aurora_launchpad_types::{IntentsAccount, InvestmentAmount};
+use near_sdk::json_types::U128;
+use near_sdk::serde_json;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+pub use input::{Action, Event, timeline_from_csv, timeline_from_json};
+pub use report::{AccountReport, ClaimPoint, EventOutcome, PhaseReport, Report};
+pub use state::MemoryDiscountState;
+
+mod input;
+mod report;
+mod state;
+#[cfg(test)]
+mod tests;
+
+/// The number of equal steps of the claim schedule between the end of the cliff and the end of
+/// the vesting.
+const CLAIM_SCHEDULE_STEPS: u64 = 4;
+
+/// The launchpad state driven by the timeline of events.
+pub struct Simulator {
+    config: LaunchpadConfig,
+    discount_state: Option<MemoryDiscountState>,
+    investments: BTreeMap<IntentsAccount, InvestmentAmount>,
+    refunds: BTreeMap<IntentsAccount, u128>,
+    withdrawals: BTreeMap<IntentsAccount, u128>,
+    total_deposited: u128,
+    total_sold_tokens: u128,
+    outcomes: Vec<EventOutcome>,
+}
+
+impl Simulator {
+    /// Creates the simulator of the initialized launchpad with the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid.
+    pub fn new(config: LaunchpadConfig) -> Result<Self, Error> {
+        config.validate().map_err(Error::InvalidConfig)?;
+
+        Ok(Self {
+            discount_state: config.discounts.as_ref().map(MemoryDiscountState::init),
+            config,
+            investments: BTreeMap::new(),
+            refunds: BTreeMap::new(),
+            withdrawals: BTreeMap::new(),
+            total_deposited: 0,
+            total_sold_tokens: 0,
+            outcomes: Vec::new(),
+        })
+    }
+
+    /// Replays the timeline and returns the report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid or the events are not ordered by time.
+    pub fn run(config: LaunchpadConfig, timeline: &[Event]) -> Result<Report, Error> {
+        let mut simulator = Self::new(config)?;
+
+        for event in timeline {
+            simulator.apply(event)?;
+        }
+
+        Ok(simulator.report())
+    }
+
+    /// Applies the event to the state. Events rejected by the contract are recorded with the
+    /// error and don't change the state. The events must be ordered by time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event is earlier than the previous one.
+    pub fn apply(&mut self, event: &Event) -> Result<(), Error> {
+        if self
+            .outcomes
+            .last()
+            .is_some_and(|last| last.event.timestamp > event.timestamp)
+        {
+            return Err(Error::UnorderedTimeline {
+                index: self.outcomes.len(),
+            });
+        }
+
+        let result = match event.action {
+            Action::Deposit => self.deposit(&event.account, event.amount.0, event.timestamp),
+            Action::Withdraw => self
+                .withdraw(&event.account, event.amount.0, event.timestamp)
+                .map(|()| 0),
+        };
+
+        if let Ok(refund) = result
+            && refund > 0
+        {
+            *self.refunds.entry(event.account.clone()).or_default() += refund;
+        }
+
+        self.outcomes.push(EventOutcome {
+            event: event.clone(),
+            refund: U128(result.unwrap_or_default()),
+            error: result.err().map(ToString::to_string),
+        });
+
+        Ok(())
+    }
+
+    /// Returns the status of the sale at the given time.
+    #[must_use]
+    pub fn status_at(&self, timestamp: u64) -> LaunchpadStatus {
+        sale_status(
+            &self.config,
+            self.total_deposited,
+            self.total_sold_tokens,
+            timestamp,
+        )
+    }
+
+    /// Returns the report of the sale after the end of the sale and TGE.
+    #[must_use]
+    pub fn report(&self) -> Report {
+        let status = self.status_at(self.config.tge.unwrap_or(self.config.end_date));
+        let is_success = matches!(status, LaunchpadStatus::Success);
+        let accounts = self
+            .investments
+            .iter()
+            .map(|(account, investment)| AccountReport {
+                account: account.clone(),
+                deposited: U128(investment.amount),
+                weight: U128(investment.weight),
+                refunded: U128(self.refunds.get(account).copied().unwrap_or_default()),
+                withdrawn: U128(self.withdrawals.get(account).copied().unwrap_or_default()),
+                allocation: U128(if is_success {
+                    user_allocation(investment.weight, self.total_sold_tokens, &self.config)
+                        .unwrap_or_default()
+                } else {
+                    0
+                }),
+                claim_schedule: if is_success {
+                    self.claim_schedule(investment)
+                } else {
+                    Vec::new()
+                },
+            })
+            .collect();
+        let phases = self
+            .discount_state
+            .as_ref()
+            .map(|state| {
+                state
+                    .phase_ids()
+                    .into_iter()
+                    .map(|id| PhaseReport {
+                        id,
+                        sale_tokens: U128(state.total_sale_tokens(id)),
+                        limit: state.limit_per_phase(id).map(U128),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Report {
+            status,
+            total_deposited: U128(self.total_deposited),
+            total_sold_tokens: U128(self.total_sold_tokens),
+            participants_count: self
+                .investments
+                .values()
+                .filter(|investment| investment.amount > 0)
+                .count() as u64,
+            events: self.outcomes.clone(),
+            accounts,
+            phases,
+        }
+    }
+
+    /// Deposits the amount as `handle_deposit` of the contract does. Returns the refund.
+    fn deposit(
+        &mut self,
+        account: &IntentsAccount,
+        amount: u128,
+        timestamp: u64,
+    ) -> Result<u128, &'static str> {
+        if !matches!(self.status_at(timestamp), LaunchpadStatus::Ongoing) {
+            return Err("Launchpad is not ongoing");
+        }
+
+        if amount < self.config.min_deposit.0 {
+            return Err("Deposit amount is too low");
+        }
+
+        let deposit_distribution = self.deposit_distribution(account, amount, timestamp);
+
+        if let DepositDistribution::Refund(refund) = deposit_distribution {
+            return Ok(refund);
+        }
+
+        let is_new_participant = !self.investments.contains_key(account);
+        let investment = self.investments.entry(account.clone()).or_default();
+        let refund = match deposit::deposit(
+            investment,
+            amount,
+            &mut self.total_deposited,
+            &mut self.total_sold_tokens,
+            &self.config,
+            &deposit_distribution,
+        ) {
+            Ok(refund) => {
+                self.update_discount_state(account, &deposit_distribution)?;
+                refund
+            }
+            Err(_) => amount,
+        };
+
+        if refund == amount && is_new_participant {
+            self.investments.remove(account);
+        }
+
+        Ok(refund)
+    }
+
+    /// Withdraws the amount as `withdraw` of the contract does. The withdrawal during the sale
+    /// is expected to be made with the signed intents.
+    fn withdraw(
+        &mut self,
+        account: &IntentsAccount,
+        amount: u128,
+        timestamp: u64,
+    ) -> Result<(), &'static str> {
+        let status = self.status_at(timestamp);
+        let is_price_discovery_ongoing = matches!(self.config.mechanics, Mechanics::PriceDiscovery)
+            && matches!(status, LaunchpadStatus::Ongoing);
+
+        if !is_price_discovery_ongoing && !matches!(status, LaunchpadStatus::Failed) {
+            return Err("Withdraw is not allowed");
+        }
+
+        let deposited = self
+            .investments
+            .get(account)
+            .map(|investment| investment.amount)
+            .ok_or("No deposit for the intents account")?;
+        let remain_deposit = deposited
+            .checked_sub(amount)
+            .ok_or("Withdraw amount is greater than the deposit amount")?;
+        let deposit_distribution = self.deposit_distribution(account, remain_deposit, timestamp);
+        let investment = self
+            .investments
+            .get_mut(account)
+            .ok_or("No deposits were found for the intents account")?;
+
+        withdraw::withdraw(
+            investment,
+            amount,
+            &mut self.total_deposited,
+            &mut self.total_sold_tokens,
+            &self.config,
+            &deposit_distribution,
+        )?;
+
+        *self.withdrawals.entry(account.clone()).or_default() += amount;
+
+        Ok(())
+    }
+
+    fn deposit_distribution(
+        &self,
+        account: &IntentsAccount,
+        amount: u128,
+        timestamp: u64,
+    ) -> DepositDistribution {
+        self.discount_state
+            .as_ref()
+            .map_or(DepositDistribution::WithoutDiscount(amount), |state| {
+                state.get_deposit_distribution(
+                    account,
+                    amount,
+                    timestamp,
+                    &self.config,
+                    self.total_sold_tokens,
+                )
+            })
+    }
+
+    fn update_discount_state(
+        &mut self,
+        account: &IntentsAccount,
+        deposit_distribution: &DepositDistribution,
+    ) -> Result<(), &'static str> {
+        // The discount state is tracked for the FixedPrice mechanic only.
+        if let Mechanics::FixedPrice {
+            deposit_token,
+            sale_token,
+        } = self.config.mechanics
+            && let Some(state) = self.discount_state.as_mut()
+        {
+            state.update(account, deposit_distribution, deposit_token.0, sale_token.0)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the sale tokens available for claim at the start of the vesting, at the end of
+    /// the cliff and at equal steps until the end of the vesting.
+    fn claim_schedule(&self, investment: &InvestmentAmount) -> Vec<ClaimPoint> {
+        let vesting_start = self.config.tge.unwrap_or(self.config.end_date);
+        let timestamps = self.config.vesting_schedule.as_ref().map_or_else(
+            || vec![vesting_start],
+            |vesting| vesting.unlock_timestamps(vesting_start, CLAIM_SCHEDULE_STEPS),
+        );
+
+        timestamps
+            .into_iter()
+            .map(|timestamp| ClaimPoint {
+                timestamp,
+                available: U128(
+                    available_for_claim(
+                        investment,
+                        self.total_sold_tokens,
+                        &self.config,
+                        timestamp,
+                    )
+                    .unwrap_or_default(),
+                ),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidConfig(String),
+    Json(serde_json::Error),
+    Csv { line: usize, reason: String },
+    UnorderedTimeline { index: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidConfig(e) => write!(f, "Invalid config: {e}"),
+            Self::Json(e) => write!(f, "Error deserializing the timeline: {e}"),
+            Self::Csv { line, reason } => write!(f, "Wrong timeline at line {line}: {reason}"),
+            Self::UnorderedTimeline { index } => {
+                write!(f, "The event {index} happens before the previous event")
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use aurora_launchpad_simulator::{Simulator, timeline_from_csv, timeline_from_json};
+use aurora_launchpad_types::config::LaunchpadConfig;
+use near_sdk::serde_json;
+use std::fmt::Display;
+use std::path::Path;
+
+#[derive(near_sdk::serde::Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct ConfigWithAdmin {
+    #[allow(dead_code)]
+    admin: Option<String>,
+    config: LaunchpadConfig,
+}
+
+fn main() {
+    match simulate() {
+        Ok(report) => println!("{report}"),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn simulate() -> Result<String, Error> {
+    let mut args = std::env::args().skip(1);
+    let path_to_config = args.next().ok_or(Error::InvalidPath("config"))?;
+    let path_to_timeline = args.next().ok_or(Error::InvalidPath("timeline"))?;
+
+    let config = std::fs::read_to_string(path_to_config).map_err(Error::Read)?;
+    let args: ConfigWithAdmin = serde_json::from_str(&config).map_err(Error::Deserialize)?;
+    let timeline = std::fs::read_to_string(&path_to_timeline).map_err(Error::Read)?;
+    let is_csv = Path::new(&path_to_timeline)
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+    let timeline = if is_csv {
+        timeline_from_csv(&timeline)
+    } else {
+        timeline_from_json(&timeline)
+    }
+    .map_err(Error::Simulation)?;
+
+    let report = Simulator::run(args.config, &timeline).map_err(Error::Simulation)?;
+    serde_json::to_string_pretty(&report).map_err(Error::Serialize)
+}
+
+#[derive(Debug)]
+enum Error {
+    InvalidPath(&'static str),
+    Read(std::io::Error),
+    Deserialize(serde_json::Error),
+    Serialize(serde_json::Error),
+    Simulation(aurora_launchpad_simulator::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidPath(file) => write!(f, "Missing path to the {file} file"),
+            Self::Read(e) => write!(f, "Error reading the file: {e}"),
+            Self::Deserialize(e) => write!(f, "Error deserializing the config file: {e}"),
+            Self::Serialize(e) => write!(f, "Error serializing the report: {e}"),
+            Self::Simulation(e) => write!(f, "{e}"),
+        }
+    }
+}
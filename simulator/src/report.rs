@@ -0,0 +1,83 @@
+use aurora_launchpad_types::config::LaunchpadStatus;
+use aurora_launchpad_types::{IntentsAccount, date_time};
+use near_sdk::json_types::U128;
+use near_sdk::near;
+
+use crate::Event;
+
+/// The result of the simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct Report {
+    /// The status of the sale after the end of the sale and TGE.
+    pub status: LaunchpadStatus,
+    /// The total number of deposited tokens.
+    pub total_deposited: U128,
+    /// The total number of sold tokens (the sum of weights for the price discovery).
+    pub total_sold_tokens: U128,
+    /// The number of participants with deposits.
+    pub participants_count: u64,
+    /// The outcomes of the events in the order of the timeline.
+    pub events: Vec<EventOutcome>,
+    /// The state of every participant after the sale.
+    pub accounts: Vec<AccountReport>,
+    /// The fill levels of the discount phases.
+    pub phases: Vec<PhaseReport>,
+}
+
+/// The outcome of the event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct EventOutcome {
+    /// The event of the timeline.
+    pub event: Event,
+    /// The number of deposit tokens refunded to the participant.
+    pub refund: U128,
+    /// The reason why the contract would reject the event, if any.
+    pub error: Option<String>,
+}
+
+/// The state of the participant after the sale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct AccountReport {
+    /// The intents account of the participant.
+    pub account: IntentsAccount,
+    /// The number of deposited tokens.
+    pub deposited: U128,
+    /// The weight of the deposit.
+    pub weight: U128,
+    /// The number of deposit tokens refunded during the sale.
+    pub refunded: U128,
+    /// The number of deposit tokens withdrawn by the participant.
+    pub withdrawn: U128,
+    /// The number of sale tokens allocated to the participant. Zero if the sale failed.
+    pub allocation: U128,
+    /// The number of sale tokens available for claim over time.
+    pub claim_schedule: Vec<ClaimPoint>,
+}
+
+/// The number of sale tokens available for claim at the given time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct ClaimPoint {
+    #[serde(
+        deserialize_with = "date_time::deserialize",
+        serialize_with = "date_time::serialize"
+    )]
+    pub timestamp: u64,
+    pub available: U128,
+}
+
+/// The fill level of the discount phase. The sold tokens are tracked for the fixed price only,
+/// since the phases have no limits for the price discovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[near(serializers = [json])]
+pub struct PhaseReport {
+    /// ID of the phase.
+    pub id: u16,
+    /// The number of sale tokens sold with discount in the phase.
+    pub sale_tokens: U128,
+    /// The limit of sale tokens for the phase, if any.
+    pub limit: Option<U128>,
+}
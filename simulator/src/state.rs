@@ -0,0 +1,103 @@
+use aurora_launchpad_contract::DiscountPhases;
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::discount::DiscountParams;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The state of the discount phases kept in memory.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryDiscountState {
+    phases: BTreeMap<u16, PhaseState>,
+    linked_phases: HashMap<u16, HashSet<u16>>,
+}
+
+#[derive(Debug, Default, Clone)]
+struct PhaseState {
+    /// The total number of sold tokens with discount in the phase.
+    total_sale_tokens: u128,
+    /// Limit of sale tokens for this phase.
+    limit_per_phase: Option<u128>,
+    /// The number of sold tokens with discount per account in the phase.
+    account_sale_tokens: HashMap<IntentsAccount, u128>,
+    /// The accounts allowed to participate in the phase. If None, then any account can participate.
+    whitelist: Option<HashSet<IntentsAccount>>,
+}
+
+impl MemoryDiscountState {
+    #[must_use]
+    pub fn init(discounts: &DiscountParams) -> Self {
+        let phases = discounts
+            .phases
+            .iter()
+            .map(|phase| {
+                let state = PhaseState {
+                    limit_per_phase: phase.phase_sale_limit.map(|limit| limit.0),
+                    whitelist: phase.whitelist.clone(),
+                    ..Default::default()
+                };
+
+                (phase.id, state)
+            })
+            .collect();
+
+        Self {
+            phases,
+            linked_phases: discounts.get_all_linked_phases(),
+        }
+    }
+}
+
+impl DiscountPhases for MemoryDiscountState {
+    fn phase_ids(&self) -> Vec<u16> {
+        self.phases.keys().copied().collect()
+    }
+
+    fn has_phase(&self, phase_id: u16) -> bool {
+        self.phases.contains_key(&phase_id)
+    }
+
+    fn total_sale_tokens(&self, phase_id: u16) -> u128 {
+        self.phases
+            .get(&phase_id)
+            .map_or(0, |phase| phase.total_sale_tokens)
+    }
+
+    fn limit_per_phase(&self, phase_id: u16) -> Option<u128> {
+        self.phases
+            .get(&phase_id)
+            .and_then(|phase| phase.limit_per_phase)
+    }
+
+    fn account_sale_tokens(&self, phase_id: u16, account: &IntentsAccount) -> Option<u128> {
+        self.phases
+            .get(&phase_id)
+            .and_then(|phase| phase.account_sale_tokens.get(account))
+            .copied()
+    }
+
+    fn is_account_allowed(&self, phase_id: u16, account: &IntentsAccount) -> bool {
+        self.phases.get(&phase_id).is_some_and(|phase| {
+            phase
+                .whitelist
+                .as_ref()
+                .is_none_or(|list| list.contains(account))
+        })
+    }
+
+    fn is_phases_linked(&self, phase_id: u16, linked_id: u16) -> bool {
+        self.linked_phases
+            .get(&phase_id)
+            .is_some_and(|phases| phases.contains(&linked_id))
+    }
+
+    fn add_sale_tokens(&mut self, phase_id: u16, account: &IntentsAccount, sale_tokens: u128) {
+        if let Some(phase) = self.phases.get_mut(&phase_id) {
+            let sale_tokens_per_user = phase
+                .account_sale_tokens
+                .entry(account.clone())
+                .or_insert(0);
+
+            *sale_tokens_per_user = sale_tokens_per_user.saturating_add(sale_tokens);
+            phase.total_sale_tokens = phase.total_sale_tokens.saturating_add(sale_tokens);
+        }
+    }
+}
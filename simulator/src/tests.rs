@@ -0,0 +1,264 @@
+use aurora_launchpad_types::IntentsAccount;
+use aurora_launchpad_types::config::{
+    DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig, LaunchpadStatus,
+    Mechanics, SaleToken, VestingSchedule, VestingScheme,
+};
+use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
+use aurora_launchpad_types::duration::Duration;
+use near_sdk::json_types::U128;
+
+use crate::{Action, Error, Event, Simulator, timeline_from_csv, timeline_from_json};
+
+const START: u64 = 1_000_000_000_000;
+const SECOND: u64 = 1_000_000_000;
+const END: u64 = START + 100 * SECOND;
+
+fn config(mechanics: Mechanics) -> LaunchpadConfig {
+    LaunchpadConfig {
+        deposit_token: DepositToken::Nep141("wrap.near".parse().unwrap()),
+        min_deposit: U128(10),
+        sale_token: SaleToken::Nep141("sale.token.near".parse().unwrap()),
+        intents_account_id: "intents.near".parse().unwrap(),
+        start_date: START,
+        end_date: END,
+        tge: None,
+        soft_cap: U128(500),
+        mechanics,
+        sale_amount: U128(1_000),
+        total_sale_amount: U128(1_100),
+        vesting_schedule: None,
+        distribution_proportions: DistributionProportions {
+            solver_account_id: DistributionAccount::new_near("solver.near").unwrap(),
+            solver_allocation: U128(100),
+            stakeholder_proportions: vec![],
+            deposits: None,
+            liquidity: None,
+        },
+        discounts: None,
+        bundled_sale_tokens: vec![],
+        allow_unreachable_soft_cap: false,
+    }
+}
+
+const fn fixed_price() -> Mechanics {
+    Mechanics::FixedPrice {
+        deposit_token: U128(1),
+        sale_token: U128(1),
+    }
+}
+
+fn account(name: &str) -> IntentsAccount {
+    IntentsAccount::try_from(name).unwrap()
+}
+
+fn event(timestamp: u64, name: &str, action: Action, amount: u128) -> Event {
+    Event {
+        timestamp,
+        account: account(name),
+        action,
+        amount: U128(amount),
+    }
+}
+
+#[test]
+fn test_fixed_price_sold_out_with_refund() {
+    let timeline = [
+        event(START - SECOND, "alice.near", Action::Deposit, 100),
+        event(START, "alice.near", Action::Deposit, 600),
+        event(START + SECOND, "bob.near", Action::Deposit, 5),
+        event(START + SECOND, "bob.near", Action::Deposit, 500),
+        event(START + 2 * SECOND, "carol.near", Action::Deposit, 100),
+    ];
+    let report = Simulator::run(config(fixed_price()), &timeline).unwrap();
+
+    assert_eq!(report.status, LaunchpadStatus::Success);
+    assert_eq!(report.total_deposited, U128(1_000));
+    assert_eq!(report.total_sold_tokens, U128(1_000));
+    assert_eq!(report.participants_count, 2);
+
+    let errors = report
+        .events
+        .iter()
+        .map(|outcome| outcome.error.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        errors,
+        [
+            Some("Launchpad is not ongoing"),
+            None,
+            Some("Deposit amount is too low"),
+            None,
+            Some("Launchpad is not ongoing"),
+        ]
+    );
+    assert_eq!(report.events[3].refund, U128(100));
+
+    let alice = &report.accounts[0];
+    assert_eq!(alice.account, account("alice.near"));
+    assert_eq!(alice.allocation, U128(600));
+
+    let bob = &report.accounts[1];
+    assert_eq!(bob.deposited, U128(400));
+    assert_eq!(bob.refunded, U128(100));
+    assert_eq!(bob.allocation, U128(400));
+}
+
+#[test]
+fn test_fixed_price_phase_limit() {
+    let mut config = config(fixed_price());
+    config.discounts = Some(DiscountParams {
+        phases: vec![DiscountPhase {
+            id: 1,
+            start_time: START,
+            end_time: START + 10 * SECOND,
+            percentage: 1_000,
+            phase_sale_limit: Some(U128(100)),
+            ..Default::default()
+        }],
+        public_sale_start_time: None,
+    });
+    let timeline = [
+        event(START, "alice.near", Action::Deposit, 200),
+        event(START + SECOND, "bob.near", Action::Deposit, 100),
+    ];
+    let report = Simulator::run(config, &timeline).unwrap();
+
+    assert_eq!(report.phases.len(), 1);
+    assert_eq!(report.phases[0].id, 1);
+    assert_eq!(report.phases[0].sale_tokens, U128(100));
+    assert_eq!(report.phases[0].limit, Some(U128(100)));
+    // The discount is applied to the first 100 sale tokens only.
+    assert!(report.accounts[0].weight.0 > 200);
+    assert!(report.accounts[0].weight.0 < 220);
+}
+
+#[test]
+fn test_price_discovery_withdraw() {
+    let timeline = [
+        event(START, "alice.near", Action::Deposit, 300),
+        event(START + SECOND, "bob.near", Action::Deposit, 300),
+        event(START + 2 * SECOND, "alice.near", Action::Withdraw, 100),
+        event(START + 3 * SECOND, "bob.near", Action::Withdraw, 400),
+        event(START + 4 * SECOND, "carol.near", Action::Withdraw, 10),
+    ];
+    let report = Simulator::run(config(Mechanics::PriceDiscovery), &timeline).unwrap();
+
+    assert_eq!(report.status, LaunchpadStatus::Success);
+    assert_eq!(report.total_deposited, U128(500));
+    assert_eq!(
+        report.events[3].error.as_deref(),
+        Some("Withdraw amount is greater than the deposit amount")
+    );
+    assert_eq!(
+        report.events[4].error.as_deref(),
+        Some("No deposit for the intents account")
+    );
+
+    let alice = &report.accounts[0];
+    assert_eq!(alice.deposited, U128(200));
+    assert_eq!(alice.withdrawn, U128(100));
+    assert_eq!(alice.allocation, U128(400));
+    assert_eq!(report.accounts[1].allocation, U128(600));
+}
+
+#[test]
+fn test_failed_sale() {
+    let timeline = [
+        event(START, "alice.near", Action::Deposit, 100),
+        event(START + SECOND, "alice.near", Action::Withdraw, 100),
+        event(END, "alice.near", Action::Withdraw, 100),
+    ];
+    let report = Simulator::run(config(fixed_price()), &timeline).unwrap();
+
+    assert_eq!(report.status, LaunchpadStatus::Failed);
+    assert_eq!(
+        report.events[1].error.as_deref(),
+        Some("Withdraw is not allowed")
+    );
+    assert_eq!(report.events[2].error, None);
+    assert_eq!(report.total_deposited, U128(0));
+    assert_eq!(report.participants_count, 0);
+    assert_eq!(report.accounts[0].withdrawn, U128(100));
+    assert_eq!(report.accounts[0].allocation, U128(0));
+    assert!(report.accounts[0].claim_schedule.is_empty());
+}
+
+#[test]
+fn test_claim_schedule_with_vesting() {
+    let mut config = config(fixed_price());
+    config.vesting_schedule = Some(VestingSchedule {
+        cliff_period: Duration::from_secs(10),
+        vesting_period: Duration::from_secs(50),
+        instant_claim_percentage: None,
+        vesting_scheme: VestingScheme::AfterCliff,
+    });
+    let timeline = [event(START, "alice.near", Action::Deposit, 800)];
+    let report = Simulator::run(config, &timeline).unwrap();
+    let schedule = &report.accounts[0].claim_schedule;
+
+    assert_eq!(schedule.len(), 6);
+    assert_eq!(schedule[0].timestamp, END);
+    assert_eq!(schedule[0].available, U128(0));
+    assert_eq!(schedule[1].timestamp, END + 10 * SECOND);
+    assert_eq!(schedule[5].timestamp, END + 50 * SECOND);
+    assert_eq!(schedule[5].available, U128(800));
+    assert!(
+        schedule
+            .windows(2)
+            .all(|points| points[0].available.0 <= points[1].available.0)
+    );
+}
+
+#[test]
+fn test_unordered_timeline() {
+    let timeline = [
+        event(START + SECOND, "alice.near", Action::Deposit, 100),
+        event(START, "bob.near", Action::Deposit, 100),
+    ];
+    let result = Simulator::run(config(fixed_price()), &timeline);
+
+    assert!(matches!(result, Err(Error::UnorderedTimeline { index: 1 })));
+}
+
+#[test]
+fn test_invalid_config() {
+    let mut config = config(fixed_price());
+    config.total_sale_amount = U128(1_000);
+
+    assert!(matches!(
+        Simulator::new(config),
+        Err(Error::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_timeline_from_csv() {
+    let csv = "timestamp,account,action,amount\n\
+               # The first deposit\n\
+               1000000000000,alice.near,deposit,100\n\
+               \n\
+               1970-01-01T00:16:41Z, bob.near, withdraw, 50\n";
+    let timeline = timeline_from_csv(csv).unwrap();
+
+    assert_eq!(
+        timeline,
+        [
+            event(START, "alice.near", Action::Deposit, 100),
+            event(START + SECOND, "bob.near", Action::Withdraw, 50),
+        ]
+    );
+
+    let result = timeline_from_csv("1000000000000,alice.near,buy,100");
+    assert!(matches!(result, Err(Error::Csv { line: 1, .. })));
+}
+
+#[test]
+fn test_timeline_from_json() {
+    let json = r#"[
+        {"timestamp": "1970-01-01T00:16:40Z", "account": "alice.near", "action": "deposit", "amount": "100"}
+    ]"#;
+    let timeline = timeline_from_json(json).unwrap();
+
+    assert_eq!(timeline, [event(START, "alice.near", Action::Deposit, 100)]);
+    assert!(matches!(timeline_from_json("{}"), Err(Error::Json(_))));
+}
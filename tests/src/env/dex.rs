@@ -0,0 +1,33 @@
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::json;
+use near_workspaces::AccountId;
+
+pub trait Dex {
+    async fn get_pool_amounts(&self, pool_id: u64) -> anyhow::Result<Vec<u128>>;
+    async fn get_pool_shares(&self, pool_id: u64, account_id: &AccountId) -> anyhow::Result<u128>;
+}
+
+impl Dex for near_workspaces::Contract {
+    async fn get_pool_amounts(&self, pool_id: u64) -> anyhow::Result<Vec<u128>> {
+        let pool = self
+            .view("get_pool")
+            .args_json(json!({ "pool_id": pool_id }))
+            .await?
+            .json::<near_sdk::serde_json::Value>()?;
+        let amounts = near_sdk::serde_json::from_value::<Vec<U128>>(pool["amounts"].clone())?;
+
+        Ok(amounts.into_iter().map(|amount| amount.0).collect())
+    }
+
+    async fn get_pool_shares(&self, pool_id: u64, account_id: &AccountId) -> anyhow::Result<u128> {
+        self.view("get_pool_shares")
+            .args_json(json!({
+                "pool_id": pool_id,
+                "account_id": account_id,
+            }))
+            .await?
+            .json::<U128>()
+            .map(|shares| shares.0)
+            .map_err(Into::into)
+    }
+}
@@ -2,6 +2,7 @@ use crate::env::defuse::DefuseSigner;
 use crate::tests::NANOSECONDS_PER_SECOND;
 use aurora_launchpad_types::config::{
     DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig, Mechanics,
+    SaleToken,
 };
 use near_sdk::json_types::U128;
 use near_sdk::serde_json::json;
@@ -14,6 +15,7 @@ use tokio::sync::{Mutex, OnceCell};
 
 pub mod alt_defuse;
 pub mod defuse;
+pub mod dex;
 pub mod fungible_token;
 pub mod mt_token;
 pub mod rpc;
@@ -24,6 +26,7 @@ const INIT_TOTAL_SUPPLY: u128 = 1_000_000_000 * 10u128.pow(18);
 static FACTORY_CODE: OnceCell<Vec<u8>> = OnceCell::const_new();
 static NEP_141_CODE: OnceCell<Vec<u8>> = OnceCell::const_new();
 static ALT_DEFUSE_CODE: OnceCell<Vec<u8>> = OnceCell::const_new();
+static MOCK_DEX_CODE: OnceCell<Vec<u8>> = OnceCell::const_new();
 
 pub fn validate_result(
     result: ExecutionFinalResult,
@@ -92,6 +95,15 @@ impl Env {
         &self,
         config: &LaunchpadConfig,
         admin: Option<&AccountId>,
+    ) -> anyhow::Result<Contract> {
+        self.create_launchpad_with_name(config, admin, None).await
+    }
+
+    pub async fn create_launchpad_with_name(
+        &self,
+        config: &LaunchpadConfig,
+        admin: Option<&AccountId>,
+        name: Option<&str>,
     ) -> anyhow::Result<Contract> {
         self.config.lock().await.replace(config.clone());
 
@@ -100,7 +112,8 @@ impl Env {
             .call("create_launchpad")
             .args_json(json!({
                 "config": config,
-                "admin": admin
+                "admin": admin,
+                "name": name
             }))
             .deposit(CREATE_LAUNCHPAD_DEPOSIT)
             .max_gas()
@@ -118,6 +131,26 @@ impl Env {
         ))
     }
 
+    pub async fn grant_factory_role(
+        &self,
+        role: &str,
+        account_id: &AccountId,
+    ) -> anyhow::Result<()> {
+        let _result = self
+            .factory
+            .call("acl_grant_role")
+            .args_json(json!({
+                "role": role,
+                "account_id": account_id,
+            }))
+            .max_gas()
+            .transact()
+            .await
+            .and_then(validate_result)?;
+
+        Ok(())
+    }
+
     pub fn alice(&self) -> &Account {
         &self.users[0]
     }
@@ -130,12 +163,22 @@ impl Env {
         &self.users[2]
     }
 
+    pub async fn create_token(&self, token: &str) -> Contract {
+        deploy_nep141_token(&self.master_account, token)
+            .await
+            .unwrap()
+    }
+
     pub async fn alt_defuse(&self) -> Contract {
         deploy_alt_defuse(&self.master_account, "alt-defuse")
             .await
             .unwrap()
     }
 
+    pub async fn create_dex(&self) -> Contract {
+        deploy_mock_dex(&self.master_account, "dex").await.unwrap()
+    }
+
     pub async fn wait_for_sale_finish(&self, config: &LaunchpadConfig) {
         while config.end_date > self.worker.view_block().await.unwrap().timestamp() {
             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
@@ -154,7 +197,7 @@ impl Env {
         LaunchpadConfig {
             deposit_token: DepositToken::Nep141(self.deposit_ft.id().clone()),
             min_deposit: 100.into(),
-            sale_token_account_id: self.sale_token.id().clone(),
+            sale_token: SaleToken::Nep141(self.sale_token.id().clone()),
             intents_account_id: self.defuse.id().clone(),
             start_date: now,
             end_date: now + 15 * NANOSECONDS_PER_SECOND,
@@ -172,8 +215,12 @@ impl Env {
                 solver_allocation: 0.into(),
                 stakeholder_proportions: vec![],
                 deposits: None,
+                liquidity: None,
             },
             discounts: None,
+            bundled_sale_tokens: vec![],
+            // Tests of failed sales raise the soft cap above the sale amount.
+            allow_unreachable_soft_cap: true,
         }
     }
 
@@ -185,7 +232,7 @@ impl Env {
                 format!("nep141:{}", self.deposit_ft.id()),
             )),
             min_deposit: 100.into(),
-            sale_token_account_id: self.sale_token.id().clone(),
+            sale_token: SaleToken::Nep141(self.sale_token.id().clone()),
             intents_account_id: self.defuse.id().clone(),
             start_date: now,
             end_date: now + 15 * NANOSECONDS_PER_SECOND,
@@ -203,8 +250,12 @@ impl Env {
                 solver_allocation: 0.into(),
                 stakeholder_proportions: vec![],
                 deposits: None,
+                liquidity: None,
             },
             discounts: None,
+            bundled_sale_tokens: vec![],
+            // Tests of failed sales raise the soft cap above the sale amount.
+            allow_unreachable_soft_cap: true,
         }
     }
 
@@ -323,6 +374,26 @@ pub async fn deploy_alt_defuse(master_account: &Account, name: &str) -> anyhow::
     Ok(contract)
 }
 
+pub async fn deploy_mock_dex(master_account: &Account, name: &str) -> anyhow::Result<Contract> {
+    let contract = deploy_contract(
+        name,
+        MOCK_DEX_CODE
+            .get_or_try_init(|| build_contract("../res/mock-dex/Cargo.toml"))
+            .await?,
+        master_account,
+        NearToken::from_near(3),
+    )
+    .await?;
+    let _result = contract
+        .call("new")
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    Ok(contract)
+}
+
 async fn deploy_nep245_token(master_account: &Account, token: &str) -> anyhow::Result<Contract> {
     // Use defuse contract as a NEP-245 token since it implements the NEP-245 interface.
     let defuse_wasm = tokio::fs::read("../res/defuse.wasm").await?;
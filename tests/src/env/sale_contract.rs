@@ -5,6 +5,7 @@ use aurora_launchpad_types::IntentsAccount;
 use aurora_launchpad_types::admin_withdraw::{AdminWithdrawDirection, WithdrawalToken};
 use aurora_launchpad_types::config::{
     DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig, Mechanics,
+    SaleToken,
 };
 use chrono::{DateTime, Utc};
 use defuse::core::Deadline;
@@ -35,6 +36,7 @@ pub trait SaleContract {
     async fn get_soft_cap(&self) -> anyhow::Result<u128>;
     async fn get_sale_amount(&self) -> anyhow::Result<u128>;
     async fn get_sale_token_account_id(&self) -> anyhow::Result<AccountId>;
+    async fn get_sale_token(&self) -> anyhow::Result<SaleToken>;
     async fn get_solver_allocation(&self) -> anyhow::Result<u128>;
     async fn get_config(&self) -> anyhow::Result<LaunchpadConfig>;
     async fn get_mechanics(&self) -> anyhow::Result<Mechanics>;
@@ -102,6 +104,15 @@ pub trait SaleContract {
     ) -> anyhow::Result<Option<Vec<IntentsAccount>>>;
     async fn get_tge_timestamp(&self) -> anyhow::Result<Option<u64>>;
     async fn get_tge(&self) -> anyhow::Result<Option<DateTime<Utc>>>;
+    async fn get_unregistered_recipients(&self) -> anyhow::Result<Vec<AccountId>>;
+    async fn get_storage_deposit_budget(&self) -> anyhow::Result<Option<u128>>;
+    async fn get_funded_sale_tokens(&self) -> anyhow::Result<Vec<SaleToken>>;
+    async fn is_liquidity_seeded(&self) -> anyhow::Result<bool>;
+    async fn get_distribution_progress(&self) -> anyhow::Result<Vec<(DistributionAccount, u128)>>;
+    async fn get_bundled_allocations(
+        &self,
+        account: impl Into<IntentsAccount>,
+    ) -> anyhow::Result<Vec<(SaleToken, u128)>>;
 }
 
 pub trait Locker {
@@ -186,7 +197,17 @@ pub trait Claim {
 
 pub trait Distribute {
     async fn distribute_sale_tokens(&self, launchpad_account: &AccountId) -> anyhow::Result<()>;
+    async fn distribute_sale_tokens_in_batch(
+        &self,
+        launchpad_account: &AccountId,
+        batch_size: u8,
+    ) -> anyhow::Result<()>;
     async fn distribute_deposit_tokens(&self, launchpad_account: &AccountId) -> anyhow::Result<()>;
+    async fn seed_liquidity(
+        &self,
+        launchpad_account: &AccountId,
+        deposit: NearToken,
+    ) -> anyhow::Result<()>;
 }
 
 pub trait AdminWithdraw {
@@ -207,6 +228,15 @@ pub trait TGEUpdate {
     ) -> anyhow::Result<()>;
 }
 
+pub trait StorageDepositManage {
+    async fn set_storage_deposit_settings(
+        &self,
+        launchpad_account: &AccountId,
+        deposit: NearToken,
+        budget: Option<NearToken>,
+    ) -> anyhow::Result<()>;
+}
+
 pub trait WhiteListManage {
     async fn extend_whitelist_for_discount_phase(
         &self,
@@ -312,6 +342,13 @@ impl SaleContract for Contract {
             .map_err(Into::into)
     }
 
+    async fn get_sale_token(&self) -> anyhow::Result<SaleToken> {
+        self.view("get_sale_token")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
     async fn get_solver_allocation(&self) -> anyhow::Result<u128> {
         self.view("get_solver_allocation")
             .await?
@@ -583,6 +620,75 @@ impl SaleContract for Contract {
     async fn get_tge(&self) -> anyhow::Result<Option<DateTime<Utc>>> {
         self.view("get_tge").await?.json().map_err(Into::into)
     }
+
+    async fn get_unregistered_recipients(&self) -> anyhow::Result<Vec<AccountId>> {
+        self.view("get_unregistered_recipients")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn get_storage_deposit_budget(&self) -> anyhow::Result<Option<u128>> {
+        let settings = self
+            .view("get_storage_deposit_settings")
+            .await?
+            .json::<near_sdk::serde_json::Value>()?;
+        let budget: Option<U128> = near_sdk::serde_json::from_value(settings["budget"].clone())?;
+
+        Ok(budget.map(|budget| budget.0))
+    }
+
+    async fn get_funded_sale_tokens(&self) -> anyhow::Result<Vec<SaleToken>> {
+        self.view("get_funded_sale_tokens")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn is_liquidity_seeded(&self) -> anyhow::Result<bool> {
+        self.view("is_liquidity_seeded")
+            .await?
+            .json()
+            .map_err(Into::into)
+    }
+
+    async fn get_distribution_progress(&self) -> anyhow::Result<Vec<(DistributionAccount, u128)>> {
+        let result = self.view("get_distribution_progress").await?;
+
+        result
+            .json::<Vec<near_sdk::serde_json::Value>>()?
+            .into_iter()
+            .map(|progress| {
+                let account = near_sdk::serde_json::from_value(progress["account"].clone())?;
+                let distributed: U128 =
+                    near_sdk::serde_json::from_value(progress["distributed"].clone())?;
+                Ok((account, distributed.0))
+            })
+            .collect()
+    }
+
+    async fn get_bundled_allocations(
+        &self,
+        account: impl Into<IntentsAccount>,
+    ) -> anyhow::Result<Vec<(SaleToken, u128)>> {
+        let result = self
+            .view("get_bundled_allocations")
+            .args_json(json!({
+                "account": account.into(),
+            }))
+            .await?;
+
+        result
+            .json::<Vec<near_sdk::serde_json::Value>>()?
+            .into_iter()
+            .map(|allocation| {
+                let token = near_sdk::serde_json::from_value(allocation["token"].clone())?;
+                let amount: U128 =
+                    near_sdk::serde_json::from_value(allocation["allocation"].clone())?;
+                Ok((token, amount.0))
+            })
+            .collect()
+    }
 }
 
 pub trait Deposit {
@@ -772,6 +878,25 @@ impl Distribute for Account {
         Ok(())
     }
 
+    async fn distribute_sale_tokens_in_batch(
+        &self,
+        launchpad_account: &AccountId,
+        batch_size: u8,
+    ) -> anyhow::Result<()> {
+        let _result = self
+            .call(launchpad_account, "distribute_sale_tokens")
+            .args_json(json!({
+                "batch_size": batch_size,
+            }))
+            .deposit(ONE_YOCTO)
+            .max_gas()
+            .transact()
+            .await
+            .and_then(validate_result)?;
+
+        Ok(())
+    }
+
     async fn distribute_deposit_tokens(&self, launchpad_account: &AccountId) -> anyhow::Result<()> {
         let _result = self
             .call(launchpad_account, "distribute_deposit_tokens")
@@ -783,6 +908,22 @@ impl Distribute for Account {
 
         Ok(())
     }
+
+    async fn seed_liquidity(
+        &self,
+        launchpad_account: &AccountId,
+        deposit: NearToken,
+    ) -> anyhow::Result<()> {
+        let _result = self
+            .call(launchpad_account, "seed_liquidity")
+            .deposit(deposit)
+            .max_gas()
+            .transact()
+            .await
+            .and_then(validate_result)?;
+
+        Ok(())
+    }
 }
 
 impl Withdraw for Account {
@@ -960,6 +1101,27 @@ impl Locker for Account {
     }
 }
 
+impl StorageDepositManage for Account {
+    async fn set_storage_deposit_settings(
+        &self,
+        launchpad_account: &AccountId,
+        deposit: NearToken,
+        budget: Option<NearToken>,
+    ) -> anyhow::Result<()> {
+        let _result = self
+            .call(launchpad_account, "set_storage_deposit_settings")
+            .args_json(json!({
+                "deposit": U128(deposit.as_yoctonear()),
+                "budget": budget.map(|budget| U128(budget.as_yoctonear())),
+            }))
+            .transact()
+            .await
+            .and_then(validate_result)?;
+
+        Ok(())
+    }
+}
+
 impl WhiteListManage for Account {
     async fn extend_whitelist_for_discount_phase(
         &self,
@@ -31,7 +31,7 @@ async fn successful_withdraw_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -54,7 +54,7 @@ async fn successful_withdraw_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -74,7 +74,7 @@ async fn successful_withdraw_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -213,7 +213,7 @@ async fn successful_withdraw_deposited_nep_141_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -328,7 +328,7 @@ async fn successful_withdraw_deposited_nep_245_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -387,7 +387,7 @@ async fn fails_unauthorized_withdraw_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -490,7 +490,7 @@ async fn withdraw_unsold_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -510,7 +510,7 @@ async fn withdraw_unsold_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -535,7 +535,7 @@ async fn withdraw_unsold_sale_tokens() {
         .defuse
         .mt_balance_of(
             tokens_receiver.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -547,7 +547,7 @@ async fn withdraw_unsold_sale_tokens() {
         .defuse
         .mt_balance_of(
             alice.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -654,7 +654,7 @@ async fn test_unsold_calculation_multiple_users_with_discounts() {
         .defuse
         .mt_balance_of(
             admin.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -665,7 +665,7 @@ async fn test_unsold_calculation_multiple_users_with_discounts() {
         .defuse
         .mt_balance_of(
             alice.id(),
-            format!("nep141:{}", config.sale_token_account_id),
+            format!("nep141:{}", config.sale_token.account_id()),
         )
         .await
         .unwrap();
@@ -674,7 +674,10 @@ async fn test_unsold_calculation_multiple_users_with_discounts() {
     bob.claim_to_intents(lp.id(), bob.id()).await.unwrap();
     let bob_balance = env
         .defuse
-        .mt_balance_of(bob.id(), format!("nep141:{}", config.sale_token_account_id))
+        .mt_balance_of(
+            bob.id(),
+            format!("nep141:{}", config.sale_token.account_id()),
+        )
         .await
         .unwrap();
     assert_eq!(bob_balance, bob_allocation);
@@ -7,6 +7,7 @@ use crate::tests::NANOSECONDS_PER_SECOND;
 use aurora_launchpad_types::admin_withdraw::{AdminWithdrawDirection, WithdrawalToken};
 use aurora_launchpad_types::config::Mechanics;
 use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
+use near_sdk::serde_json::json;
 
 #[tokio::test]
 async fn successful_withdraw_sale_tokens() {
@@ -94,6 +95,78 @@ async fn successful_withdraw_sale_tokens() {
     assert_eq!(balance, 0);
 }
 
+#[tokio::test]
+async fn successful_withdraw_storage_fees() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let admin = env.john();
+    let lp = env
+        .create_launchpad_with_admin(&config, Some(admin.id()))
+        .await
+        .unwrap();
+    let alice = env.alice();
+    let tokens_receiver = env.bob();
+
+    env.sale_token.storage_deposit(lp.id()).await.unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id(), tokens_receiver.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    let result = admin
+        .call(lp.id(), "set_storage_mode")
+        .args_json(json!({"mode": {"DepositTokenFee": "1000"}}))
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+    assert_eq!(lp.get_status().await.unwrap(), "Failed");
+
+    admin
+        .admin_withdraw(
+            lp.id(),
+            WithdrawalToken::StorageFees,
+            AdminWithdrawDirection::Near(tokens_receiver.id().clone()),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let balance = env
+        .deposit_ft
+        .ft_balance_of(tokens_receiver.id())
+        .await
+        .unwrap();
+    assert_eq!(balance, 1_000);
+
+    let err = admin
+        .admin_withdraw(
+            lp.id(),
+            WithdrawalToken::StorageFees,
+            AdminWithdrawDirection::Near(tokens_receiver.id().clone()),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Nothing to withdraw"));
+}
+
 #[tokio::test]
 async fn successful_withdraw_deposited_nep_141_tokens() {
     let env = Env::new().await.unwrap();
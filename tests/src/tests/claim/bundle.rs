@@ -0,0 +1,92 @@
+use crate::env::Env;
+use crate::env::fungible_token::FungibleToken;
+use crate::env::mt_token::MultiToken;
+use crate::env::sale_contract::{Claim, Deposit, SaleContract};
+use aurora_launchpad_types::config::{BundleRatio, BundledSaleToken, SaleToken};
+
+#[tokio::test]
+async fn claims_of_bundled_sale_tokens() {
+    let env = Env::new().await.unwrap();
+    let utility_token = env.create_token("utility").await;
+    let mut config = env.create_config().await;
+    let bundled_token = SaleToken::Nep141(utility_token.id().clone());
+
+    config.bundled_sale_tokens = vec![BundledSaleToken {
+        token: bundled_token.clone(),
+        total_sale_amount: 400_000.into(),
+        ratio: BundleRatio {
+            sale_token: 1.into(),
+            bundled_token: 2.into(),
+        },
+        vesting_schedule: None,
+    }];
+
+    let lp = env.create_launchpad(&config).await.unwrap();
+    let alice = env.alice();
+
+    env.sale_token
+        .storage_deposits(&[lp.id(), env.defuse.id()])
+        .await
+        .unwrap();
+    utility_token
+        .storage_deposits(&[lp.id(), env.defuse.id()])
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    // The launchpad waits for the bundled sale token.
+    assert_eq!(lp.get_status().await.unwrap(), "NotInitialized");
+    assert_eq!(
+        lp.get_funded_sale_tokens().await.unwrap(),
+        vec![config.sale_token.clone()]
+    );
+
+    utility_token
+        .ft_transfer_call(lp.id(), 400_000, "")
+        .await
+        .unwrap();
+
+    assert_eq!(lp.get_status().await.unwrap(), "Ongoing");
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+    assert_eq!(
+        lp.get_bundled_allocations(alice.id()).await.unwrap(),
+        vec![(bundled_token, 200_000)]
+    );
+
+    alice.claim_to_intents(lp.id(), alice.id()).await.unwrap();
+
+    let balance = env
+        .defuse
+        .mt_balance_of(alice.id(), format!("nep141:{}", env.sale_token.id()))
+        .await
+        .unwrap();
+    assert_eq!(balance, 100_000);
+
+    let balance = env
+        .defuse
+        .mt_balance_of(alice.id(), format!("nep141:{}", utility_token.id()))
+        .await
+        .unwrap();
+    assert_eq!(balance, 200_000);
+
+    assert_eq!(lp.get_available_for_claim(alice.id()).await.unwrap(), 0);
+}
@@ -1,2 +1,4 @@
+mod bundle;
 mod intents;
 mod near;
+mod nep245;
@@ -0,0 +1,110 @@
+use crate::env::Env;
+use crate::env::fungible_token::FungibleToken;
+use crate::env::mt_token::MultiToken;
+use crate::env::sale_contract::{Claim, Deposit, Distribute, SaleContract};
+use aurora_launchpad_types::config::{
+    DistributionAccount, DistributionProportions, SaleToken, StakeholderProportion,
+};
+use near_sdk::AccountId;
+
+#[tokio::test]
+async fn claims_and_distribution_of_nep245_sale_token() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    let sale_token_id = format!("nep141:{}", env.sale_token.id());
+    let solver_account_id: AccountId = "solver.near".parse().unwrap();
+    let stakeholder_account_id: AccountId = "stakeholder.near".parse().unwrap();
+
+    config.sale_token = SaleToken::Nep245((env.deposit_mt.id().clone(), sale_token_id.clone()));
+    config.soft_cap = 100_000.into();
+    config.sale_amount = 100_000.into();
+    config.total_sale_amount = 150_000.into();
+    config.distribution_proportions = DistributionProportions {
+        solver_account_id: DistributionAccount::new_near(solver_account_id.clone()).unwrap(),
+        solver_allocation: 20_000.into(),
+        stakeholder_proportions: vec![StakeholderProportion {
+            account: DistributionAccount::new_intents(stakeholder_account_id.clone()).unwrap(),
+            allocation: 30_000.into(),
+            vesting: None,
+        }],
+        deposits: None,
+        liquidity: None,
+    };
+
+    let lp = env.create_launchpad(&config).await.unwrap();
+    let alice = env.alice();
+
+    // The sale tokens are moved to the NEP-245 token contract and transferred to the launchpad.
+    env.sale_token
+        .storage_deposit(env.deposit_mt.id())
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(
+            env.deposit_mt.id(),
+            config.total_sale_amount,
+            env.sale_token.id(),
+        )
+        .await
+        .unwrap();
+    env.sale_token
+        .as_account()
+        .deposit_nep245(
+            lp.id(),
+            env.deposit_mt.id(),
+            env.sale_token.id(),
+            config.total_sale_amount,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(lp.get_status().await.unwrap(), "Ongoing");
+    assert_eq!(lp.get_sale_token().await.unwrap(), config.sale_token);
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+
+    alice.claim_to_intents(lp.id(), alice.id()).await.unwrap();
+    alice.distribute_sale_tokens(lp.id()).await.unwrap();
+
+    let intents_token_id = format!("nep245:{}:{sale_token_id}", env.deposit_mt.id());
+
+    let balance = env
+        .defuse
+        .mt_balance_of(alice.id(), &intents_token_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 100_000);
+
+    let balance = env
+        .defuse
+        .mt_balance_of(&stakeholder_account_id, &intents_token_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 30_000);
+
+    // NEAR recipients receive the tokens with `mt_transfer` on the NEP-245 token contract.
+    let balance = env
+        .deposit_mt
+        .mt_balance_of(&solver_account_id, &sale_token_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 20_000);
+
+    assert_eq!(lp.get_available_for_claim(alice.id()).await.unwrap(), 0);
+}
@@ -5,6 +5,9 @@ use crate::env::sale_contract::{Deposit, SaleContract};
 use crate::tests::NANOSECONDS_PER_SECOND;
 use aurora_launchpad_types::config::{DepositToken, Mechanics};
 use aurora_launchpad_types::discount::{DiscountParams, DiscountPhase};
+use near_sdk::NearToken;
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::{Value, json};
 
 #[tokio::test]
 async fn deposit_without_init() {
@@ -435,7 +438,9 @@ async fn deposits_for_status_not_ongoing() {
     let res = bob
         .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
         .await;
-    assert!(format!("{res:?}").contains("Smart contract panicked: Launchpad is not ongoing"));
+    assert!(
+        format!("{res:?}").contains("Smart contract panicked: ERR_2001: Launchpad is not ongoing")
+    );
 
     let balance = env.deposit_ft.ft_balance_of(alice.id()).await.unwrap();
     assert_eq!(balance, 0);
@@ -696,3 +701,77 @@ async fn deposits_reach_sale_amount() {
     let current_time = env.current_timestamp().await;
     assert!(lp.is_success().await.unwrap() && current_time < config.end_date);
 }
+
+#[tokio::test]
+async fn deposit_requires_storage_registration() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let admin = env.john();
+    let lp = env
+        .create_launchpad_with_admin(&config, Some(admin.id()))
+        .await
+        .unwrap();
+    let alice = env.alice();
+
+    env.sale_token.storage_deposit(lp.id()).await.unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    let result = admin
+        .call(lp.id(), "set_storage_mode")
+        .args_json(json!({"mode": "Required"}))
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let err = alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("The account is not registered"));
+
+    let bounds: Value = lp
+        .view("storage_balance_bounds")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    let min: U128 = near_sdk::serde_json::from_value(bounds["min"].clone()).unwrap();
+
+    let result = alice
+        .call(lp.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(NearToken::from_yoctonear(min.0))
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+    assert_eq!(lp.get_investments(alice.id()).await.unwrap(), Some(100_000));
+
+    // The storage balance is locked until the participation is finished.
+    let balance: Value = lp
+        .view("storage_balance_of")
+        .args_json(json!({"account_id": alice.id()}))
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_eq!(balance, json!({"total": min, "available": "0"}));
+}
@@ -427,7 +427,9 @@ async fn deposits_for_status_not_ongoing() {
     let res = bob
         .deposit_nep245(lp.id(), env.deposit_mt.id(), env.deposit_ft.id(), 100_000)
         .await;
-    assert!(format!("{res:?}").contains("Smart contract panicked: Launchpad is not ongoing"));
+    assert!(
+        format!("{res:?}").contains("Smart contract panicked: ERR_2001: Launchpad is not ongoing")
+    );
 
     let balance = env
         .deposit_mt
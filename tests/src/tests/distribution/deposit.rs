@@ -1,5 +1,7 @@
 use aurora_launchpad_types::admin_withdraw::{AdminWithdrawDirection, WithdrawalToken};
-use aurora_launchpad_types::config::DepositDistributionProportion;
+use aurora_launchpad_types::config::{
+    DepositDistributionProportion, DepositRecipient, DistributionAccount,
+};
 
 use crate::env::alt_defuse::AltDefuse;
 use crate::env::fungible_token::FungibleToken;
@@ -22,8 +24,10 @@ async fn successful_withdraw_deposited_nep_141_tokens_with_designation() {
 
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9900,
-        fee_account: alice.id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(alice.id()).unwrap(),
+            percentage: 100,
+        }],
     });
     let admin = env.john();
     let lp = env
@@ -88,6 +92,96 @@ async fn successful_withdraw_deposited_nep_141_tokens_with_designation() {
     assert_eq!(balance, 2_000);
 }
 
+#[tokio::test]
+async fn successful_distribution_of_nep_141_deposits_to_multiple_recipients() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+
+    let alice = env.alice();
+    let bob = env.bob();
+    let john = env.john();
+    let solver_account_id = config
+        .distribution_proportions
+        .solver_account_id
+        .as_account_id();
+
+    config.distribution_proportions.deposits = Some(DepositDistributionProportion {
+        solver_percentage: 7000,
+        recipients: vec![
+            DepositRecipient {
+                account: DistributionAccount::new_intents(alice.id()).unwrap(),
+                percentage: 1000,
+            },
+            DepositRecipient {
+                account: DistributionAccount::new_near(bob.id()).unwrap(),
+                percentage: 1500,
+            },
+            DepositRecipient {
+                account: DistributionAccount::new_intents(john.id()).unwrap(),
+                percentage: 500,
+            },
+        ],
+    });
+    let lp = env.create_launchpad(&config).await.unwrap();
+
+    env.sale_token.storage_deposit(lp.id()).await.unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id(), bob.id(), env.defuse.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+    env.deposit_ft.ft_transfer(bob.id(), 100_000).await.unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+    bob.deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+
+    alice.distribute_deposit_tokens(lp.id()).await.unwrap();
+
+    let token_id = format!("nep141:{}", env.deposit_ft.id());
+
+    for (account_id, expected) in [
+        (&solver_account_id, 140_000),
+        (alice.id(), 20_000),
+        (john.id(), 10_000),
+    ] {
+        let balance = env
+            .defuse
+            .mt_balance_of(account_id, &token_id)
+            .await
+            .unwrap();
+        assert_eq!(balance, expected);
+    }
+
+    // The NEAR recipient receives the tokens on its NEAR account.
+    assert_eq!(
+        env.deposit_ft.ft_balance_of(bob.id()).await.unwrap(),
+        30_000
+    );
+    assert_eq!(env.deposit_ft.ft_balance_of(lp.id()).await.unwrap(), 0);
+
+    let err = alice.distribute_deposit_tokens(lp.id()).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Deposit tokens have been already distributed")
+    );
+}
+
 #[tokio::test]
 async fn successful_withdraw_deposits_nep_141_tokens() {
     let env = Env::new().await.unwrap();
@@ -102,8 +196,10 @@ async fn successful_withdraw_deposits_nep_141_tokens() {
 
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9900,
-        fee_account: alice.id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(alice.id()).unwrap(),
+            percentage: 100,
+        }],
     });
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -178,8 +274,10 @@ async fn successful_withdraw_deposited_nep_245_tokens_with_designation() {
 
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9900,
-        fee_account: env.alice().id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(env.alice().id()).unwrap(),
+            percentage: 100,
+        }],
     });
     let lp = env.create_launchpad(&config).await.unwrap();
 
@@ -257,8 +355,10 @@ async fn successful_withdraw_deposits_nep245_tokens() {
 
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9000,
-        fee_account: env.alice().id().into(),
-        fee_percentage: 1000,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(env.alice().id()).unwrap(),
+            percentage: 1000,
+        }],
     });
     let lp = env.create_launchpad(&config).await.unwrap();
 
@@ -339,8 +439,10 @@ async fn successful_withdraw_deposits_nep141_tokens_with_refund() {
     config.intents_account_id = alt_defuse.id().clone();
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9000,
-        fee_account: alice.id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(alice.id()).unwrap(),
+            percentage: 100,
+        }],
     });
     let lp = env
         .create_launchpad_with_admin(&config, Some(admin.id()))
@@ -487,8 +589,10 @@ async fn successful_withdraw_deposits_nep245_tokens_with_refund() {
     config.intents_account_id = alt_defuse.id().clone();
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9000,
-        fee_account: alice.id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(alice.id()).unwrap(),
+            percentage: 100,
+        }],
     });
     let lp = env
         .create_launchpad_with_admin(&config, Some(admin.id()))
@@ -651,8 +755,10 @@ async fn test_reentrancy_protection() {
 
     config.distribution_proportions.deposits = Some(DepositDistributionProportion {
         solver_percentage: 9900,
-        fee_account: alice.id().into(),
-        fee_percentage: 100,
+        recipients: vec![DepositRecipient {
+            account: DistributionAccount::new_intents(alice.id()).unwrap(),
+            percentage: 100,
+        }],
     });
     let admin = env.john();
     let lp = env
@@ -0,0 +1,119 @@
+use aurora_launchpad_types::config::LiquidityProportion;
+use near_sdk::NearToken;
+
+use crate::env::Env;
+use crate::env::dex::Dex;
+use crate::env::fungible_token::FungibleToken;
+use crate::env::mt_token::MultiToken;
+use crate::env::sale_contract::{Deposit, Distribute, SaleContract};
+
+const NEAR_FOR_POOL_CREATION: NearToken = NearToken::from_millinear(200);
+const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
+
+#[tokio::test]
+async fn successful_liquidity_seeding() {
+    let env = Env::new().await.unwrap();
+    let dex = env.create_dex().await;
+    let mut config = env.create_config().await;
+
+    let alice = env.alice();
+    let bob = env.bob();
+    let solver_account_id = config
+        .distribution_proportions
+        .solver_account_id
+        .as_account_id();
+
+    config.distribution_proportions.liquidity = Some(LiquidityProportion {
+        dex_account_id: dex.id().clone(),
+        pool_fee: 30,
+        deposit_percentage: 2000,
+        sale_allocation: 50_000.into(),
+        shares_receiver_id: Some(bob.id().clone()),
+    });
+    config.total_sale_amount = 250_000.into();
+
+    let lp = env.create_launchpad(&config).await.unwrap();
+
+    env.sale_token.storage_deposit(lp.id()).await.unwrap();
+    env.sale_token.storage_deposit(dex.id()).await.unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id(), bob.id(), env.defuse.id(), dex.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+    env.deposit_ft.ft_transfer(bob.id(), 100_000).await.unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+    bob.deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    let err = alice
+        .seed_liquidity(lp.id(), NEAR_FOR_POOL_CREATION)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Liquidity could be seeded after success only")
+    );
+
+    env.wait_for_sale_finish(&config).await;
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+    assert!(!lp.is_liquidity_seeded().await.unwrap());
+
+    // The excess of the attached NEAR is refunded.
+    let balance_before = alice.view_account().await.unwrap().balance;
+    alice
+        .seed_liquidity(lp.id(), NearToken::from_near(1))
+        .await
+        .unwrap();
+    assert!(lp.is_liquidity_seeded().await.unwrap());
+
+    let balance_after = alice.view_account().await.unwrap().balance;
+    assert!(
+        balance_before.saturating_sub(balance_after)
+            < NEAR_FOR_POOL_CREATION.saturating_add(NearToken::from_millinear(100))
+    );
+
+    assert_eq!(dex.get_pool_amounts(0).await.unwrap(), vec![40_000, 40_000]);
+    assert_eq!(dex.get_pool_shares(0, bob.id()).await.unwrap(), 40_000);
+    assert_eq!(dex.get_pool_shares(0, lp.id()).await.unwrap(), 0);
+    assert_eq!(
+        env.deposit_ft.ft_balance_of(dex.id()).await.unwrap(),
+        40_000
+    );
+    assert_eq!(
+        env.sale_token.ft_balance_of(dex.id()).await.unwrap(),
+        40_000
+    );
+
+    let err = alice.seed_liquidity(lp.id(), ONE_YOCTO).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The liquidity has been already seeded")
+    );
+
+    // The solver receives the deposited tokens which aren't added to the pool.
+    alice.distribute_deposit_tokens(lp.id()).await.unwrap();
+
+    let balance = env
+        .defuse
+        .mt_balance_of(
+            &solver_account_id,
+            format!("nep141:{}", env.deposit_ft.id()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(balance, 160_000);
+}
@@ -1,2 +1,3 @@
 mod deposit;
+mod liquidity;
 mod sale;
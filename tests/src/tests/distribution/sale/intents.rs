@@ -37,6 +37,7 @@ async fn successful_distribution() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -149,6 +150,7 @@ async fn distribution_for_max_stakeholders() {
             })
             .collect(),
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -239,6 +241,7 @@ async fn double_distribution() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -346,6 +349,7 @@ async fn multiple_distribution() {
             })
             .collect(),
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -457,6 +461,7 @@ async fn distribution_with_partial_refunds() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -599,6 +604,7 @@ async fn distribution_with_partial_refunds_max_stakeholders() {
             })
             .collect(),
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -729,6 +735,7 @@ async fn successful_distribution_with_zero_allocation_for_solver() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -815,3 +822,114 @@ async fn successful_distribution_with_zero_allocation_for_solver() {
         .unwrap();
     assert_eq!(balance, 50_000);
 }
+
+#[tokio::test]
+async fn distribution_in_batches() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    let solver_account_id: AccountId = "solver.near".parse().unwrap();
+    let stakeholders = (1..=3)
+        .map(|i| format!("stakeholder{i}.near").parse().unwrap())
+        .collect::<Vec<AccountId>>();
+    let solver_allocation = 97_000;
+    let stakeholder_allocation = 1_000;
+
+    config.soft_cap = 100_000.into();
+    config.sale_amount = 100_000.into();
+    config.distribution_proportions = DistributionProportions {
+        solver_account_id: DistributionAccount::new_intents(solver_account_id.clone()).unwrap(),
+        solver_allocation: solver_allocation.into(),
+        stakeholder_proportions: stakeholders
+            .iter()
+            .map(|a| StakeholderProportion {
+                account: DistributionAccount::new_intents(a).unwrap(),
+                allocation: stakeholder_allocation.into(),
+                vesting: None,
+            })
+            .collect(),
+        deposits: None,
+        liquidity: None,
+    };
+
+    let lp = env.create_launchpad(&config).await.unwrap();
+    let alice = env.alice();
+
+    env.sale_token
+        .storage_deposits(&[lp.id(), env.defuse.id()])
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+
+    let err = alice
+        .distribute_sale_tokens_in_batch(lp.id(), 8)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The batch size must be between 1 and 7")
+    );
+
+    alice
+        .distribute_sale_tokens_in_batch(lp.id(), 2)
+        .await
+        .unwrap();
+
+    let distributed = lp
+        .get_distribution_progress()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(_, amount)| amount)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        distributed,
+        [solver_allocation, stakeholder_allocation, 0, 0]
+    );
+
+    alice
+        .distribute_sale_tokens_in_batch(lp.id(), 2)
+        .await
+        .unwrap();
+
+    let progress = lp.get_distribution_progress().await.unwrap();
+    assert_eq!(
+        progress,
+        std::iter::once((
+            DistributionAccount::new_intents(solver_account_id).unwrap(),
+            solver_allocation
+        ))
+        .chain(stakeholders.iter().map(|account| (
+            DistributionAccount::new_intents(account).unwrap(),
+            stakeholder_allocation
+        )))
+        .collect::<Vec<_>>()
+    );
+
+    for stakeholder in stakeholders {
+        let balance = env
+            .defuse
+            .mt_balance_of(&stakeholder, format!("nep141:{}", env.sale_token.id()))
+            .await
+            .unwrap();
+        assert_eq!(balance, stakeholder_allocation);
+    }
+}
@@ -1,12 +1,12 @@
-use crate::env::fungible_token::FungibleToken;
+use crate::env::fungible_token::{FungibleToken, STORAGE_DEPOSIT};
 use crate::env::mt_token::MultiToken;
 use crate::env::rpc::AssertError;
-use crate::env::sale_contract::{Claim, Deposit, Distribute, SaleContract};
+use crate::env::sale_contract::{Claim, Deposit, Distribute, SaleContract, StorageDepositManage};
 use crate::env::{Env, rpc};
 use aurora_launchpad_types::config::{
     DistributionAccount, DistributionProportions, StakeholderProportion,
 };
-use near_sdk::AccountId;
+use near_sdk::{AccountId, NearToken};
 
 const MAX_STAKEHOLDERS: u128 = 12;
 
@@ -36,6 +36,7 @@ async fn successful_distribution() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -145,6 +146,7 @@ async fn distribution_for_max_stakeholders() {
             })
             .collect(),
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -235,6 +237,7 @@ async fn double_distribution() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -338,6 +341,7 @@ async fn multiple_distribution() {
             })
             .collect(),
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -438,6 +442,7 @@ async fn distribution_without_storage_deposit() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -485,18 +490,9 @@ async fn distribution_without_storage_deposit() {
     let balance = env.sale_token.ft_balance_of(alice.id()).await.unwrap();
     assert_eq!(balance, 100_000);
 
-    let err = alice.distribute_sale_tokens(lp.id()).await.unwrap_err();
-    assert!(
-        err.to_string()
-            .contains("The account stakeholder2.near is not registered")
-    );
-
-    env.sale_token
-        .storage_deposit(&stakeholder2_account_id)
-        .await
-        .unwrap();
-
+    // The stakeholder is registered in the sale token by the launchpad.
     alice.distribute_sale_tokens(lp.id()).await.unwrap();
+    assert!(lp.get_unregistered_recipients().await.unwrap().is_empty());
 
     let balance = env
         .defuse
@@ -523,6 +519,192 @@ async fn distribution_without_storage_deposit() {
     assert_eq!(balance, 30_000);
 }
 
+#[tokio::test]
+async fn distribution_without_storage_deposit_budget() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    let solver_account_id: AccountId = "solver.near".parse().unwrap();
+    let stakeholder_account_id: AccountId = "stakeholder.near".parse().unwrap();
+
+    config.soft_cap = 100_000.into();
+    config.sale_amount = 100_000.into();
+    config.distribution_proportions = DistributionProportions {
+        solver_account_id: DistributionAccount::new_near(solver_account_id.clone()).unwrap(),
+        solver_allocation: 50_000.into(),
+        stakeholder_proportions: vec![StakeholderProportion {
+            account: DistributionAccount::new_near(stakeholder_account_id.clone()).unwrap(),
+            allocation: 50_000.into(),
+            vesting: None,
+        }],
+        deposits: None,
+        liquidity: None,
+    };
+
+    let admin = env.john();
+    let lp = env
+        .create_launchpad_with_admin(&config, Some(admin.id()))
+        .await
+        .unwrap();
+    let alice = env.alice();
+
+    env.sale_token
+        .storage_deposits(&[lp.id(), alice.id(), &solver_account_id, env.defuse.id()])
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    admin
+        .set_storage_deposit_settings(lp.id(), STORAGE_DEPOSIT, Some(NearToken::from_near(0)))
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+
+    // The budget doesn't allow registering the stakeholder, so only the solver gets the tokens.
+    alice.distribute_sale_tokens(lp.id()).await.unwrap();
+    assert_eq!(
+        lp.get_unregistered_recipients().await.unwrap(),
+        vec![stakeholder_account_id.clone()]
+    );
+
+    let balance = env
+        .sale_token
+        .ft_balance_of(&solver_account_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 50_000);
+
+    let balance = env
+        .sale_token
+        .ft_balance_of(&stakeholder_account_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+
+    // The distribution is retried after increasing the budget.
+    admin
+        .set_storage_deposit_settings(lp.id(), STORAGE_DEPOSIT, Some(STORAGE_DEPOSIT))
+        .await
+        .unwrap();
+    alice.distribute_sale_tokens(lp.id()).await.unwrap();
+    assert!(lp.get_unregistered_recipients().await.unwrap().is_empty());
+
+    let balance = env
+        .sale_token
+        .ft_balance_of(&stakeholder_account_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 50_000);
+
+    let err = alice.distribute_sale_tokens(lp.id()).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Tokens have been already distributed")
+    );
+}
+
+#[tokio::test]
+async fn storage_deposit_budget_is_restored_if_distribution_fails() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    let solver_account_id: AccountId = "solver.near".parse().unwrap();
+    // The test token fails the transfers from the launchpad to the account.
+    let stakeholder_account_id: AccountId = "alice_fail.near".parse().unwrap();
+
+    config.soft_cap = 100_000.into();
+    config.sale_amount = 100_000.into();
+    config.distribution_proportions = DistributionProportions {
+        solver_account_id: DistributionAccount::new_near(solver_account_id.clone()).unwrap(),
+        solver_allocation: 50_000.into(),
+        stakeholder_proportions: vec![StakeholderProportion {
+            account: DistributionAccount::new_near(stakeholder_account_id.clone()).unwrap(),
+            allocation: 50_000.into(),
+            vesting: None,
+        }],
+        deposits: None,
+        liquidity: None,
+    };
+
+    let admin = env.john();
+    let lp = env
+        .create_launchpad_with_admin(&config, Some(admin.id()))
+        .await
+        .unwrap();
+    let alice = env.alice();
+
+    env.sale_token
+        .storage_deposits(&[lp.id(), alice.id(), &solver_account_id, env.defuse.id()])
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), alice.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(alice.id(), 100_000)
+        .await
+        .unwrap();
+
+    alice
+        .deposit_nep141(lp.id(), env.deposit_ft.id(), 100_000)
+        .await
+        .unwrap();
+
+    admin
+        .set_storage_deposit_settings(lp.id(), STORAGE_DEPOSIT, Some(STORAGE_DEPOSIT))
+        .await
+        .unwrap();
+
+    env.wait_for_sale_finish(&config).await;
+
+    assert_eq!(lp.get_status().await.unwrap(), "Success");
+
+    // The failed batch refunds the deposit for the registration, so it's returned to the budget.
+    alice.distribute_sale_tokens(lp.id()).await.unwrap();
+    assert_eq!(
+        lp.get_storage_deposit_budget().await.unwrap(),
+        Some(STORAGE_DEPOSIT.as_yoctonear())
+    );
+
+    let balance = env
+        .sale_token
+        .ft_balance_of(&solver_account_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+
+    let balance = env
+        .sale_token
+        .ft_balance_of(&stakeholder_account_id)
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+}
+
 #[tokio::test]
 async fn successful_distribution_with_zero_allocation_for_solver() {
     let env = Env::new().await.unwrap();
@@ -549,6 +731,7 @@ async fn successful_distribution_with_zero_allocation_for_solver() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -659,6 +842,7 @@ async fn test_reentrancy_protection() {
             },
         ],
         deposits: None,
+        liquidity: None,
     };
 
     let lp = env.create_launchpad(&config).await.unwrap();
@@ -1,9 +1,15 @@
+use aurora_launchpad_types::config::{DistributionAccount, LaunchpadConfig};
+use near_primitives::hash::CryptoHash;
 use near_sdk::NearToken;
-use near_sdk::serde_json::json;
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::{Value, json};
 use near_workspaces::types::{KeyType, SecretKey};
+use near_workspaces::{AccountId, Contract};
 
-use crate::env::Env;
+use crate::env::fungible_token::FungibleToken;
 use crate::env::sale_contract::SaleContract;
+use crate::env::{Env, validate_result};
+use crate::tests::NANOSECONDS_PER_SECOND;
 
 #[tokio::test]
 async fn create_via_factory() {
@@ -29,6 +35,849 @@ async fn create_via_factory_with_invalid_config() {
     assert!(result.to_string().contains("The Total sale amount must be equal to the sale amount plus solver allocation and distribution allocations"));
 }
 
+#[tokio::test]
+async fn failed_creation_does_not_burn_launchpad_id() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    config.distribution_proportions.solver_allocation = 2500.into();
+
+    let err = env.create_launchpad(&config).await.unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The Total sale amount must be equal")
+    );
+
+    config.distribution_proportions.solver_allocation = 0.into();
+    let lp = env.create_launchpad(&config).await.unwrap();
+    assert_eq!(lp.id().as_str(), format!("lp-1.{}", env.factory.id()));
+}
+
+#[tokio::test]
+async fn failed_creation_refunds_deposit() {
+    let env = Env::new().await.unwrap();
+    let creator = env
+        .master_account
+        .create_subaccount("creator")
+        .initial_balance(NearToken::from_near(20))
+        .transact()
+        .await
+        .unwrap()
+        .result;
+    env.grant_factory_role("Controller", creator.id())
+        .await
+        .unwrap();
+
+    // The account with the same name already exists, so the creation fails after the call.
+    let _result = env
+        .factory
+        .as_account()
+        .create_subaccount("taken")
+        .initial_balance(NearToken::from_near(1))
+        .transact()
+        .await
+        .unwrap();
+
+    let config = env.create_config().await;
+    let balance_before = creator.view_account().await.unwrap().balance;
+    let result = creator
+        .call(env.factory.id(), "create_launchpad")
+        .args_json(json!({
+            "config": config,
+            "name": "taken",
+        }))
+        .deposit(NearToken::from_near(9))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.json::<Option<AccountId>>().unwrap().is_none());
+
+    let balance_after = creator.view_account().await.unwrap().balance;
+    // Only the gas fees should be spent.
+    assert!(balance_before.saturating_sub(balance_after) < NearToken::from_millinear(100));
+}
+
+#[tokio::test]
+async fn invalid_config_is_rejected_before_deployment() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    config.tge = Some(config.end_date);
+
+    let result = env
+        .factory
+        .call("create_launchpad")
+        .args_json(json!({
+            "config": config,
+        }))
+        .deposit(NearToken::from_near(9))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+    assert!(format!("{result:?}").contains("Invalid config: TGE must be after the sale end time"));
+
+    // The transaction fails in the factory, so the launchpad account isn't created.
+    let launchpad_account_id: AccountId = format!("lp-1.{}", env.factory.id()).parse().unwrap();
+    assert!(
+        env.worker
+            .view_account(&launchpad_account_id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn create_via_factory_from_template() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let mut config_json = near_sdk::serde_json::to_value(&config).unwrap();
+    let template = json!({
+        "deposit_token": config_json["deposit_token"].take(),
+        "intents_account_id": config_json["intents_account_id"].take(),
+        "min_deposit": "1",
+        "distribution_proportions": {
+            "solver_account_id": config_json["distribution_proportions"]["solver_account_id"].take(),
+            "solver_allocation": "0",
+            "stakeholder_proportions": [],
+            "deposits": {
+                "solver_percentage": 0,
+                "recipients": [{
+                    "account": "intents:fee.near",
+                    "percentage": 100,
+                }],
+            },
+        },
+    });
+    let overrides = json!({
+        "min_deposit": config.min_deposit,
+        "sale_token": config.sale_token,
+        "start_date": config_json["start_date"],
+        "end_date": config_json["end_date"],
+        "soft_cap": config.soft_cap,
+        "mechanics": config.mechanics,
+        "sale_amount": config.sale_amount,
+        "total_sale_amount": config.total_sale_amount,
+        "distribution_proportions": {
+            "deposits": {
+                "solver_percentage": 200,
+            },
+        },
+    });
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+
+    let result = env
+        .factory
+        .call("set_config_template")
+        .args_json(json!({
+            "name": "intents-usdc",
+            "template": template,
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let names: Vec<String> = env
+        .factory
+        .view("get_config_template_names")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_eq!(names, ["intents-usdc"]);
+
+    let err = create_launchpad_from_template(&env, "unknown", &overrides)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unknown config template"));
+
+    let err = create_launchpad_from_template(&env, "intents-usdc", &json!({}))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid config: missing field"));
+
+    let lp = create_launchpad_from_template(&env, "intents-usdc", &overrides)
+        .await
+        .unwrap();
+    assert_eq!(lp.as_str(), format!("lp-1.{}", env.factory.id()));
+
+    let lp_config: LaunchpadConfig = env
+        .worker
+        .view(&lp, "get_config")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    let deposits = lp_config.distribution_proportions.deposits.unwrap();
+    assert_eq!(deposits.solver_percentage, 200);
+    assert_eq!(
+        deposits.recipients[0].account,
+        DistributionAccount::new_intents("fee.near").unwrap()
+    );
+    assert_eq!(deposits.recipients[0].percentage, 100);
+    assert_eq!(lp_config.deposit_token, config.deposit_token);
+    assert_eq!(lp_config.min_deposit, config.min_deposit);
+    assert_eq!(lp_config.start_date, config.start_date);
+}
+
+async fn create_launchpad_from_template(
+    env: &Env,
+    template: &str,
+    overrides: &Value,
+) -> anyhow::Result<AccountId> {
+    let result = env
+        .factory
+        .call("create_launchpad_from_template")
+        .args_json(json!({
+            "template": template,
+            "overrides": overrides,
+        }))
+        .deposit(NearToken::from_near(9))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    result.json().map_err(Into::into)
+}
+
+#[tokio::test]
+async fn create_and_fund_via_sale_token() {
+    let env = Env::new().await.unwrap();
+    let mut config = env.create_config().await;
+    config.start_date += 100 * NANOSECONDS_PER_SECOND;
+    config.end_date += 100 * NANOSECONDS_PER_SECOND;
+
+    env.grant_factory_role("Controller", env.sale_token.id())
+        .await
+        .unwrap();
+    env.sale_token
+        .storage_deposit(env.factory.id())
+        .await
+        .unwrap();
+    let balance_before = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+
+    let result = env
+        .sale_token
+        .call("ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.factory.id(),
+            "amount": config.total_sale_amount,
+            "msg": json!({
+                "config": config,
+                "name": "funded",
+            })
+            .to_string(),
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    // The deposit for the launchpad account isn't paid from the factory balance.
+    assert_eq!(result.json::<U128>().unwrap(), U128(0));
+    let lp_account_id: AccountId = format!("funded.{}", env.factory.id()).parse().unwrap();
+    assert!(!is_launchpad(&env, &lp_account_id).await);
+
+    deposit_for_creation(&env, NearToken::from_near(10)).await;
+
+    env.sale_token
+        .ft_transfer_call(
+            env.factory.id(),
+            config.total_sale_amount,
+            json!({
+                "config": config,
+                "name": "funded",
+            })
+            .to_string(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        get_creation_deposit(&env).await,
+        NearToken::from_near(10)
+            .saturating_sub(NearToken::from_near(9))
+            .saturating_sub(sale_token_storage_deposit(&env).await)
+    );
+
+    let lp = Contract::from_secret_key(
+        lp_account_id.clone(),
+        env.factory.as_account().secret_key().clone(),
+        &env.worker,
+    );
+    assert_eq!(lp.get_status().await.unwrap(), "NotStarted");
+
+    let balance = env.sale_token.ft_balance_of(&lp_account_id).await.unwrap();
+    assert_eq!(balance, config.total_sale_amount.0);
+    let balance = env
+        .sale_token
+        .ft_balance_of(env.factory.id())
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+    let balance_after = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+    assert_eq!(balance_before - balance_after, config.total_sale_amount.0);
+}
+
+#[tokio::test]
+async fn create_and_fund_via_sale_token_refunds_on_failure() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+
+    env.grant_factory_role("Controller", env.sale_token.id())
+        .await
+        .unwrap();
+    env.sale_token
+        .storage_deposit(env.factory.id())
+        .await
+        .unwrap();
+    deposit_for_creation(&env, NearToken::from_near(10)).await;
+    // The account with the same name already exists, so the creation fails.
+    let _result = env
+        .factory
+        .as_account()
+        .create_subaccount("taken")
+        .initial_balance(NearToken::from_near(1))
+        .transact()
+        .await
+        .unwrap();
+    let balance_before = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+
+    let result = env
+        .sale_token
+        .call("ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.factory.id(),
+            "amount": config.total_sale_amount,
+            "msg": json!({
+                "config": config,
+                "name": "taken",
+            })
+            .to_string(),
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert_eq!(result.json::<U128>().unwrap(), U128(0));
+
+    let balance_after = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+    assert_eq!(balance_before, balance_after);
+    let balance = env
+        .sale_token
+        .ft_balance_of(env.factory.id())
+        .await
+        .unwrap();
+    assert_eq!(balance, 0);
+
+    let lp_account_id: AccountId = format!("taken.{}", env.factory.id()).parse().unwrap();
+    assert!(!is_launchpad(&env, &lp_account_id).await);
+    assert_eq!(get_creation_deposit(&env).await, NearToken::from_near(10));
+}
+
+#[tokio::test]
+async fn create_and_fund_via_sale_token_keeps_unfunded_launchpad() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+
+    env.grant_factory_role("Controller", env.sale_token.id())
+        .await
+        .unwrap();
+    env.sale_token
+        .storage_deposit(env.factory.id())
+        .await
+        .unwrap();
+    deposit_for_creation(&env, NearToken::from_near(10)).await;
+    let balance_before = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+
+    // The sale token fails forwarding the tokens to the launchpad.
+    let result = env
+        .sale_token
+        .call("ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.factory.id(),
+            "amount": config.total_sale_amount,
+            "msg": json!({
+                "config": config,
+                "name": "fail-funding",
+            })
+            .to_string(),
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert_eq!(result.json::<U128>().unwrap(), U128(0));
+
+    let balance_after = env
+        .sale_token
+        .ft_balance_of(env.sale_token.id())
+        .await
+        .unwrap();
+    assert_eq!(balance_before, balance_after);
+
+    // The launchpad stays created and unfunded, and its deposit isn't refunded.
+    let lp_account_id: AccountId = format!("fail-funding.{}", env.factory.id())
+        .parse()
+        .unwrap();
+    assert!(is_launchpad(&env, &lp_account_id).await);
+    let lp = Contract::from_secret_key(
+        lp_account_id,
+        env.factory.as_account().secret_key().clone(),
+        &env.worker,
+    );
+    assert!(lp.is_not_initialized().await.unwrap());
+    assert_eq!(
+        get_creation_deposit(&env).await,
+        NearToken::from_near(10)
+            .saturating_sub(NearToken::from_near(9))
+            .saturating_sub(sale_token_storage_deposit(&env).await)
+    );
+}
+
+async fn sale_token_storage_deposit(env: &Env) -> NearToken {
+    let bounds: Value = env
+        .sale_token
+        .view("storage_balance_bounds")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    let min: U128 = near_sdk::serde_json::from_value(bounds["min"].clone()).unwrap();
+
+    NearToken::from_yoctonear(min.0)
+}
+
+async fn deposit_for_creation(env: &Env, deposit: NearToken) {
+    let result = env
+        .sale_token
+        .as_account()
+        .call(env.factory.id(), "deposit_for_creation")
+        .deposit(deposit)
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+}
+
+async fn get_creation_deposit(env: &Env) -> NearToken {
+    env.factory
+        .view("get_creation_deposit")
+        .args_json(json!({
+            "account_id": env.sale_token.id(),
+        }))
+        .await
+        .unwrap()
+        .json()
+        .unwrap()
+}
+
+async fn is_launchpad(env: &Env, account_id: &AccountId) -> bool {
+    env.factory
+        .view("is_launchpad")
+        .args_json(json!({
+            "account_id": account_id,
+        }))
+        .await
+        .unwrap()
+        .json()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_via_factory_with_custom_name() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+
+    let lp = env
+        .create_launchpad_with_name(&config, None, Some("my-sale"))
+        .await
+        .unwrap();
+    assert_eq!(lp.id().as_str(), format!("my-sale.{}", env.factory.id()));
+    assert_eq!(lp.get_version().await.unwrap(), env!("CARGO_PKG_VERSION"));
+
+    // The custom name doesn't affect the generated names.
+    let lp = env.create_launchpad(&config).await.unwrap();
+    assert_eq!(lp.id().as_str(), format!("lp-1.{}", env.factory.id()));
+
+    let err = env
+        .create_launchpad_with_name(&config, None, Some("my-sale"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Launchpad with the same account id already exists")
+    );
+}
+
+#[tokio::test]
+async fn create_via_factory_with_invalid_name() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+
+    let err = env
+        .create_launchpad_with_name(&config, None, Some("lp-5"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Launchpad names in format `lp-<number>` are reserved")
+    );
+
+    let err = env
+        .create_launchpad_with_name(&config, None, Some("my.sale"))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Launchpad name must not contain dots")
+    );
+
+    let err = env
+        .create_launchpad_with_name(&config, None, Some("My-Sale"))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Invalid launchpad name"));
+}
+
+#[tokio::test]
+async fn create_via_factory_with_registered_code() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let code = tokio::fs::read("../res/aurora_launchpad_contract.wasm")
+        .await
+        .unwrap();
+    let hash = CryptoHash::hash_bytes(&code);
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+
+    let result = env
+        .factory
+        .call("stage_launchpad_code")
+        .args_borsh(("1.0.0".to_string(), code))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let err = env
+        .factory
+        .call("register_launchpad_code")
+        .args_json(json!({
+            "version": "1.0.0",
+            "hash": CryptoHash::hash_bytes(b"wrong code").to_string(),
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap_err();
+    assert!(err.to_string().contains("doesn't match the provided one"));
+
+    let result = env
+        .factory
+        .call("register_launchpad_code")
+        .args_json(json!({
+            "version": "1.0.0",
+            "hash": hash.to_string(),
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let versions: Vec<Value> = env
+        .factory
+        .view("get_launchpad_code_versions")
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert_eq!(versions.len(), 2);
+    assert!(versions.iter().any(|v| v["version"] == "1.0.0"
+        && v["hash"] == hash.to_string()
+        && v["is_default"] == false
+        && v["is_builtin"] == false));
+    assert!(
+        versions
+            .iter()
+            .any(|v| v["version"] == env!("CARGO_PKG_VERSION")
+                && v["is_default"] == true
+                && v["is_builtin"] == true)
+    );
+
+    let lp = create_launchpad_with_version(&env, &config, "1.0.0")
+        .await
+        .unwrap();
+    assert_eq!(lp.as_str(), format!("lp-1.{}", env.factory.id()));
+
+    let result = env
+        .factory
+        .call("deprecate_launchpad_version")
+        .args_json(json!({
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let err = create_launchpad_with_version(&env, &config, "1.0.0")
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The launchpad code version is deprecated")
+    );
+
+    let err = create_launchpad_with_version(&env, &config, "2.0.0")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unknown launchpad code version"));
+}
+
+#[tokio::test]
+async fn upgrade_via_factory() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let alice = env.alice();
+    let code = tokio::fs::read("../res/aurora_launchpad_contract.wasm")
+        .await
+        .unwrap();
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+    register_launchpad_code(&env, "1.0.0", code).await.unwrap();
+
+    let lp = env
+        .create_launchpad_with_admin(&config, Some(alice.id()))
+        .await
+        .unwrap();
+    let lp2 = env
+        .create_launchpad_with_admin(&config, Some(alice.id()))
+        .await
+        .unwrap();
+
+    let has_role: bool = lp
+        .view("acl_has_role")
+        .args_json(json!({
+            "role": "Upgrader",
+            "account_id": env.factory.id(),
+        }))
+        .await
+        .unwrap()
+        .json()
+        .unwrap();
+    assert!(has_role);
+
+    // The admin of the second launchpad doesn't allow the factory to upgrade it.
+    let result = alice
+        .call(lp2.id(), "acl_revoke_role")
+        .args_json(json!({
+            "role": "Upgrader",
+            "account_id": env.factory.id(),
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let err = env
+        .factory
+        .call("upgrade_launchpads")
+        .args_json(json!({
+            "ids": [lp.id(), lp2.id()],
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The launchpad code version is not deployed as a global contract")
+    );
+
+    let result = env
+        .factory
+        .call("deploy_global_launchpad_code")
+        .args_json(json!({
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+    assert!(result.json::<bool>().unwrap());
+
+    let err = env
+        .factory
+        .call("upgrade_launchpads")
+        .args_json(json!({
+            "ids": vec![lp.id(); 9],
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The number of launchpads to upgrade must not exceed 8")
+    );
+
+    let result = env
+        .factory
+        .call("upgrade_launchpads")
+        .args_json(json!({
+            "ids": [lp.id(), lp2.id()],
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    let upgrade = get_launchpad_upgrade(&env, lp.id()).await;
+    assert_eq!(upgrade, json!({"version": "1.0.0", "status": "Success"}));
+    let upgrade = get_launchpad_upgrade(&env, lp2.id()).await;
+    assert_eq!(upgrade, json!({"version": "1.0.0", "status": "Failed"}));
+
+    assert_eq!(lp.get_version().await.unwrap(), env!("CARGO_PKG_VERSION"));
+    assert_eq!(lp.get_config().await.unwrap(), config);
+    assert!(lp.is_not_initialized().await.unwrap());
+
+    let err = env
+        .factory
+        .call("upgrade_launchpads")
+        .args_json(json!({
+            "ids": [alice.id()],
+            "version": "1.0.0",
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("is not a launchpad created by the factory")
+    );
+
+    let err = env
+        .factory
+        .call("track_launchpads")
+        .args_json(json!({
+            "ids": [alice.id()],
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("is not a subaccount of the factory")
+    );
+}
+
+async fn register_launchpad_code(env: &Env, version: &str, code: Vec<u8>) -> anyhow::Result<()> {
+    let hash = CryptoHash::hash_bytes(&code);
+
+    env.factory
+        .call("stage_launchpad_code")
+        .args_borsh((version.to_string(), code))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    env.factory
+        .call("register_launchpad_code")
+        .args_json(json!({
+            "version": version,
+            "hash": hash.to_string(),
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    Ok(())
+}
+
+async fn get_launchpad_upgrade(env: &Env, account_id: &AccountId) -> Value {
+    env.factory
+        .view("get_launchpad_upgrade")
+        .args_json(json!({
+            "account_id": account_id,
+        }))
+        .await
+        .unwrap()
+        .json()
+        .unwrap()
+}
+
+async fn create_launchpad_with_version(
+    env: &Env,
+    config: &LaunchpadConfig,
+    version: &str,
+) -> anyhow::Result<AccountId> {
+    let result = env
+        .factory
+        .call("create_launchpad")
+        .args_json(json!({
+            "config": config,
+            "version": version,
+        }))
+        .deposit(NearToken::from_near(9))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    result.json().map_err(Into::into)
+}
+
 #[tokio::test]
 async fn add_full_access_key() {
     let env = Env::new().await.unwrap();
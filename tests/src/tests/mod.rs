@@ -5,6 +5,7 @@ mod distribution;
 mod factory;
 mod init;
 mod lock;
+mod partner;
 mod tge;
 mod vesting;
 mod view;
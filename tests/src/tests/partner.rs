@@ -0,0 +1,344 @@
+use aurora_launchpad_types::config::LaunchpadConfig;
+use near_sdk::NearToken;
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::{Value, json};
+use near_workspaces::{Account, AccountId};
+
+use crate::env::fungible_token::FungibleToken;
+use crate::env::{Env, validate_result};
+
+#[tokio::test]
+async fn create_by_not_partner() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let partner = create_partner_account(&env).await;
+
+    let err = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Only controllers and partners can create launchpads")
+    );
+}
+
+#[tokio::test]
+async fn create_by_partner_with_near_fee() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let partner = create_partner_account(&env).await;
+    let treasury = env.bob();
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+    set_partner(&env, partner.id(), 2).await;
+    set_creation_fee(
+        &env,
+        json!({
+            "token": "Near",
+            "amount": NearToken::from_near(1).as_yoctonear().to_string(),
+            "treasury": treasury.id(),
+        }),
+    )
+    .await;
+
+    let err = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Attached deposit must be at least 10 NEAR including the creation fee")
+    );
+
+    let treasury_balance_before = treasury.view_account().await.unwrap().balance;
+    let lp = create_launchpad(&partner, &env, &config, NearToken::from_near(10))
+        .await
+        .unwrap();
+    assert_eq!(lp.as_str(), format!("lp-1.{}", env.factory.id()));
+
+    let treasury_balance_after = treasury.view_account().await.unwrap().balance;
+    assert_eq!(
+        treasury_balance_after.saturating_sub(treasury_balance_before),
+        NearToken::from_near(1)
+    );
+
+    let partner_info = get_partner(&env, partner.id()).await;
+    assert_eq!(
+        partner_info,
+        json!({"max_live_launchpads": 2, "live_launchpads": 1})
+    );
+}
+
+#[tokio::test]
+async fn create_by_partner_with_nep141_fee() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let partner = create_partner_account(&env).await;
+    let treasury = env.bob();
+
+    env.deposit_ft
+        .storage_deposits(&[env.factory.id(), partner.id(), treasury.id()])
+        .await
+        .unwrap();
+    env.deposit_ft.ft_transfer(partner.id(), 150).await.unwrap();
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+    set_partner(&env, partner.id(), 2).await;
+    set_creation_fee(
+        &env,
+        json!({
+            "token": {"Nep141": env.deposit_ft.id()},
+            "amount": "100",
+            "treasury": treasury.id(),
+        }),
+    )
+    .await;
+
+    let err = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Insufficient creation fee deposit")
+    );
+
+    let result = partner
+        .call(env.deposit_ft.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.factory.id(),
+            "amount": U128(150),
+            "msg": "",
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+    assert_eq!(get_creation_fee_deposit(&env, partner.id()).await, 150);
+
+    let lp = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap();
+    assert_eq!(lp.as_str(), format!("lp-1.{}", env.factory.id()));
+    assert_eq!(get_creation_fee_deposit(&env, partner.id()).await, 50);
+
+    let balance = env.deposit_ft.ft_balance_of(treasury.id()).await.unwrap();
+    assert_eq!(balance, 100);
+
+    let result = partner
+        .call(env.factory.id(), "withdraw_creation_fee_deposit")
+        .args_json(json!({
+            "token_id": env.deposit_ft.id(),
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+    assert_eq!(get_creation_fee_deposit(&env, partner.id()).await, 0);
+
+    let balance = env.deposit_ft.ft_balance_of(partner.id()).await.unwrap();
+    assert_eq!(balance, 50);
+}
+
+#[tokio::test]
+async fn nep141_fee_is_credited_back_if_treasury_is_not_registered() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let partner = create_partner_account(&env).await;
+    let treasury = env.bob();
+
+    env.deposit_ft
+        .storage_deposits(&[env.factory.id(), partner.id()])
+        .await
+        .unwrap();
+    env.deposit_ft.ft_transfer(partner.id(), 100).await.unwrap();
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+    set_partner(&env, partner.id(), 2).await;
+    set_creation_fee(
+        &env,
+        json!({
+            "token": {"Nep141": env.deposit_ft.id()},
+            "amount": "100",
+            "treasury": treasury.id(),
+        }),
+    )
+    .await;
+
+    let result = partner
+        .call(env.deposit_ft.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": env.factory.id(),
+            "amount": U128(100),
+            "msg": "",
+        }))
+        .deposit(NearToken::from_yoctonear(1))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+
+    create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap();
+
+    // The treasury isn't registered in the token, so the fee is credited back to the partner.
+    assert_eq!(get_creation_fee_deposit(&env, partner.id()).await, 100);
+    let balance = env.deposit_ft.ft_balance_of(treasury.id()).await.unwrap();
+    assert_eq!(balance, 0);
+}
+
+#[tokio::test]
+async fn partner_quota_of_live_launchpads() {
+    let env = Env::new().await.unwrap();
+    let config = env.create_config().await;
+    let partner = create_partner_account(&env).await;
+
+    env.grant_factory_role("Dao", env.factory.id())
+        .await
+        .unwrap();
+    set_partner(&env, partner.id(), 1).await;
+
+    let lp = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap();
+
+    let err = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The quota of live launchpads is exceeded")
+    );
+
+    // The sale isn't finished yet, so the quota is not released.
+    assert!(!release_launchpad_quota(&env, &lp).await);
+
+    env.sale_token.storage_deposit(&lp).await.unwrap();
+    env.sale_token
+        .ft_transfer_call(&lp, config.total_sale_amount, "")
+        .await
+        .unwrap();
+    env.wait_for_sale_finish(&config).await;
+
+    assert!(release_launchpad_quota(&env, &lp).await);
+
+    let partner_info = get_partner(&env, partner.id()).await;
+    assert_eq!(
+        partner_info,
+        json!({"max_live_launchpads": 1, "live_launchpads": 0})
+    );
+
+    let lp = create_launchpad(&partner, &env, &config, NearToken::from_near(9))
+        .await
+        .unwrap();
+    assert_eq!(lp.as_str(), format!("lp-2.{}", env.factory.id()));
+}
+
+async fn create_partner_account(env: &Env) -> Account {
+    env.master_account
+        .create_subaccount("partner")
+        .initial_balance(NearToken::from_near(30))
+        .transact()
+        .await
+        .unwrap()
+        .result
+}
+
+async fn create_launchpad(
+    partner: &Account,
+    env: &Env,
+    config: &LaunchpadConfig,
+    deposit: NearToken,
+) -> anyhow::Result<AccountId> {
+    let result = partner
+        .call(env.factory.id(), "create_launchpad")
+        .args_json(json!({
+            "config": config,
+        }))
+        .deposit(deposit)
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)?;
+
+    result.json().map_err(Into::into)
+}
+
+async fn set_partner(env: &Env, account_id: &AccountId, max_live_launchpads: u32) {
+    let result = env
+        .factory
+        .call("set_partner")
+        .args_json(json!({
+            "account_id": account_id,
+            "max_live_launchpads": max_live_launchpads,
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+}
+
+async fn set_creation_fee(env: &Env, fee: Value) {
+    let result = env
+        .factory
+        .call("set_creation_fee")
+        .args_json(json!({
+            "fee": fee,
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success(), "{result:#?}");
+}
+
+async fn release_launchpad_quota(env: &Env, launchpad_account_id: &AccountId) -> bool {
+    env.factory
+        .call("release_launchpad_quota")
+        .args_json(json!({
+            "launchpad_account_id": launchpad_account_id,
+        }))
+        .max_gas()
+        .transact()
+        .await
+        .and_then(validate_result)
+        .unwrap()
+        .json()
+        .unwrap()
+}
+
+async fn get_partner(env: &Env, account_id: &AccountId) -> Value {
+    env.factory
+        .view("get_partner")
+        .args_json(json!({
+            "account_id": account_id,
+        }))
+        .await
+        .unwrap()
+        .json()
+        .unwrap()
+}
+
+async fn get_creation_fee_deposit(env: &Env, account_id: &AccountId) -> u128 {
+    env.factory
+        .view("get_creation_fee_deposit")
+        .args_json(json!({
+            "account_id": account_id,
+            "token_id": env.deposit_ft.id(),
+        }))
+        .await
+        .unwrap()
+        .json::<U128>()
+        .unwrap()
+        .0
+}
@@ -307,6 +307,77 @@ async fn individual_vesting_schedule_claim_success_exactly_after_cliff_period()
     );
 }
 
+#[tokio::test]
+async fn individual_vesting_claim_for_unregistered_recipient() {
+    let env = Env::new().await.unwrap();
+    let alice = env.alice();
+    let bob = env.bob();
+    let alice_distribution_account = DistributionAccount::new_near(alice.id()).unwrap();
+    let alice_allocation = 100_000;
+    let bob_allocation = 200_000;
+
+    let mut config = env.create_config().await;
+    let schedule = VestingSchedule {
+        cliff_period: Duration::from_secs(20),
+        vesting_period: Duration::from_secs(60),
+        instant_claim_percentage: None,
+        vesting_scheme: VestingScheme::Immediate,
+    };
+
+    config.vesting_schedule = Some(schedule);
+    config.total_sale_amount = 300_000.into();
+    config.distribution_proportions.stakeholder_proportions = vec![StakeholderProportion {
+        account: alice_distribution_account.clone(),
+        allocation: alice_allocation.into(),
+        vesting: Some(schedule),
+    }];
+    let lp = env.create_launchpad(&config).await.unwrap();
+
+    // Alice isn't registered in the sale token.
+    env.sale_token
+        .storage_deposits(&[lp.id(), bob.id(), env.defuse.id()])
+        .await
+        .unwrap();
+    env.sale_token
+        .ft_transfer_call(lp.id(), config.total_sale_amount, "")
+        .await
+        .unwrap();
+
+    env.deposit_ft
+        .storage_deposits(&[lp.id(), bob.id()])
+        .await
+        .unwrap();
+    env.deposit_ft
+        .ft_transfer(bob.id(), bob_allocation)
+        .await
+        .unwrap();
+
+    bob.deposit_nep141(lp.id(), env.deposit_ft.id(), bob_allocation)
+        .await
+        .unwrap();
+
+    env.wait_for_timestamp(config.end_date + 20 * NANOSECONDS_PER_SECOND)
+        .await;
+    assert!(lp.is_success().await.unwrap());
+
+    let block_hash = alice
+        .claim_individual_vesting(lp.id(), &alice_distribution_account)
+        .await
+        .unwrap();
+    let block_time = env.get_blocktime(block_hash).await;
+    let expected = expected_balance(alice_allocation, &schedule, config.end_date, block_time);
+
+    let balance = env.sale_token.ft_balance_of(alice.id()).await.unwrap();
+    assert_eq!(balance, expected);
+    assert_eq!(
+        lp.get_individual_vesting_claimed(&alice_distribution_account)
+            .await
+            .unwrap(),
+        Some(expected)
+    );
+    assert!(lp.get_unregistered_recipients().await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn individual_vesting_schedule_many_claims_success_for_different_periods() {
     let env = Env::new().await.unwrap();
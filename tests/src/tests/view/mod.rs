@@ -31,7 +31,10 @@ async fn valid_view_data() {
     assert_eq!(config_sale_amount, config.sale_amount.0);
 
     let config_sale_token_account_id = lp.get_sale_token_account_id().await.unwrap();
-    assert_eq!(config_sale_token_account_id, config.sale_token_account_id);
+    assert_eq!(
+        &config_sale_token_account_id,
+        config.sale_token.account_id()
+    );
 
     let config_total_sale_amount = lp.get_total_sale_amount().await.unwrap();
     assert_eq!(config_total_sale_amount, config.total_sale_amount.0);
@@ -227,7 +227,7 @@ async fn error_withdraw_price_discovery_while_ongoing() {
         .unwrap();
     assert!(
         err.to_string()
-            .contains("Smart contract panicked: Withdraw is not allowed")
+            .contains("Smart contract panicked: ERR_3002: Withdraw is not allowed")
     );
 
     env.wait_for_sale_finish(&config).await;
@@ -1386,7 +1386,7 @@ async fn withdraw_with_intent_signed_by_another_key() {
         .unwrap_err();
     assert!(
         err.to_string()
-            .contains("Smart contract panicked: Withdraw is not allowed")
+            .contains("Smart contract panicked: ERR_3002: Withdraw is not allowed")
     );
 
     // Check that Alice's investments haven't been changed after an attempt of unsanctioned withdrawal.
@@ -0,0 +1,66 @@
+use near_sdk::json_types::U128;
+use near_sdk::near;
+
+/// The state of the participation of an account in the launchpad.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct AccountSummary {
+    /// Number of deposited tokens.
+    pub deposit: U128,
+    /// Weight of the account which is used to calculate the allocation.
+    pub weight: U128,
+    /// Number of sale tokens allocated to the account.
+    pub allocation: U128,
+    /// Number of claimed sale tokens.
+    pub claimed: U128,
+    /// Number of sale tokens available for claim.
+    pub available_for_claim: U128,
+    /// Number of sale tokens which are still locked by the vesting.
+    pub remaining_vesting: U128,
+    /// The time when the next sale tokens are unlocked, if any.
+    pub next_unlock: Option<u64>,
+    /// Whether a withdrawal of the account is in progress.
+    pub is_withdraw_in_progress: bool,
+    /// The participation of the account in the discount phases.
+    pub discount_phases: Vec<AccountDiscountPhase>,
+    /// The actions which the account could make at the moment.
+    pub allowed_actions: Vec<AccountAction>,
+}
+
+/// The participation of an account in a discount phase.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct AccountDiscountPhase {
+    /// ID of the phase.
+    pub id: u16,
+    /// Number of sale tokens bought by the account with discount in the phase.
+    pub sale_tokens: U128,
+    /// Whether the phase is active at the moment.
+    pub is_active: bool,
+    /// Whether the account is allowed to participate in the phase by the whitelist.
+    pub is_allowed: bool,
+}
+
+/// Actions which an account could make in the launchpad.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub enum AccountAction {
+    /// Deposit tokens to the launchpad.
+    Deposit,
+    /// Withdraw deposited tokens without intents.
+    Withdraw,
+    /// Withdraw deposited tokens with signed intents only.
+    WithdrawWithIntents,
+    /// Claim sale tokens.
+    Claim,
+}
+
+/// The number of sale tokens unlocked by the vesting at the given time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct VestingPoint {
+    /// The time of the point.
+    pub timestamp: u64,
+    /// Cumulative number of unlocked sale tokens, including the already claimed ones.
+    pub unlocked: U128,
+}
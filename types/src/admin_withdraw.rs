@@ -18,6 +18,10 @@ pub enum WithdrawalToken {
     Deposit,
     /// Withdraw sale tokens from the contract.
     Sale,
+    /// Withdraw the bundled sale tokens with the given index in the configuration.
+    Bundled(u8),
+    /// Withdraw the storage fees collected in the deposit tokens after failing.
+    StorageFees,
 }
 
 #[derive(Default, Debug, Copy, Clone)]
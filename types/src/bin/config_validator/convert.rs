@@ -0,0 +1,71 @@
+use aurora_launchpad_types::date_time;
+use near_sdk::serde_json::{self, Value};
+use std::str::FromStr;
+
+/// The fields of the config containing dates.
+const DATE_FIELDS: [&str; 6] = [
+    "start_date",
+    "end_date",
+    "tge",
+    "public_sale_start_time",
+    "start_time",
+    "end_time",
+];
+
+/// The format of the dates in the config.
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    /// Timestamps in nanoseconds.
+    Nanos,
+    /// Date times in ISO 8601 format.
+    Dates,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "nanos" => Ok(Self::Nanos),
+            "dates" => Ok(Self::Dates),
+            _ => Err(format!("Unknown format: {s}, expected nanos or dates")),
+        }
+    }
+}
+
+/// Converts the dates of the config to the format. The dates already in the format are kept.
+pub fn convert(value: &mut Value, format: Format) -> Result<(), String> {
+    match value {
+        Value::Object(map) => map.iter_mut().try_for_each(|(key, value)| {
+            if DATE_FIELDS.contains(&key.as_str()) {
+                convert_date(value, format).map_err(|e| format!("{key}: {e}"))
+            } else {
+                convert(value, format)
+            }
+        }),
+        Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|value| convert(value, format)),
+        _ => Ok(()),
+    }
+}
+
+fn convert_date(value: &mut Value, format: Format) -> Result<(), String> {
+    let converted = match (format, &*value) {
+        (Format::Nanos, Value::String(date)) => Value::from(
+            date_time::deserialize(Value::String(date.clone())).map_err(|e| e.to_string())?,
+        ),
+        (Format::Dates, Value::Number(number)) => {
+            let timestamp = number
+                .as_u64()
+                .ok_or_else(|| format!("Wrong timestamp: {number}"))?;
+            date_time::serialize(&timestamp, serde_json::value::Serializer)
+                .map_err(|e| e.to_string())?
+        }
+        _ => return Ok(()),
+    };
+
+    *value = converted;
+
+    Ok(())
+}
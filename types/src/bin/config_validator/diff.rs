@@ -0,0 +1,38 @@
+use near_sdk::serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Returns the differences of the configs as `path: left -> right` lines.
+pub fn diff(left: &Value, right: &Value) -> Vec<String> {
+    let mut differences = Vec::new();
+    diff_at("config", left, right, &mut differences);
+    differences
+}
+
+fn diff_at(path: &str, left: &Value, right: &Value, differences: &mut Vec<String>) {
+    match (left, right) {
+        (Value::Object(left), Value::Object(right)) => {
+            let keys = left.keys().chain(right.keys()).collect::<BTreeSet<_>>();
+
+            for key in keys {
+                diff_at(
+                    &format!("{path}.{key}"),
+                    left.get(key).unwrap_or(&Value::Null),
+                    right.get(key).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            for index in 0..left.len().max(right.len()) {
+                diff_at(
+                    &format!("{path}[{index}]"),
+                    left.get(index).unwrap_or(&Value::Null),
+                    right.get(index).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        }
+        _ if left != right => differences.push(format!("{path}: {left} -> {right}")),
+        _ => {}
+    }
+}
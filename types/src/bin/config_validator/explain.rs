@@ -0,0 +1,139 @@
+use aurora_launchpad_types::config::{LaunchpadConfig, Mechanics, VestingSchedule};
+
+use crate::format_date;
+
+/// Returns the human-readable description of the sale: the main parameters and the events
+/// ordered by time.
+pub fn explain(config: &LaunchpadConfig) -> Vec<String> {
+    let mechanics = match config.mechanics {
+        Mechanics::FixedPrice {
+            deposit_token,
+            sale_token,
+        } => format!(
+            "fixed price of {} deposit tokens per {} sale tokens",
+            deposit_token.0, sale_token.0
+        ),
+        Mechanics::PriceDiscovery => "price discovery".to_string(),
+    };
+    let mut lines = vec![
+        format!("Mechanics: {mechanics}"),
+        format!("Sale amount: {}", config.sale_amount.0),
+        format!("Soft cap: {}", config.soft_cap.0),
+        format!("Minimum deposit: {}", config.min_deposit.0),
+        String::new(),
+    ];
+
+    lines.extend(
+        timeline(config)
+            .into_iter()
+            .map(|(timestamp, event)| format!("{}  {event}", format_date(timestamp))),
+    );
+
+    lines
+}
+
+fn timeline(config: &LaunchpadConfig) -> Vec<(u64, String)> {
+    let mut events = vec![
+        (config.start_date, "The sale starts".to_string()),
+        (config.end_date, "The sale ends".to_string()),
+    ];
+
+    if let Some(discounts) = &config.discounts {
+        for phase in &discounts.phases {
+            let mut start = format!(
+                "Discount phase {} starts with {} discount",
+                phase.id,
+                percent(phase.percentage)
+            );
+            if let Some(limit) = phase.phase_sale_limit {
+                start.push_str(&format!(", the limit is {} sale tokens", limit.0));
+            }
+            if let Some(whitelist) = &phase.whitelist {
+                start.push_str(&format!(", {} whitelisted accounts", whitelist.len()));
+            }
+
+            let mut end = format!("Discount phase {} ends", phase.id);
+            if let Some(id) = phase.remaining_go_to_phase_id {
+                end.push_str(&format!(", the unsold tokens go to phase {id}"));
+            }
+
+            events.push((phase.start_time, start));
+            events.push((phase.end_time, end));
+        }
+
+        if let Some(public_sale_start_time) = discounts.public_sale_start_time {
+            events.push((public_sale_start_time, "The public sale starts".to_string()));
+        }
+    }
+
+    let vesting_start = config.tge.unwrap_or(config.end_date);
+
+    if let Some(tge) = config.tge {
+        events.push((tge, "TGE".to_string()));
+    }
+
+    let vestings = config
+        .vesting_schedule
+        .as_ref()
+        .map(|vesting| ("sale tokens".to_string(), vesting))
+        .into_iter()
+        .chain(
+            config
+                .distribution_proportions
+                .stakeholder_proportions
+                .iter()
+                .filter_map(|stakeholder| {
+                    stakeholder
+                        .vesting
+                        .as_ref()
+                        .map(|vesting| (format!("stakeholder {}", stakeholder.account), vesting))
+                }),
+        )
+        .chain(
+            config
+                .bundled_sale_tokens
+                .iter()
+                .enumerate()
+                .filter_map(|(index, bundled)| {
+                    bundled
+                        .vesting_schedule
+                        .as_ref()
+                        .map(|vesting| (format!("bundled token {index}"), vesting))
+                }),
+        );
+
+    for (name, vesting) in vestings {
+        events.extend(vesting_events(&name, vesting, vesting_start));
+    }
+
+    // The stable sort keeps the events of the same time in the order of the config.
+    events.sort_by_key(|(timestamp, _)| *timestamp);
+    events
+}
+
+fn vesting_events(name: &str, vesting: &VestingSchedule, vesting_start: u64) -> Vec<(u64, String)> {
+    let mut events = Vec::new();
+
+    if let Some(percentage) = vesting.instant_claim_percentage {
+        events.push((
+            vesting_start,
+            format!("{} of the {name} are unlocked", percent(percentage)),
+        ));
+    }
+
+    events.push((
+        vesting_start + vesting.cliff_period.as_nanos(),
+        format!("The vesting cliff of the {name} ends"),
+    ));
+    events.push((
+        vesting_start + vesting.vesting_period.as_nanos(),
+        format!("The vesting of the {name} ends, all tokens are unlocked"),
+    ));
+
+    events
+}
+
+/// Formats the percentage in basis points.
+fn percent(basis_points: u16) -> String {
+    format!("{}.{:02}%", basis_points / 100, basis_points % 100)
+}
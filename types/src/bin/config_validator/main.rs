@@ -0,0 +1,208 @@
+//! Command line tool for checking the launchpad configuration before the deployment.
+//!
+//! Usage:
+//! - `config_validator validate <config.json>` - prints all errors and warnings of the config;
+//! - `config_validator explain <config.json>` - prints the timeline of the sale;
+//! - `config_validator schedule <config.json> <account> [allocation]` - prints the unlock dates
+//!   of the participant's allocation or the stakeholder's allocation;
+//! - `config_validator diff <config.json> <other.json>` - prints the differences of the configs;
+//! - `config_validator convert <config.json> <nanos|dates>` - converts the dates of the config
+//!   between ISO 8601 and timestamps in nanoseconds.
+//!
+//! The config file contains either the config itself or the arguments of the factory
+//! `{"config": ..., "admin": ...}`. The dates could be given in both formats.
+#![allow(clippy::cast_possible_wrap)]
+use aurora_launchpad_types::config::LaunchpadConfig;
+use chrono::{DateTime, SecondsFormat, Utc};
+use near_sdk::serde_json::{self, Value};
+use std::fmt::Display;
+
+use crate::convert::Format;
+
+mod convert;
+mod diff;
+mod explain;
+mod schedule;
+mod validate;
+
+/// Exit codes of the tool, so the deployment pipeline could distinguish the outcomes.
+mod exit_code {
+    /// The config is valid, or the configs are equal.
+    pub const OK: i32 = 0;
+    /// The config has errors.
+    pub const INVALID_CONFIG: i32 = 1;
+    /// The config is valid but has warnings.
+    pub const WARNINGS: i32 = 2;
+    /// The configs are different.
+    pub const DIFFERENT: i32 = 3;
+    /// Wrong command line arguments.
+    pub const USAGE: i32 = 64;
+    /// The file is not a config or the requested data can't be calculated.
+    pub const DATA: i32 = 65;
+    /// The file can't be read.
+    pub const IO: i32 = 66;
+}
+
+const COMMANDS: [&str; 5] = ["validate", "explain", "schedule", "diff", "convert"];
+const USAGE: &str =
+    "Usage: config_validator <validate|explain|schedule|diff|convert> <config.json> [args]";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let code = run(&args).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        e.exit_code()
+    });
+
+    std::process::exit(code);
+}
+
+fn run(args: &[String]) -> Result<i32, Error> {
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    match args.as_slice() {
+        // A single path is validated for compatibility with the previous versions of the tool.
+        ["validate", path] => validate(path),
+        [path] if !COMMANDS.contains(path) => validate(path),
+        ["explain", path] => {
+            explain::explain(&load_config(path)?)
+                .iter()
+                .for_each(|line| println!("{line}"));
+            Ok(exit_code::OK)
+        }
+        ["schedule", path, account] => schedule(path, account, None),
+        ["schedule", path, account, allocation] => schedule(path, account, Some(allocation)),
+        ["diff", path, other] => {
+            let differences = diff::diff(&normalized_config(path)?, &normalized_config(other)?);
+            differences.iter().for_each(|line| println!("{line}"));
+
+            Ok(if differences.is_empty() {
+                exit_code::OK
+            } else {
+                exit_code::DIFFERENT
+            })
+        }
+        ["convert", path, format] => {
+            let format = format.parse::<Format>().map_err(Error::Usage)?;
+            let mut value = read_json(path)?;
+            convert::convert(&mut value, format).map_err(Error::Convert)?;
+            let json =
+                serde_json::to_string_pretty(&value).map_err(|e| Error::Convert(e.to_string()))?;
+            println!("{json}");
+            Ok(exit_code::OK)
+        }
+        _ => Err(Error::Usage(USAGE.to_string())),
+    }
+}
+
+fn validate(path: &str) -> Result<i32, Error> {
+    let config = load_config(path)?;
+    let errors = validate::errors(&config);
+    let warnings = validate::warnings(&config, now());
+
+    errors.iter().for_each(|e| println!("error: {e}"));
+    warnings.iter().for_each(|w| println!("warning: {w}"));
+
+    Ok(if !errors.is_empty() {
+        exit_code::INVALID_CONFIG
+    } else if !warnings.is_empty() {
+        exit_code::WARNINGS
+    } else {
+        println!("Config is OK!!!");
+        exit_code::OK
+    })
+}
+
+fn schedule(path: &str, account: &str, allocation: Option<&str>) -> Result<i32, Error> {
+    let allocation = allocation
+        .map(|allocation| {
+            allocation
+                .parse::<u128>()
+                .map_err(|e| Error::Usage(format!("Wrong allocation: {e}")))
+        })
+        .transpose()?;
+    let schedule =
+        schedule::schedule(&load_config(path)?, account, allocation).map_err(Error::Calculation)?;
+
+    for (timestamp, unlocked) in schedule {
+        println!("{}  {unlocked}", format_date(timestamp));
+    }
+
+    Ok(exit_code::OK)
+}
+
+fn read_json(path: &str) -> Result<Value, Error> {
+    let json = std::fs::read_to_string(path).map_err(Error::Read)?;
+    serde_json::from_str(&json).map_err(Error::Deserialize)
+}
+
+/// Returns the config from the arguments of the factory or the config itself.
+fn config_value(mut value: Value) -> Value {
+    if value.get("config").is_some() {
+        value["config"].take()
+    } else {
+        value
+    }
+}
+
+fn load_config(path: &str) -> Result<LaunchpadConfig, Error> {
+    let mut value = config_value(read_json(path)?);
+    convert::convert(&mut value, Format::Dates).map_err(Error::Convert)?;
+    serde_json::from_value(value).map_err(Error::Deserialize)
+}
+
+/// Returns the config with the dates in the same format, so the configs could be compared.
+fn normalized_config(path: &str) -> Result<Value, Error> {
+    // Check that the file contains the config.
+    load_config(path)?;
+
+    let mut value = config_value(read_json(path)?);
+    convert::convert(&mut value, Format::Nanos).map_err(Error::Convert)?;
+    convert::convert(&mut value, Format::Dates).map_err(Error::Convert)?;
+
+    Ok(value)
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_nanos().try_into().unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+/// Formats the timestamp in nanoseconds as a date time in ISO 8601 format.
+fn format_date(timestamp: u64) -> String {
+    DateTime::<Utc>::from_timestamp_nanos(timestamp as i64)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Read(std::io::Error),
+    Deserialize(serde_json::Error),
+    Convert(String),
+    Calculation(&'static str),
+}
+
+impl Error {
+    const fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(_) => exit_code::USAGE,
+            Self::Read(_) => exit_code::IO,
+            Self::Deserialize(_) | Self::Convert(_) | Self::Calculation(_) => exit_code::DATA,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Usage(e) => write!(f, "{e}"),
+            Self::Read(e) => write!(f, "Error reading the config file: {e}"),
+            Self::Deserialize(e) => write!(f, "Error deserializing the config file: {e}"),
+            Self::Convert(e) => write!(f, "Error converting the dates: {e}"),
+            Self::Calculation(e) => write!(f, "Error calculating the schedule: {e}"),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use aurora_launchpad_types::config::{DistributionAccount, LaunchpadConfig, VestingSchedule};
+
+/// The step of the schedule between the end of the cliff and the end of the vesting.
+const SCHEDULE_STEP: u64 = 30 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Returns the number of unlocked tokens of the allocation at the start of the vesting, at
+/// the end of the cliff, every 30 days and at the end of the vesting. The account is either
+/// a stakeholder (e.g. `near:team.near`) with the allocation and the vesting from the config or
+/// a participant with the given allocation.
+pub fn schedule(
+    config: &LaunchpadConfig,
+    account: &str,
+    allocation: Option<u128>,
+) -> Result<Vec<(u64, u128)>, &'static str> {
+    let stakeholder = account
+        .parse::<DistributionAccount>()
+        .ok()
+        .and_then(|account| {
+            config
+                .distribution_proportions
+                .stakeholder_proportions
+                .iter()
+                .find(|stakeholder| stakeholder.account == account)
+        });
+    let (allocation, vesting) = match stakeholder {
+        Some(stakeholder) => (
+            allocation.unwrap_or(stakeholder.allocation.0),
+            stakeholder.vesting.as_ref(),
+        ),
+        None => (
+            allocation.ok_or("The allocation is required for the participant")?,
+            config.vesting_schedule.as_ref(),
+        ),
+    };
+    let vesting_start = config.tge.unwrap_or(config.end_date);
+
+    let Some(vesting) = vesting else {
+        return Ok(vec![(vesting_start, allocation)]);
+    };
+
+    unlock_dates(vesting, vesting_start)
+        .into_iter()
+        .map(|timestamp| {
+            vesting
+                .unlocked_amount(allocation, vesting_start, timestamp)
+                .map(|unlocked| (timestamp, unlocked))
+        })
+        .collect()
+}
+
+fn unlock_dates(vesting: &VestingSchedule, vesting_start: u64) -> Vec<u64> {
+    let cliff_end = vesting_start + vesting.cliff_period.as_nanos();
+    let vesting_end = vesting_start + vesting.vesting_period.as_nanos();
+    let mut dates = vec![vesting_start];
+
+    dates.extend(
+        (cliff_end..vesting_end).step_by(usize::try_from(SCHEDULE_STEP).unwrap_or(usize::MAX)),
+    );
+    dates.push(vesting_end);
+    dates.dedup();
+    dates
+}
@@ -0,0 +1,43 @@
+use aurora_launchpad_types::config::{ConfigErrors, LaunchpadConfig};
+
+/// TGE later than this period after the end of the sale is most likely a mistake.
+const MAX_TGE_DELAY: u64 = 365 * 24 * 60 * 60 * 1_000_000_000;
+
+/// Returns the errors of the config which prevent the launchpad from the initialization.
+pub fn errors(config: &LaunchpadConfig) -> ConfigErrors {
+    config.validate_all().err().unwrap_or_default()
+}
+
+/// Returns the warnings of the config. The config is valid, but probably it's not what
+/// the issuer intended.
+pub fn warnings(config: &LaunchpadConfig, now: u64) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if config.start_date < now {
+        warnings.push("The sale starts in the past".to_string());
+    }
+
+    if let Some(tge) = config.tge
+        && tge.saturating_sub(config.end_date) > MAX_TGE_DELAY
+    {
+        warnings.push("TGE is more than a year after the end of the sale".to_string());
+    }
+
+    let phases = config
+        .discounts
+        .as_ref()
+        .map_or(&[][..], |discounts| discounts.phases.as_slice());
+
+    for (index, phase) in phases.iter().enumerate() {
+        for other in &phases[index + 1..] {
+            if phase.start_time < other.end_time && other.start_time < phase.end_time {
+                warnings.push(format!(
+                    "Discount phases {} and {} overlap in time",
+                    phase.id, other.id
+                ));
+            }
+        }
+    }
+
+    warnings
+}
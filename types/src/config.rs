@@ -3,13 +3,15 @@ use near_sdk::json_types::U128;
 use near_sdk::serde::de::Error;
 use near_sdk::serde::{Deserialize, Deserializer, Serialize, Serializer};
 use near_sdk::{AccountId, near};
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use std::ops::Deref;
 use std::str::FromStr;
 
 use crate::IntentsAccount;
 use crate::discount::{DiscountParams, DiscountPhase};
 use crate::duration::Duration;
-use crate::utils::{is_all_unique, to_u128};
+use crate::utils::{duplicates, to_u128};
 use crate::{date_time, date_time_opt};
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -19,8 +21,13 @@ pub struct LaunchpadConfig {
     pub deposit_token: DepositToken,
     /// Minimum deposit amount denominated in the deposit token.
     pub min_deposit: U128,
-    /// The account of the token used in the Sale.
-    pub sale_token_account_id: AccountId,
+    /// The NEP-141 or NEP-245 token used in the Sale. The account id of the NEP-141 token in
+    /// `sale_token_account_id` of the configs of 0.7.x is accepted as well.
+    #[serde(
+        alias = "sale_token_account_id",
+        deserialize_with = "deserialize_sale_token"
+    )]
+    pub sale_token: SaleToken,
     /// The account of the intents contract.
     pub intents_account_id: AccountId,
     /// Start time of the sale.
@@ -60,6 +67,16 @@ pub struct LaunchpadConfig {
     pub distribution_proportions: DistributionProportions,
     /// An optional discount phases defined for the sale.
     pub discounts: Option<DiscountParams>,
+    /// Sale tokens sold together with the main sale token in fixed ratios to the allocations
+    /// of the participants.
+    #[serde(default)]
+    pub bundled_sale_tokens: Vec<BundledSaleToken>,
+    /// Allows the soft cap which can't be reached by selling the whole sale amount at the fixed
+    /// price, i.e. the sale which succeeds only if all tokens are sold. The flag is used by
+    /// the validation at the initialization only, so it isn't stored in the contract's state.
+    #[borsh(skip)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_unreachable_soft_cap: bool,
 }
 
 impl LaunchpadConfig {
@@ -81,12 +98,40 @@ impl LaunchpadConfig {
         }
     }
 
-    /// Config validator.
+    /// Config validator. Returns the reason of the first violation, see `validate_all` for
+    /// the full list of violations with the paths of the wrong fields.
     ///
     /// # Errors
     /// 1. Returns an error if the total sale amount is not equal to the sale amount plus solver
-    ///    allocation and distribution allocations.
-    pub fn validate(&self) -> Result<(), &'static str> {
+    ///    allocation, distribution allocations and liquidity allocation.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_all().map_err(|errors| {
+            errors
+                .first()
+                .map_or("Invalid config", ConfigError::reason)
+                .to_string()
+        })
+    }
+
+    /// Validates the config and returns all violations of the rules.
+    pub fn validate_all(&self) -> Result<(), ConfigErrors> {
+        let mut errors = Vec::new();
+
+        if self.start_date >= self.end_date {
+            errors.push(ConfigError::conflict(
+                "start_date",
+                "end_date",
+                "The sale start time must be before the sale end time",
+            ));
+        }
+
+        if self.min_deposit.0 == 0 {
+            errors.push(ConfigError::invalid(
+                "min_deposit",
+                "Minimum deposit must be greater than zero",
+            ));
+        }
+
         if self.total_sale_amount.0
             != self.sale_amount.0
                 + self.distribution_proportions.solver_allocation.0
@@ -96,17 +141,68 @@ impl LaunchpadConfig {
                     .iter()
                     .map(|s| s.allocation.0)
                     .sum::<u128>()
+                + self
+                    .distribution_proportions
+                    .liquidity
+                    .as_ref()
+                    .map_or(0, |liquidity| liquidity.sale_allocation.0)
         {
-            return Err(
+            errors.push(ConfigError::conflict(
+                "total_sale_amount",
+                "sale_amount",
                 "The Total sale amount must be equal to the sale amount plus solver allocation and distribution allocations",
+            ));
+        }
+
+        self.validate_soft_cap(&mut errors);
+        self.validate_discounts(&mut errors);
+        self.validate_stakeholders(&mut errors);
+
+        if let Some(deposit_distribution) = &self.distribution_proportions.deposits {
+            errors.extend(
+                deposit_distribution
+                    .validate_all()
+                    .into_iter()
+                    .map(|e| e.with_prefix("distribution_proportions.deposits")),
             );
         }
 
-        let discount_params = self.discounts.as_ref();
+        if let Some(liquidity) = &self.distribution_proportions.liquidity {
+            self.validate_liquidity(liquidity, &mut errors);
+        }
+
+        self.validate_vestings(&mut errors);
+
+        // Validate that TGE is after sale end time.
+        if self.tge.is_some_and(|tge| tge <= self.end_date) {
+            errors.push(ConfigError::conflict(
+                "tge",
+                "end_date",
+                "TGE must be after the sale end time",
+            ));
+        }
+
+        self.validate_bundled_sale_tokens(&mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(errors))
+        }
+    }
+
+    fn validate_discounts(&self, errors: &mut Vec<ConfigError>) {
+        let phases = self
+            .discounts
+            .as_ref()
+            .map_or(&[][..], |params| params.phases.as_slice());
 
         // Validate that all discount phases have unique IDs.
-        if !discount_params.is_none_or(|params| is_all_unique(params.phases.iter().map(|p| p.id))) {
-            return Err("All discount phase IDs must be unique");
+        for index in duplicates(phases.iter().map(|phase| phase.id)) {
+            errors.push(ConfigError::invalid(
+                format!("discounts.phases[{index}].id"),
+                "All discount phase IDs must be unique",
+            ));
         }
 
         if let Mechanics::FixedPrice {
@@ -114,61 +210,490 @@ impl LaunchpadConfig {
             sale_token,
         } = self.mechanics
         {
-            if deposit_token.0 == 0 || sale_token.0 == 0 {
-                return Err("Deposit and sale token amounts must be greater than zero");
+            for (field, amount) in [("deposit_token", deposit_token), ("sale_token", sale_token)] {
+                if amount.0 == 0 {
+                    errors.push(ConfigError::invalid(
+                        format!("mechanics.FixedPrice.{field}"),
+                        "Deposit and sale token amounts must be greater than zero",
+                    ));
+                }
             }
         } else {
             // Validate that discount phases have no limits for mechanics PriceDiscovery.
-            if discount_params.is_some_and(DiscountParams::has_limits) {
-                return Err("Discount phases shouldn't have limits for price discovery mechanics");
+            for (index, phase) in phases.iter().enumerate() {
+                if phase.phase_sale_limit.is_some()
+                    || phase.max_limit_per_account.is_some()
+                    || phase.min_limit_per_account.is_some()
+                {
+                    errors.push(ConfigError::conflict(
+                        format!("discounts.phases[{index}]"),
+                        "mechanics",
+                        "Discount phases shouldn't have limits for price discovery mechanics",
+                    ));
+                }
             }
         }
 
-        let stakeholders = self
-            .distribution_proportions
-            .stakeholder_proportions
-            .iter()
-            .map(|proportion| &proportion.account);
+        for index in 0..phases.len() {
+            self.validate_discount_phase(phases, index, errors);
+        }
 
-        if stakeholders
-            .clone()
-            .any(|account| account == &self.distribution_proportions.solver_account_id)
+        // The public sale may start at the end of the sale, i.e. there is no public sale at all.
+        if let Some(public_sale_start_time) = self
+            .discounts
+            .as_ref()
+            .and_then(|params| params.public_sale_start_time)
+        {
+            let other = if public_sale_start_time < self.start_date {
+                Some("start_date")
+            } else if public_sale_start_time > self.end_date {
+                Some("end_date")
+            } else {
+                None
+            };
+
+            if let Some(other) = other {
+                errors.push(ConfigError::conflict(
+                    "discounts.public_sale_start_time",
+                    other,
+                    "The public sale start time must be within the sale period",
+                ));
+            }
+        }
+    }
+
+    fn validate_discount_phase(
+        &self,
+        phases: &[DiscountPhase],
+        index: usize,
+        errors: &mut Vec<ConfigError>,
+    ) {
+        let phase = &phases[index];
+        let path = format!("discounts.phases[{index}]");
+
+        if phase.start_time >= phase.end_time {
+            errors.push(ConfigError::conflict(
+                format!("{path}.start_time"),
+                format!("{path}.end_time"),
+                "The discount phase start time must be before the phase end time",
+            ));
+        }
+
+        if phase.start_time < self.start_date {
+            errors.push(ConfigError::conflict(
+                format!("{path}.start_time"),
+                "start_date",
+                "Discount phases must be within the sale period",
+            ));
+        }
+
+        if phase.end_time > self.end_date {
+            errors.push(ConfigError::conflict(
+                format!("{path}.end_time"),
+                "end_date",
+                "Discount phases must be within the sale period",
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (phase.min_limit_per_account, phase.max_limit_per_account)
+            && min.0 > max.0
+        {
+            errors.push(ConfigError::conflict(
+                format!("{path}.min_limit_per_account"),
+                format!("{path}.max_limit_per_account"),
+                "The min limit per account must be less or equal than the max limit per account",
+            ));
+        }
+
+        if phase
+            .phase_sale_limit
+            .is_some_and(|limit| limit.0 > self.sale_amount.0)
         {
-            return Err("The solver account must not be a stakeholder");
+            errors.push(ConfigError::conflict(
+                format!("{path}.phase_sale_limit"),
+                "sale_amount",
+                "The phase sale limit must be less or equal than the sale amount",
+            ));
         }
 
-        if !is_all_unique(stakeholders) {
-            return Err("All stakeholders must have unique accounts");
+        // The unsold tokens can move to the following phases only, so the links can't form
+        // a cycle.
+        if let Some(id) = phase.remaining_go_to_phase_id {
+            match phases.iter().position(|other| other.id == id) {
+                None => errors.push(ConfigError::invalid(
+                    format!("{path}.remaining_go_to_phase_id"),
+                    "The unsold tokens must go to an existing discount phase",
+                )),
+                Some(position) if position <= index => errors.push(ConfigError::invalid(
+                    format!("{path}.remaining_go_to_phase_id"),
+                    "The unsold tokens must go to one of the following discount phases",
+                )),
+                Some(_) => {}
+            }
         }
+    }
 
-        // Validate that solver_percentage and fee_percentage do not exceed 100%
-        if let Some(deposit_distribution) = &self.distribution_proportions.deposits
-            && deposit_distribution.fee_percentage + deposit_distribution.solver_percentage > 10_000
+    fn validate_soft_cap(&self, errors: &mut Vec<ConfigError>) {
+        // Discounts only decrease the deposits needed to sell the whole sale amount, so the price
+        // without a discount gives the maximum of the deposits.
+        if let Mechanics::FixedPrice {
+            deposit_token,
+            sale_token,
+        } = self.mechanics
+            && !self.allow_unreachable_soft_cap
+            && U256::from(self.soft_cap.0) * U256::from(sale_token.0)
+                > U256::from(self.sale_amount.0) * U256::from(deposit_token.0)
         {
-            return Err(
-                "The sum of solver percentage and fee percentage shouldn't be greater than 10000 (100%)",
+            errors.push(ConfigError::conflict(
+                "soft_cap",
+                "sale_amount",
+                "The soft cap must be reachable by selling the sale amount at the fixed price",
+            ));
+        }
+    }
+
+    fn validate_vestings(&self, errors: &mut Vec<ConfigError>) {
+        let vestings = self
+            .vesting_schedule
+            .as_ref()
+            .map(|vesting| ("vesting_schedule".to_string(), vesting))
+            .into_iter()
+            .chain(
+                self.distribution_proportions
+                    .stakeholder_proportions
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, proportion)| {
+                        proportion.vesting.as_ref().map(|vesting| {
+                            (
+                                format!(
+                                    "distribution_proportions.stakeholder_proportions[{index}].vesting"
+                                ),
+                                vesting,
+                            )
+                        })
+                    }),
             );
+
+        for (path, vesting) in vestings {
+            if let Err(e) = vesting.validate_all() {
+                errors.extend(e.into_iter().map(|e| e.with_prefix(&path)));
+            }
         }
+    }
+
+    fn validate_stakeholders(&self, errors: &mut Vec<ConfigError>) {
+        let stakeholders = &self.distribution_proportions.stakeholder_proportions;
+
+        for (index, proportion) in stakeholders.iter().enumerate() {
+            if proportion.account == self.distribution_proportions.solver_account_id {
+                errors.push(ConfigError::conflict(
+                    format!("distribution_proportions.stakeholder_proportions[{index}].account"),
+                    "distribution_proportions.solver_account_id",
+                    "The solver account must not be a stakeholder",
+                ));
+            }
+        }
+
+        for index in duplicates(stakeholders.iter().map(|proportion| &proportion.account)) {
+            errors.push(ConfigError::invalid(
+                format!("distribution_proportions.stakeholder_proportions[{index}].account"),
+                "All stakeholders must have unique accounts",
+            ));
+        }
+    }
 
-        // Validate vesting schedules
-        self.vesting_schedule
+    fn validate_liquidity(&self, liquidity: &LiquidityProportion, errors: &mut Vec<ConfigError>) {
+        const PATH: &str = "distribution_proportions.liquidity";
+
+        if !matches!(self.deposit_token, DepositToken::Nep141(_))
+            || !matches!(self.sale_token, SaleToken::Nep141(_))
+        {
+            errors.push(ConfigError::invalid(
+                PATH,
+                "Liquidity seeding requires NEP-141 deposit and sale tokens",
+            ));
+        }
+
+        let distributed_percentage = self
+            .distribution_proportions
+            .deposits
             .as_ref()
-            .map_or(Ok(()), VestingSchedule::validate)?;
-        self.distribution_proportions
-            .stakeholder_proportions
-            .iter()
-            .filter_map(|p| p.vesting.as_ref())
-            .try_for_each(VestingSchedule::validate)?;
+            .map_or(0, DepositDistributionProportion::total_percentage);
 
-        // Validate that TGE is after sale end time.
-        if self.tge.is_some_and(|tge| tge <= self.end_date) {
-            return Err("TGE must be after the sale end time");
+        if u32::from(liquidity.deposit_percentage) + distributed_percentage > 10_000 {
+            errors.push(ConfigError::conflict(
+                format!("{PATH}.deposit_percentage"),
+                "distribution_proportions.deposits",
+                "The sum of liquidity, solver and fee percentages shouldn't be greater than 10000 (100%)",
+            ));
+        }
+
+        if liquidity.deposit_percentage == 0 {
+            errors.push(ConfigError::invalid(
+                format!("{PATH}.deposit_percentage"),
+                "Liquidity deposit percentage and sale allocation must be greater than zero",
+            ));
+        }
+
+        if liquidity.sale_allocation.0 == 0 {
+            errors.push(ConfigError::invalid(
+                format!("{PATH}.sale_allocation"),
+                "Liquidity deposit percentage and sale allocation must be greater than zero",
+            ));
+        }
+    }
+
+    fn validate_bundled_sale_tokens(&self, errors: &mut Vec<ConfigError>) {
+        if self.bundled_sale_tokens.len() > MAX_BUNDLED_SALE_TOKENS {
+            errors.push(ConfigError::invalid(
+                "bundled_sale_tokens",
+                format!(
+                    "The number of bundled sale tokens must not exceed {MAX_BUNDLED_SALE_TOKENS}"
+                ),
+            ));
+        }
+
+        for index in duplicates(
+            self.bundled_sale_tokens
+                .iter()
+                .map(|bundled| &bundled.token),
+        ) {
+            errors.push(ConfigError::invalid(
+                format!("bundled_sale_tokens[{index}].token"),
+                "All bundled sale tokens must be unique",
+            ));
+        }
+
+        for (index, bundled) in self.bundled_sale_tokens.iter().enumerate() {
+            let path = format!("bundled_sale_tokens[{index}]");
+
+            if bundled.token == self.sale_token {
+                errors.push(ConfigError::conflict(
+                    format!("{path}.token"),
+                    "sale_token",
+                    "The bundled sale token must differ from the sale token",
+                ));
+            }
+
+            if self.deposit_token.is_same(&bundled.token) {
+                errors.push(ConfigError::conflict(
+                    format!("{path}.token"),
+                    "deposit_token",
+                    "The bundled sale token must differ from the deposit token",
+                ));
+            }
+
+            if bundled.ratio.sale_token.0 == 0 || bundled.ratio.bundled_token.0 == 0 {
+                errors.push(ConfigError::invalid(
+                    format!("{path}.ratio"),
+                    "Bundled sale token ratio amounts must be greater than zero",
+                ));
+            } else {
+                match bundled.allocation(self.sale_amount.0) {
+                    Ok(allocation) if allocation != bundled.total_sale_amount.0 => {
+                        errors.push(ConfigError::conflict(
+                            format!("{path}.total_sale_amount"),
+                            "sale_amount",
+                            "The total sale amount of the bundled sale token must be equal to the sale amount in the ratio",
+                        ));
+                    }
+                    Ok(_) => {}
+                    Err(reason) => {
+                        errors.push(ConfigError::invalid(format!("{path}.ratio"), reason))
+                    }
+                }
+            }
+
+            if let Some(Err(e)) = bundled
+                .vesting_schedule
+                .as_ref()
+                .map(VestingSchedule::validate_all)
+            {
+                let prefix = format!("{path}.vesting_schedule");
+                errors.extend(e.into_iter().map(|e| e.with_prefix(&prefix)));
+            }
+        }
+    }
+}
+
+/// A violation of the config rules with the path to the wrong field, e.g.
+/// `distribution_proportions.stakeholder_proportions[2].vesting`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConfigError {
+    /// The field has a wrong value.
+    InvalidField {
+        path: String,
+        reason: Cow<'static, str>,
+    },
+    /// The field conflicts with another field of the config.
+    Conflict {
+        path: String,
+        other: String,
+        reason: Cow<'static, str>,
+    },
+}
+
+impl ConfigError {
+    #[must_use]
+    pub fn invalid(path: impl Into<String>, reason: impl Into<Cow<'static, str>>) -> Self {
+        Self::InvalidField {
+            path: path.into(),
+            reason: reason.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn conflict(
+        path: impl Into<String>,
+        other: impl Into<String>,
+        reason: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self::Conflict {
+            path: path.into(),
+            other: other.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Returns the path to the wrong field.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Self::InvalidField { path, .. } | Self::Conflict { path, .. } => path,
+        }
+    }
+
+    /// Returns the description of the violated rule.
+    #[must_use]
+    pub fn reason(&self) -> &str {
+        match self {
+            Self::InvalidField { reason, .. } | Self::Conflict { reason, .. } => reason,
+        }
+    }
+
+    /// Prepends the path of the parent field to the paths of the error.
+    #[must_use]
+    pub fn with_prefix(self, prefix: &str) -> Self {
+        let join = |path: String| {
+            if path.is_empty() {
+                prefix.to_string()
+            } else {
+                format!("{prefix}.{path}")
+            }
+        };
+
+        match self {
+            Self::InvalidField { path, reason } => Self::InvalidField {
+                path: join(path),
+                reason,
+            },
+            Self::Conflict {
+                path,
+                other,
+                reason,
+            } => Self::Conflict {
+                path: join(path),
+                other: join(other),
+                reason,
+            },
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidField { path, reason } => write!(f, "{reason} [{path}]"),
+            Self::Conflict {
+                path,
+                other,
+                reason,
+            } => write!(f, "{reason} [{path}, {other}]"),
+        }
+    }
+}
+
+/// All violations of the config rules. They are displayed separated by semicolons.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ConfigErrors(pub Vec<ConfigError>);
+
+impl Deref for ConfigErrors {
+    type Target = [ConfigError];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for ConfigErrors {
+    type Item = ConfigError;
+    type IntoIter = std::vec::IntoIter<ConfigError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl Display for ConfigErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str("; ")?;
+            }
+
+            write!(f, "{error}")?;
         }
 
         Ok(())
     }
 }
 
+/// The maximum number of sale tokens bundled with the main sale token.
+pub const MAX_BUNDLED_SALE_TOKENS: usize = 3;
+
+/// A sale token sold together with the main sale token. Participants receive the bundled token
+/// in a fixed ratio to their allocations of the main sale token. The solver and stakeholders
+/// receive the main sale token only.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[near(serializers = [borsh, json])]
+pub struct BundledSaleToken {
+    /// The NEP-141 or NEP-245 token.
+    pub token: SaleToken,
+    /// The number of tokens transferred to the launchpad. It must be equal to the sale amount
+    /// converted in the ratio.
+    pub total_sale_amount: U128,
+    /// The ratio of the token to the main sale token.
+    pub ratio: BundleRatio,
+    /// An optional vesting schedule of the token. It starts from the same point as the vesting
+    /// of the main sale token.
+    pub vesting_schedule: Option<VestingSchedule>,
+}
+
+impl BundledSaleToken {
+    /// Converts the allocation of the main sale token to the allocation of the bundled token.
+    pub fn allocation(&self, sale_allocation: u128) -> Result<u128, &'static str> {
+        U256::from(sale_allocation)
+            .checked_mul(U256::from(self.ratio.bundled_token.0))
+            .ok_or("Multiplication overflow")
+            .and_then(|result| {
+                result
+                    .checked_div(U256::from(self.ratio.sale_token.0))
+                    .ok_or("Division by zero")
+            })
+            .and_then(to_u128)
+    }
+}
+
+/// Represents a ratio as a fraction of the main sale token and the bundled token: participants
+/// receive `bundled_token` of the bundled token for every `sale_token` of the main sale token.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[near(serializers = [borsh, json])]
+pub struct BundleRatio {
+    pub sale_token: U128,
+    pub bundled_token: U128,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 #[near(serializers = [borsh, json])]
 pub enum Mechanics {
@@ -180,36 +705,147 @@ pub enum Mechanics {
     PriceDiscovery,
 }
 
-/// Deposit tokens distribution proportion configuration.
+/// The maximum number of recipients of the deposit tokens besides the solver.
+pub const MAX_DEPOSIT_RECIPIENTS: usize = 5;
+
+/// Deposit tokens distribution proportion configuration. The `fee_account` and
+/// `fee_percentage` of the configs of 0.7.x are accepted as the first recipient.
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[near(serializers = [borsh, json])]
+#[serde(from = "DepositDistributionProportionInput")]
 pub struct DepositDistributionProportion {
-    /// Percentage of the deposited funds to be sent to the solver account.
+    /// Percentage of the deposited funds to be sent to the solver account in basis points.
+    /// `10000 = 100%`
     pub solver_percentage: u16,
-    /// Intents (only) account to receive a fee percentage of the deposited funds.
-    pub fee_account: IntentsAccount,
-    /// Percentage of the deposited funds to be sent to the fee account.
+    /// Recipients of the deposited funds besides the solver, e.g. the protocol fee, partners,
+    /// the issuer treasury or market makers.
+    pub recipients: Vec<DepositRecipient>,
+}
+
+/// A recipient of a percentage of the deposited funds.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[near(serializers = [borsh, json])]
+pub struct DepositRecipient {
+    /// NEAR or intents account of the recipient.
+    pub account: DistributionAccount,
+    /// Percentage of the deposited funds to be sent to the recipient in basis points.
     /// `10000 = 100%`
-    pub fee_percentage: u16,
+    pub percentage: u16,
+}
+
+/// The JSON input of `DepositDistributionProportion`, which accepts the single fee recipient
+/// of the configs of 0.7.x.
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct DepositDistributionProportionInput {
+    solver_percentage: u16,
+    #[serde(default)]
+    recipients: Vec<DepositRecipient>,
+    fee_account: Option<IntentsAccount>,
+    #[serde(default)]
+    fee_percentage: u16,
+}
+
+impl From<DepositDistributionProportionInput> for DepositDistributionProportion {
+    fn from(input: DepositDistributionProportionInput) -> Self {
+        let fee_recipient = input.fee_account.map(|account| DepositRecipient {
+            account: account.into(),
+            percentage: input.fee_percentage,
+        });
+
+        Self {
+            solver_percentage: input.solver_percentage,
+            recipients: fee_recipient.into_iter().chain(input.recipients).collect(),
+        }
+    }
 }
 
 impl DepositDistributionProportion {
     /// Calculates the proportions of the total amount to be distributed to the solver
-    /// and fee accounts.
-    pub fn calculate_proportions(&self, total_amount: u128) -> Result<(u128, u128), &'static str> {
-        let solver_amount = total_amount
-            .checked_mul(u128::from(self.solver_percentage))
-            .and_then(|v| v.checked_div(10_000))
-            .ok_or("Error while calculate solver proportion")?;
-        let fee_amount = total_amount
-            .checked_mul(u128::from(self.fee_percentage))
-            .and_then(|v| v.checked_div(10_000))
-            .ok_or("Error while calculate fee proportion")?;
+    /// and to every recipient in the order of the configuration.
+    pub fn calculate_proportions(
+        &self,
+        total_amount: u128,
+    ) -> Result<(u128, Vec<u128>), &'static str> {
+        let proportion = |percentage: u16| {
+            total_amount
+                .checked_mul(u128::from(percentage))
+                .and_then(|v| v.checked_div(10_000))
+        };
+
+        let solver_amount =
+            proportion(self.solver_percentage).ok_or("Error while calculate solver proportion")?;
+        let recipient_amounts = self
+            .recipients
+            .iter()
+            .map(|recipient| {
+                proportion(recipient.percentage).ok_or("Error while calculate recipient proportion")
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok((solver_amount, recipient_amounts))
+    }
+
+    /// Returns the sum of the solver and recipients percentages.
+    #[must_use]
+    pub fn total_percentage(&self) -> u32 {
+        self.recipients
+            .iter()
+            .map(|recipient| u32::from(recipient.percentage))
+            .sum::<u32>()
+            + u32::from(self.solver_percentage)
+    }
+
+    fn validate_all(&self) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+
+        if self.total_percentage() > 10_000 {
+            errors.push(ConfigError::invalid(
+                "",
+                "The sum of solver percentage and recipients percentages shouldn't be greater than 10000 (100%)",
+            ));
+        }
+
+        if self.recipients.len() > MAX_DEPOSIT_RECIPIENTS {
+            errors.push(ConfigError::invalid(
+                "recipients",
+                format!(
+                    "The number of deposit recipients must not exceed {MAX_DEPOSIT_RECIPIENTS}"
+                ),
+            ));
+        }
+
+        for index in duplicates(self.recipients.iter().map(|recipient| &recipient.account)) {
+            errors.push(ConfigError::invalid(
+                format!("recipients[{index}].account"),
+                "All deposit recipients must have unique accounts",
+            ));
+        }
 
-        Ok((solver_amount, fee_amount))
+        errors
     }
 }
 
+/// Liquidity seeding configuration. A new pool of the deposit and sale tokens is created on
+/// a Ref-Finance-style DEX, and a part of the deposited tokens is added to the pool together
+/// with the sale tokens at the sale price.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[near(serializers = [borsh, json])]
+pub struct LiquidityProportion {
+    /// The account of the DEX contract.
+    pub dex_account_id: AccountId,
+    /// The fee of the created pool in basis points.
+    pub pool_fee: u32,
+    /// Percentage of the deposited funds to be added to the pool.
+    /// `10000 = 100%`
+    pub deposit_percentage: u16,
+    /// The maximum number of sale tokens added to the pool. The tokens are a part of the total
+    /// sale amount, and the tokens which aren't added to the pool are considered unsold.
+    pub sale_allocation: U128,
+    /// The account receiving the LP shares. The shares are locked on the launchpad if missing.
+    pub shares_receiver_id: Option<AccountId>,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 #[near(serializers = [borsh, json])]
 pub struct DistributionProportions {
@@ -223,6 +859,10 @@ pub struct DistributionProportions {
     pub stakeholder_proportions: Vec<StakeholderProportion>,
     /// An optional configuration for distribution deposit tokens between solver and fee account.
     pub deposits: Option<DepositDistributionProportion>,
+    /// An optional configuration for seeding a DEX pool with a part of the deposited tokens and
+    /// the sale tokens.
+    #[serde(default)]
+    pub liquidity: Option<LiquidityProportion>,
 }
 
 impl DistributionProportions {
@@ -422,18 +1062,119 @@ impl VestingSchedule {
     ///
     /// # Errors
     /// Returns an error if any of the validation rules are violated.
-    pub fn validate(&self) -> Result<(), &'static str> {
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_all().map_err(|errors| {
+            errors
+                .first()
+                .map_or("Invalid vesting schedule", ConfigError::reason)
+                .to_string()
+        })
+    }
+
+    /// Validates the vesting schedule and returns all violations of the rules with the paths
+    /// relative to the schedule.
+    pub fn validate_all(&self) -> Result<(), ConfigErrors> {
+        let mut errors = Vec::new();
+
         if self.cliff_period > self.vesting_period {
-            return Err("Vesting cliff period must be less or equal than vesting period");
+            errors.push(ConfigError::conflict(
+                "cliff_period",
+                "vesting_period",
+                "Vesting cliff period must be less or equal than vesting period",
+            ));
         }
 
         if let Some(percentage) = self.instant_claim_percentage
             && percentage > 10_000
         {
-            return Err("Vesting instant claim percentage cannot exceed 10000 (100%)");
+            errors.push(ConfigError::invalid(
+                "instant_claim_percentage",
+                "Vesting instant claim percentage cannot exceed 10000 (100%)",
+            ));
         }
 
-        Ok(())
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErrors(errors))
+        }
+    }
+
+    /// Returns the number of tokens of the allocation unlocked at the given time if the vesting
+    /// starts at `vesting_start`.
+    pub fn unlocked_amount(
+        &self,
+        allocation: u128,
+        vesting_start: u64,
+        timestamp: u64,
+    ) -> Result<u128, &'static str> {
+        let after_cliff_start = vesting_start + self.cliff_period.as_nanos();
+        let instant_claim = self.get_instant_claim_amount(allocation)?;
+
+        if timestamp < after_cliff_start {
+            return Ok(instant_claim);
+        } else if timestamp >= vesting_start + self.vesting_period.as_nanos() {
+            return Ok(allocation);
+        }
+
+        let (claim_increasing_start, distribution_period) = match self.vesting_scheme {
+            VestingScheme::Immediate => (vesting_start, self.vesting_period.as_nanos()),
+            VestingScheme::AfterCliff => (
+                after_cliff_start,
+                self.vesting_period.as_nanos() - self.cliff_period.as_nanos(),
+            ),
+        };
+
+        let elapsed = timestamp.saturating_sub(claim_increasing_start);
+
+        U256::from(
+            allocation
+                .checked_sub(instant_claim)
+                .ok_or("Instant claim is more than total allocation")?,
+        )
+        .checked_mul(U256::from(elapsed))
+        .ok_or("Multiplication overflow")
+        .and_then(|result| {
+            result
+                .checked_div(U256::from(distribution_period))
+                .ok_or("Division by zero")
+        })
+        .and_then(to_u128)
+        .and_then(|v| v.checked_add(instant_claim).ok_or("Addition overflow"))
+    }
+
+    /// Returns the start of the vesting, the end of the cliff and the times dividing the rest of
+    /// the vesting period into the given number of equal steps. A step is one nanosecond at least.
+    #[must_use]
+    pub fn unlock_timestamps(&self, vesting_start: u64, steps: u64) -> Vec<u64> {
+        let cliff_end = vesting_start + self.cliff_period.as_nanos();
+        let vesting_end = vesting_start + self.vesting_period.as_nanos();
+        let steps = steps.clamp(1, (vesting_end - cliff_end).max(1));
+        let step = (vesting_end - cliff_end) / steps;
+        let mut timestamps = vec![vesting_start, cliff_end];
+
+        timestamps.extend((1..steps).map(|i| cliff_end + step * i));
+        timestamps.push(vesting_end);
+        timestamps.dedup();
+        timestamps
+    }
+
+    /// Returns the time after the given one when the next tokens are unlocked if the vesting
+    /// starts at `vesting_start`, or `None` if all tokens are already unlocked. The tokens are
+    /// unlocked gradually after the cliff, so the next unlock is the given time itself then.
+    #[must_use]
+    pub fn next_unlock(&self, vesting_start: u64, timestamp: u64) -> Option<u64> {
+        let after_cliff_start = vesting_start + self.cliff_period.as_nanos();
+
+        if timestamp < vesting_start && self.instant_claim_percentage.is_some_and(|p| p > 0) {
+            Some(vesting_start)
+        } else if timestamp < after_cliff_start {
+            Some(after_cliff_start)
+        } else if timestamp < vesting_start + self.vesting_period.as_nanos() {
+            Some(timestamp)
+        } else {
+            None
+        }
     }
 }
 
@@ -447,6 +1188,7 @@ pub enum LaunchpadStatus {
     Success,
     Failed,
     Locked,
+    Archived,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -456,8 +1198,55 @@ pub enum DepositToken {
     Nep245((AccountId, TokenId)),
 }
 
+impl DepositToken {
+    /// Returns `true` if the deposit token is the same token as the sale token.
+    #[must_use]
+    pub fn is_same(&self, sale_token: &SaleToken) -> bool {
+        match (self, sale_token) {
+            (Self::Nep141(deposit), SaleToken::Nep141(sale)) => deposit == sale,
+            (Self::Nep245(deposit), SaleToken::Nep245(sale)) => deposit == sale,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone)]
+#[near(serializers = [borsh, json])]
+pub enum SaleToken {
+    Nep141(AccountId),
+    Nep245((AccountId, TokenId)),
+}
+
+impl SaleToken {
+    /// Returns the account of the token contract.
+    #[must_use]
+    pub const fn account_id(&self) -> &AccountId {
+        match self {
+            Self::Nep141(account_id) | Self::Nep245((account_id, _)) => account_id,
+        }
+    }
+}
+
 pub type TokenId = String;
 
+/// Deserializes the sale token, or the NEP-141 sale token from its account id.
+fn deserialize_sale_token<'de, D>(deserializer: D) -> Result<SaleToken, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(crate = "near_sdk::serde", untagged)]
+    enum SaleTokenInput {
+        SaleToken(SaleToken),
+        AccountId(AccountId),
+    }
+
+    Ok(match SaleTokenInput::deserialize(deserializer)? {
+        SaleTokenInput::SaleToken(sale_token) => sale_token,
+        SaleTokenInput::AccountId(account_id) => SaleToken::Nep141(account_id),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::{
@@ -474,7 +1263,9 @@ mod tests {
                 "Nep141": "17208628f84f5d6ad33f0da3bbbeb27ffcb398eac501a31bd6ad2011e36133a1"
               },
               "min_deposit": "100000",
-              "sale_token_account_id": "stjack.tkn.primitives.near",
+              "sale_token": {
+                "Nep141": "stjack.tkn.primitives.near"
+              },
               "intents_account_id": "intents.near",
               "start_date": "2025-05-04T12:00:00Z",
               "end_date": "2025-06-04T12:00:00Z",
@@ -550,6 +1341,10 @@ mod tests {
                     .unwrap()
             )
         );
+        assert_eq!(
+            config.sale_token,
+            super::SaleToken::Nep141("stjack.tkn.primitives.near".parse().unwrap())
+        );
 
         assert_eq!(
             config.distribution_proportions.solver_account_id,
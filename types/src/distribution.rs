@@ -1,13 +1,36 @@
+use near_sdk::json_types::U128;
 use near_sdk::near;
 
 /// Distribution of deposit tokens for solver and designated accounts.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 #[near(serializers = [borsh, json])]
 pub struct DepositsDistribution {
     /// Number of distributed tokens to the solver account.
     pub solver_amount: u128,
-    /// Number of distributed tokens to the designated account.
-    pub fee_amount: u128,
+    /// Numbers of distributed tokens to the recipients in the order of the configuration.
+    pub recipient_amounts: Vec<u128>,
     /// Status of the distribution.
     pub is_ongoing: bool,
 }
+
+/// Seeding of the DEX pool with the deposit and sale tokens.
+#[derive(Debug, Default, Clone)]
+#[near(serializers = [borsh, json])]
+pub struct LiquiditySeeding {
+    /// The id of the pool created on the DEX.
+    pub pool_id: Option<u64>,
+    /// Number of deposit tokens transferred to the DEX.
+    pub deposit_amount: U128,
+    /// Number of sale tokens transferred to the DEX.
+    pub sale_amount: U128,
+    /// Number of LP shares received for the added liquidity.
+    pub shares: U128,
+    /// Whether the liquidity is added to the pool.
+    pub is_added: bool,
+    /// Whether the LP shares are transferred to the shares receiver.
+    pub is_shares_transferred: bool,
+    /// Status of the seeding.
+    pub is_ongoing: bool,
+    /// The reason of the last failed step, if any.
+    pub last_failure: Option<String>,
+}
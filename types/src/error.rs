@@ -0,0 +1,263 @@
+use std::fmt::{Display, Formatter};
+
+/// The prefix of the error code in the panic messages of the launchpad.
+const CODE_PREFIX: &str = "ERR_";
+
+/// Errors of the launchpad contract with stable numeric codes. The contract panics with
+/// the `ERR_<CODE>: message` messages, e.g. `ERR_2001: Launchpad is not ongoing`. The codes
+/// never change, while the messages could be changed or contain the details of the failure.
+///
+/// The codes are grouped by the area: `1xxx` - general errors, `2xxx` - deposits, `3xxx` -
+/// withdrawals, `4xxx` - claims, `5xxx` - distributions, `6xxx` - admin transactions,
+/// `7xxx` - storage management, `8xxx` - cleanup and liquidity seeding.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[repr(u16)]
+pub enum LaunchpadError {
+    InvalidConfig = 1001,
+    AlreadyInitialized = 1002,
+    UnsupportedStateVersion = 1003,
+    StateReadFailed = 1004,
+    PromiseResultsCount = 1005,
+    Overflow = 1006,
+    Internal = 1007,
+    NoDeposit = 1008,
+    WrongTotalSaleAmount = 1009,
+    BundledTokenAlreadyFunded = 1010,
+
+    NotOngoing = 2001,
+    DepositTooLow = 2002,
+    UnsupportedDepositToken = 2003,
+    InvalidTokenArrays = 2004,
+    InvalidDepositMessage = 2005,
+    InvalidRefund = 2006,
+
+    WithdrawInProgress = 3001,
+    WithdrawNotAllowed = 3002,
+    NoIntents = 3003,
+    TooManyIntents = 3004,
+    IntentVerificationFailed = 3005,
+    WithdrawAmountTooHigh = 3006,
+    WithdrawFailed = 3007,
+
+    ClaimNotAllowed = 4001,
+    NothingToClaim = 4002,
+    ClaimFailed = 4003,
+    NoStakeholderProportion = 4004,
+    RegistrationNotRequired = 4005,
+
+    DistributionNotAllowed = 5001,
+    WrongBatchSize = 5002,
+    AlreadyDistributed = 5003,
+    DistributionOngoing = 5004,
+    DistributionFailed = 5005,
+
+    LockNotAllowed = 6001,
+    NotLocked = 6002,
+    TgeUpdateNotAllowed = 6003,
+    InvalidTge = 6004,
+    AdminWithdrawNotAllowed = 6005,
+    AdminWithdrawOngoing = 6006,
+    UnsoldAmountExceeded = 6007,
+    BundledTokenNotFound = 6008,
+    PhaseNotFound = 6009,
+    WhitelistNotFound = 6010,
+
+    NotRegistered = 7001,
+    StorageDepositTooLow = 7002,
+    StorageWithdrawTooHigh = 7003,
+    DepositTooLowForStorageFee = 7004,
+    StorageFeesExceeded = 7005,
+
+    CleanupNotAllowed = 8001,
+    NothingToWithdraw = 8002,
+    NearWithdrawTooHigh = 8003,
+    LiquidityNotAllowed = 8004,
+    LiquidityNotConfigured = 8005,
+    LiquidityOngoing = 8006,
+    NotEnoughNearForPool = 8007,
+    LiquidityAlreadySeeded = 8008,
+    LiquidityUnsupportedTokens = 8009,
+    LiquidityFailed = 8010,
+}
+
+impl LaunchpadError {
+    /// All errors in the order of the codes.
+    pub const ALL: &[Self] = &[
+        Self::InvalidConfig,
+        Self::AlreadyInitialized,
+        Self::UnsupportedStateVersion,
+        Self::StateReadFailed,
+        Self::PromiseResultsCount,
+        Self::Overflow,
+        Self::Internal,
+        Self::NoDeposit,
+        Self::WrongTotalSaleAmount,
+        Self::BundledTokenAlreadyFunded,
+        Self::NotOngoing,
+        Self::DepositTooLow,
+        Self::UnsupportedDepositToken,
+        Self::InvalidTokenArrays,
+        Self::InvalidDepositMessage,
+        Self::InvalidRefund,
+        Self::WithdrawInProgress,
+        Self::WithdrawNotAllowed,
+        Self::NoIntents,
+        Self::TooManyIntents,
+        Self::IntentVerificationFailed,
+        Self::WithdrawAmountTooHigh,
+        Self::WithdrawFailed,
+        Self::ClaimNotAllowed,
+        Self::NothingToClaim,
+        Self::ClaimFailed,
+        Self::NoStakeholderProportion,
+        Self::RegistrationNotRequired,
+        Self::DistributionNotAllowed,
+        Self::WrongBatchSize,
+        Self::AlreadyDistributed,
+        Self::DistributionOngoing,
+        Self::DistributionFailed,
+        Self::LockNotAllowed,
+        Self::NotLocked,
+        Self::TgeUpdateNotAllowed,
+        Self::InvalidTge,
+        Self::AdminWithdrawNotAllowed,
+        Self::AdminWithdrawOngoing,
+        Self::UnsoldAmountExceeded,
+        Self::BundledTokenNotFound,
+        Self::PhaseNotFound,
+        Self::WhitelistNotFound,
+        Self::NotRegistered,
+        Self::StorageDepositTooLow,
+        Self::StorageWithdrawTooHigh,
+        Self::DepositTooLowForStorageFee,
+        Self::StorageFeesExceeded,
+        Self::CleanupNotAllowed,
+        Self::NothingToWithdraw,
+        Self::NearWithdrawTooHigh,
+        Self::LiquidityNotAllowed,
+        Self::LiquidityNotConfigured,
+        Self::LiquidityOngoing,
+        Self::NotEnoughNearForPool,
+        Self::LiquidityAlreadySeeded,
+        Self::LiquidityUnsupportedTokens,
+        Self::LiquidityFailed,
+    ];
+
+    /// Returns the stable numeric code of the error.
+    #[must_use]
+    pub const fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Returns the error with the given code.
+    #[must_use]
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|error| error.code() == code)
+    }
+
+    /// Returns the default message of the error.
+    #[must_use]
+    pub const fn message(self) -> &'static str {
+        match self {
+            Self::InvalidConfig => "Invalid config",
+            Self::AlreadyInitialized => "The contract is already initialized",
+            Self::UnsupportedStateVersion => "Unsupported state version",
+            Self::StateReadFailed => "Failed to read the contract state",
+            Self::PromiseResultsCount => "Expected one promise result",
+            Self::Overflow => "Arithmetic overflow",
+            Self::Internal => "Internal error",
+            Self::NoDeposit => "No deposit was found for the intents account",
+            Self::WrongTotalSaleAmount => "Wrong total sale amount",
+            Self::BundledTokenAlreadyFunded => "The bundled sale token is already funded",
+            Self::NotOngoing => "Launchpad is not ongoing",
+            Self::DepositTooLow => "Deposit amount is too low",
+            Self::UnsupportedDepositToken => "Unsupported NEP-141 token",
+            Self::InvalidTokenArrays => "Arrays must have length 1 for a single token deposit",
+            Self::InvalidDepositMessage => "Failed to parse an account from msg",
+            Self::InvalidRefund => "Wrong refund amount",
+            Self::WithdrawInProgress => "Withdraw is still in progress",
+            Self::WithdrawNotAllowed => "Withdraw is not allowed",
+            Self::NoIntents => "No intent provided",
+            Self::TooManyIntents => "Too much intent provided",
+            Self::IntentVerificationFailed => "Intent verification failed",
+            Self::WithdrawAmountTooHigh => "Withdraw amount is greater than the deposit amount",
+            Self::WithdrawFailed => "Withdraw failed",
+            Self::ClaimNotAllowed => {
+                "Claim can be called only if the launchpad finishes with success status"
+            }
+            Self::NothingToClaim => "No assets to claim",
+            Self::ClaimFailed => "Claim failed",
+            Self::NoStakeholderProportion => "No proportion was found for the account",
+            Self::RegistrationNotRequired => "Only NEAR accounts require registration",
+            Self::DistributionNotAllowed => {
+                "Distribution can be called only if the launchpad finishes with success status"
+            }
+            Self::WrongBatchSize => "Wrong batch size",
+            Self::AlreadyDistributed => "Tokens have been already distributed",
+            Self::DistributionOngoing => "Deposit distribution is ongoing",
+            Self::DistributionFailed => "Distribution failed",
+            Self::LockNotAllowed => {
+                "The contract can only be locked when status is NotStarted, Ongoing, or PreTGE"
+            }
+            Self::NotLocked => "The contract is not locked",
+            Self::TgeUpdateNotAllowed => "Wrong status of the contract for the TGE update",
+            Self::InvalidTge => "TGE must be after the end of the sale and in the future",
+            Self::AdminWithdrawNotAllowed => "Tokens couldn't be withdrawn in the current status",
+            Self::AdminWithdrawOngoing => "Withdrawal is already ongoing",
+            Self::UnsoldAmountExceeded => {
+                "The amount is greater than the available number of unsold tokens"
+            }
+            Self::BundledTokenNotFound => "No bundled sale token was found",
+            Self::PhaseNotFound => "Discount phase not found",
+            Self::WhitelistNotFound => "Whitelist is not initialized",
+            Self::NotRegistered => "The account is not registered",
+            Self::StorageDepositTooLow => {
+                "The attached deposit is less than the minimum storage balance"
+            }
+            Self::StorageWithdrawTooHigh => {
+                "The amount is greater than the available storage balance"
+            }
+            Self::DepositTooLowForStorageFee => "Deposit amount is too low to pay the storage fee",
+            Self::StorageFeesExceeded => "The amount is greater than the collected storage fees",
+            Self::CleanupNotAllowed => "Cleanup is allowed after the sale has concluded only",
+            Self::NothingToWithdraw => "Nothing to withdraw",
+            Self::NearWithdrawTooHigh => "The amount is greater than the withdrawable NEAR balance",
+            Self::LiquidityNotAllowed => "Liquidity could be seeded after success only",
+            Self::LiquidityNotConfigured => "Liquidity seeding is not configured",
+            Self::LiquidityOngoing => "Liquidity seeding is ongoing",
+            Self::NotEnoughNearForPool => "Not enough NEAR attached for the pool creation",
+            Self::LiquidityAlreadySeeded => "The liquidity has been already seeded",
+            Self::LiquidityUnsupportedTokens => {
+                "Liquidity seeding requires NEP-141 deposit and sale tokens"
+            }
+            Self::LiquidityFailed => "Liquidity seeding failed",
+        }
+    }
+
+    /// Formats the panic message of the error with the given message instead of the default
+    /// one, e.g. with the details of the failure.
+    #[must_use]
+    pub fn with_message(self, message: &str) -> String {
+        format!("{CODE_PREFIX}{}: {message}", self.code())
+    }
+
+    /// Parses the error and its message from the panic message of the launchpad. The panic
+    /// message could be wrapped by the client, e.g. `Smart contract panicked: ERR_2001: ...`.
+    /// Returns `None` if the message contains no known error code.
+    #[must_use]
+    pub fn parse(panic_message: &str) -> Option<(Self, &str)> {
+        let (_, error) = panic_message.split_once(CODE_PREFIX)?;
+        let (code, message) = error.split_once(": ")?;
+
+        code.parse()
+            .ok()
+            .and_then(Self::from_code)
+            .map(|error| (error, message))
+    }
+}
+
+impl Display for LaunchpadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{CODE_PREFIX}{}: {}", self.code(), self.message())
+    }
+}
@@ -3,6 +3,7 @@
 use near_sdk::{AccountId, near};
 use std::fmt::{Display, Formatter};
 
+pub mod account;
 pub mod admin_withdraw;
 pub mod config;
 pub mod date_time;
@@ -10,6 +11,9 @@ pub mod date_time_opt;
 pub mod discount;
 pub mod distribution;
 pub mod duration;
+pub mod error;
+pub mod simulation;
+pub mod stats;
 #[cfg(test)]
 mod tests;
 pub mod utils;
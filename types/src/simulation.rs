@@ -0,0 +1,76 @@
+use near_sdk::json_types::U128;
+use near_sdk::near;
+
+use crate::discount::DepositDistribution;
+
+/// Distribution of the deposit between the discount phases and the public sale with the amounts
+/// serialized as strings.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub enum DepositDistributionView {
+    /// The number of deposit tokens including discount for every phase.
+    WithDiscount {
+        phase_weights: Vec<(u16, U128)>,
+        public_sale_weight: U128,
+        refund: U128,
+    },
+    /// The number of deposit tokens that were sold during the public sale without a discount.
+    WithoutDiscount(U128),
+    /// As there are no suitable discounts or public sale available, refund the full deposit.
+    Refund(U128),
+}
+
+impl From<DepositDistribution> for DepositDistributionView {
+    fn from(distribution: DepositDistribution) -> Self {
+        match distribution {
+            DepositDistribution::WithDiscount {
+                phase_weights,
+                public_sale_weight,
+                refund,
+            } => Self::WithDiscount {
+                phase_weights: phase_weights
+                    .into_iter()
+                    .map(|(id, weight)| (id, U128(weight)))
+                    .collect(),
+                public_sale_weight: U128(public_sale_weight),
+                refund: U128(refund),
+            },
+            DepositDistribution::WithoutDiscount(weight) => Self::WithoutDiscount(U128(weight)),
+            DepositDistribution::Refund(refund) => Self::Refund(U128(refund)),
+        }
+    }
+}
+
+/// The outcome of a deposit, calculated without changing the state of the launchpad.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct DepositSimulation {
+    /// Distribution of the deposit between the discount phases and the public sale.
+    pub distribution: DepositDistributionView,
+    /// Storage fee charged from the deposit of a new participant.
+    pub storage_fee: U128,
+    /// Number of deposit tokens accepted by the launchpad.
+    pub deposit: U128,
+    /// Number of deposit tokens returned to the account. The storage fee is returned as well
+    /// if the whole deposit is refunded.
+    pub refund: U128,
+    /// Total weight of the account after the deposit.
+    pub weight: U128,
+    /// Estimated number of sale tokens allocated to the account after the deposit. In the case
+    /// of `PriceDiscovery`, the allocation changes with every following deposit or withdrawal.
+    pub allocation: U128,
+}
+
+/// The outcome of a withdrawal, calculated without changing the state of the launchpad.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct WithdrawSimulation {
+    /// Distribution of the remaining deposit between the discount phases and the public sale.
+    pub distribution: DepositDistributionView,
+    /// Number of deposit tokens remaining on the account after the withdrawal.
+    pub deposit: U128,
+    /// Total weight of the account after the withdrawal.
+    pub weight: U128,
+    /// Estimated number of sale tokens allocated to the account after the withdrawal.
+    pub allocation: U128,
+}
@@ -0,0 +1,71 @@
+use near_sdk::json_types::U128;
+use near_sdk::near;
+
+use crate::config::LaunchpadStatus;
+
+/// Global statistics of the sale.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct SaleStats {
+    /// The current status of the launchpad.
+    pub status: LaunchpadStatus,
+    /// Number of unique participants.
+    pub participants_count: u64,
+    /// Total number of deposited tokens.
+    pub total_deposited: U128,
+    /// Total number of sold sale tokens. In the case of `PriceDiscovery`, it's the total weight
+    /// of the deposits.
+    pub total_sold_tokens: U128,
+    /// Number of sale tokens used for the sale.
+    pub sale_amount: U128,
+    /// Number of sale tokens which could still be sold. In the case of `PriceDiscovery`,
+    /// the whole sale amount is distributed between the participants, so nothing remains
+    /// after the first deposit.
+    pub remaining_sale_tokens: U128,
+    /// The threshold of deposited tokens needed to conclude the sale successfully.
+    pub soft_cap: U128,
+    /// Progress of reaching the soft cap in basis points, `10000 = 100%`. The value doesn't
+    /// exceed `10000`.
+    pub soft_cap_progress: u16,
+    /// The price of the sale token: fixed for `FixedPrice` and implied by the total deposit for
+    /// `PriceDiscovery`. It's `None` if nothing is deposited in the case of `PriceDiscovery`.
+    pub price: Option<Price>,
+}
+
+/// The price of the sale token as a ratio of the deposit and sale token amounts.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct Price {
+    /// Number of deposit tokens.
+    pub deposit_token: U128,
+    /// Number of sale tokens which could be bought for the number of deposit tokens.
+    pub sale_token: U128,
+}
+
+/// Progress of a discount phase.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[near(serializers = [json])]
+pub struct DiscountPhaseState {
+    /// ID of the phase.
+    pub id: u16,
+    /// Discount percentage of the phase, `10000 = 100%`.
+    pub percentage: u16,
+    /// The start time of the phase.
+    pub start_time: u64,
+    /// The end time of the phase.
+    pub end_time: u64,
+    /// Whether the phase is active at the moment.
+    pub is_active: bool,
+    /// Total number of sale tokens sold with discount in the phase.
+    pub total_sale_tokens: U128,
+    /// Limit of sale tokens for the phase from the configuration, if any.
+    pub limit: Option<U128>,
+    /// Limit of sale tokens for the phase including the tokens which weren't sold in the phases
+    /// moving their unsold tokens to this phase.
+    pub effective_limit: Option<U128>,
+    /// Number of sale tokens which could still be sold in the phase, if the phase has a limit.
+    pub available_sale_tokens: Option<U128>,
+    /// Number of accounts in the whitelist of the phase. `None` if the phase is available for
+    /// any account.
+    pub whitelist_size: Option<u32>,
+}
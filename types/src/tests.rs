@@ -1,7 +1,13 @@
 use crate::config::{
-    DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig, Mechanics,
-    StakeholderProportion,
+    BundleRatio, BundledSaleToken, ConfigError, DepositDistributionProportion, DepositRecipient,
+    DepositToken, DistributionAccount, DistributionProportions, LaunchpadConfig,
+    LiquidityProportion, Mechanics, SaleToken, StakeholderProportion, VestingSchedule,
+    VestingScheme,
 };
+use crate::discount::{DepositDistribution, DiscountParams, DiscountPhase};
+use crate::duration::Duration;
+use crate::error::LaunchpadError;
+use crate::simulation::DepositDistributionView;
 
 #[test]
 fn successful_config_validation() {
@@ -40,14 +46,489 @@ fn config_validation_zero_sale_token_in_price() {
     config.validate().unwrap();
 }
 
+#[test]
+fn bundled_sale_token_config_validation() {
+    let mut config = config();
+    config.bundled_sale_tokens = vec![bundled_sale_token()];
+    config.validate().unwrap();
+
+    assert_eq!(config.bundled_sale_tokens[0].allocation(10).unwrap(), 25);
+}
+
+#[test]
+#[should_panic(
+    expected = "The total sale amount of the bundled sale token must be equal to the sale amount in the ratio"
+)]
+fn config_validation_wrong_bundled_sale_amount() {
+    let mut config = config();
+    let mut bundled = bundled_sale_token();
+    bundled.total_sale_amount = 2000.into(); // Should be 2500.
+    config.bundled_sale_tokens = vec![bundled];
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "The bundled sale token must differ from the sale token")]
+fn config_validation_bundled_sale_token_equal_to_sale_token() {
+    let mut config = config();
+    let mut bundled = bundled_sale_token();
+    bundled.token = config.sale_token.clone();
+    config.bundled_sale_tokens = vec![bundled];
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "All bundled sale tokens must be unique")]
+fn config_validation_duplicated_bundled_sale_tokens() {
+    let mut config = config();
+    config.bundled_sale_tokens = vec![bundled_sale_token(), bundled_sale_token()];
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "The number of bundled sale tokens must not exceed 3")]
+fn config_validation_too_many_bundled_sale_tokens() {
+    let mut config = config();
+    config.bundled_sale_tokens = (0..4)
+        .map(|i| BundledSaleToken {
+            token: SaleToken::Nep141(format!("utility-{i}.near").parse().unwrap()),
+            ..bundled_sale_token()
+        })
+        .collect();
+    config.validate().unwrap();
+}
+
+#[test]
+fn liquidity_config_validation() {
+    let mut config = config();
+    config.distribution_proportions.liquidity = Some(liquidity());
+    config.total_sale_amount = 3500.into();
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "The Total sale amount must be equal to the sale amount plus solver allocation and distribution allocations"
+)]
+fn config_validation_liquidity_allocation_out_of_total_sale_amount() {
+    let mut config = config();
+    config.distribution_proportions.liquidity = Some(liquidity());
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "The sum of liquidity, solver and fee percentages shouldn't be greater than 10000 (100%)"
+)]
+fn config_validation_liquidity_percentage() {
+    let mut config = config();
+    config.distribution_proportions.liquidity = Some(liquidity());
+    config.distribution_proportions.deposits = Some(deposits(6000, &[2000]));
+    config.total_sale_amount = 3500.into();
+    config.validate().unwrap();
+}
+
+#[test]
+fn config_validation_deposit_recipients() {
+    let mut config = config();
+    config.distribution_proportions.deposits = Some(deposits(5000, &[2500, 1500, 1000]));
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(
+    expected = "The sum of solver percentage and recipients percentages shouldn't be greater than 10000 (100%)"
+)]
+fn config_validation_deposit_recipients_percentage() {
+    let mut config = config();
+    config.distribution_proportions.deposits = Some(deposits(5000, &[2500, 1500, 1001]));
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "The number of deposit recipients must not exceed 5")]
+fn config_validation_too_many_deposit_recipients() {
+    let mut config = config();
+    config.distribution_proportions.deposits = Some(deposits(0, &[100; 6]));
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "All deposit recipients must have unique accounts")]
+fn config_validation_duplicate_deposit_recipients() {
+    let mut config = config();
+    let mut deposits = deposits(0, &[100, 200]);
+    deposits.recipients[1].account = deposits.recipients[0].account.clone();
+    config.distribution_proportions.deposits = Some(deposits);
+    config.validate().unwrap();
+}
+
+#[test]
+fn deposit_recipients_proportions() {
+    let deposits = deposits(5000, &[2500, 1500, 1000]);
+    let (solver_amount, recipient_amounts) = deposits.calculate_proportions(1_000).unwrap();
+
+    assert_eq!(solver_amount, 500);
+    assert_eq!(recipient_amounts, vec![250, 150, 100]);
+}
+
+#[test]
+fn config_validation_all_errors() {
+    let mut config = config();
+    config.total_sale_amount = 2500.into();
+    config.tge = Some(0);
+    config.distribution_proportions.stakeholder_proportions[1].vesting = Some(VestingSchedule {
+        cliff_period: Duration::from_secs(20),
+        vesting_period: Duration::from_secs(10),
+        instant_claim_percentage: Some(10_001),
+        vesting_scheme: VestingScheme::Immediate,
+    });
+
+    let errors = config.validate_all().unwrap_err();
+    let paths = errors.iter().map(ConfigError::path).collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        [
+            "total_sale_amount",
+            "distribution_proportions.stakeholder_proportions[1].vesting.cliff_period",
+            "distribution_proportions.stakeholder_proportions[1].vesting.instant_claim_percentage",
+            "tge",
+        ]
+    );
+    assert_eq!(
+        errors[3].to_string(),
+        "TGE must be after the sale end time [tge, end_date]"
+    );
+    assert!(errors.to_string().ends_with(
+        "; Vesting instant claim percentage cannot exceed 10000 (100%) [distribution_proportions.stakeholder_proportions[1].vesting.instant_claim_percentage]; TGE must be after the sale end time [tge, end_date]"
+    ));
+    assert_eq!(
+        config.validate(),
+        Err(
+            "The Total sale amount must be equal to the sale amount plus solver allocation and distribution allocations"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+#[should_panic(expected = "The sale start time must be before the sale end time")]
+fn config_validation_start_date_after_end_date() {
+    let mut config = config();
+    config.start_date = config.end_date;
+    config.validate().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Minimum deposit must be greater than zero")]
+fn config_validation_zero_min_deposit() {
+    let mut config = config();
+    config.min_deposit = 0.into();
+    config.validate().unwrap();
+}
+
+#[test]
+fn config_validation_unreachable_soft_cap() {
+    let mut config = config();
+    config.mechanics = Mechanics::FixedPrice {
+        deposit_token: 1.into(),
+        sale_token: 2.into(),
+    };
+    config.soft_cap = 500.into();
+    config.validate().unwrap();
+
+    config.soft_cap = 501.into();
+    assert_eq!(
+        config.validate(),
+        Err(
+            "The soft cap must be reachable by selling the sale amount at the fixed price"
+                .to_string()
+        )
+    );
+
+    config.allow_unreachable_soft_cap = true;
+    config.validate().unwrap();
+}
+
+#[test]
+fn config_validation_discount_phases() {
+    let mut config = config();
+    config.mechanics = Mechanics::FixedPrice {
+        deposit_token: 1.into(),
+        sale_token: 1.into(),
+    };
+    config.start_date = 10;
+    config.discounts = Some(DiscountParams {
+        phases: vec![
+            DiscountPhase {
+                id: 0,
+                start_time: 10,
+                end_time: 50,
+                min_limit_per_account: Some(20.into()),
+                max_limit_per_account: Some(10.into()),
+                remaining_go_to_phase_id: Some(2),
+                ..Default::default()
+            },
+            DiscountPhase {
+                id: 1,
+                start_time: 5,
+                end_time: 101,
+                phase_sale_limit: Some(1001.into()),
+                remaining_go_to_phase_id: Some(0),
+                ..Default::default()
+            },
+            DiscountPhase {
+                id: 2,
+                start_time: 50,
+                end_time: 50,
+                remaining_go_to_phase_id: Some(3),
+                ..Default::default()
+            },
+        ],
+        public_sale_start_time: Some(101),
+    });
+
+    let errors = config.validate_all().unwrap_err();
+    let paths = errors.iter().map(ConfigError::path).collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        [
+            "discounts.phases[0].min_limit_per_account",
+            "discounts.phases[1].start_time",
+            "discounts.phases[1].end_time",
+            "discounts.phases[1].phase_sale_limit",
+            "discounts.phases[1].remaining_go_to_phase_id",
+            "discounts.phases[2].start_time",
+            "discounts.phases[2].remaining_go_to_phase_id",
+            "discounts.public_sale_start_time",
+        ]
+    );
+    assert_eq!(
+        errors[4].to_string(),
+        "The unsold tokens must go to one of the following discount phases [discounts.phases[1].remaining_go_to_phase_id]"
+    );
+
+    // The public sale may start at the end of the sale.
+    let discounts = config.discounts.as_mut().unwrap();
+    discounts.phases[0].max_limit_per_account = Some(20.into());
+    discounts.phases[1] = DiscountPhase {
+        id: 1,
+        start_time: 50,
+        end_time: 100,
+        phase_sale_limit: Some(1000.into()),
+        ..Default::default()
+    };
+    discounts.phases[2].end_time = 100;
+    discounts.phases[2].remaining_go_to_phase_id = None;
+    discounts.public_sale_start_time = Some(100);
+    config.validate().unwrap();
+}
+
+#[test]
+fn vesting_unlocked_amount() {
+    let vesting = VestingSchedule {
+        cliff_period: Duration::from_secs(10),
+        vesting_period: Duration::from_secs(30),
+        instant_claim_percentage: Some(1000),
+        vesting_scheme: VestingScheme::AfterCliff,
+    };
+    let second = 1_000_000_000;
+    let unlocked = |seconds: u64| vesting.unlocked_amount(1_000, 0, seconds * second).unwrap();
+
+    assert_eq!(unlocked(0), 100);
+    assert_eq!(unlocked(9), 100);
+    assert_eq!(unlocked(10), 100);
+    assert_eq!(unlocked(20), 550);
+    assert_eq!(unlocked(30), 1_000);
+    assert_eq!(unlocked(40), 1_000);
+}
+
+#[test]
+fn vesting_next_unlock() {
+    let mut vesting = VestingSchedule {
+        cliff_period: Duration::from_nanos(10),
+        vesting_period: Duration::from_nanos(30),
+        instant_claim_percentage: Some(1000),
+        vesting_scheme: VestingScheme::AfterCliff,
+    };
+
+    assert_eq!(vesting.next_unlock(100, 50), Some(100));
+    assert_eq!(vesting.next_unlock(100, 100), Some(110));
+    assert_eq!(vesting.next_unlock(100, 115), Some(115));
+    assert_eq!(vesting.next_unlock(100, 130), None);
+
+    vesting.instant_claim_percentage = None;
+
+    assert_eq!(vesting.next_unlock(100, 50), Some(110));
+}
+
+#[test]
+fn vesting_unlock_timestamps() {
+    let vesting = VestingSchedule {
+        cliff_period: Duration::from_nanos(10),
+        vesting_period: Duration::from_nanos(30),
+        instant_claim_percentage: None,
+        vesting_scheme: VestingScheme::Immediate,
+    };
+
+    assert_eq!(
+        vesting.unlock_timestamps(100, 4),
+        vec![100, 110, 115, 120, 125, 130]
+    );
+    assert_eq!(vesting.unlock_timestamps(100, 0), vec![100, 110, 130]);
+    // A step can't be shorter than a nanosecond.
+    assert_eq!(vesting.unlock_timestamps(100, 100).len(), 22);
+}
+
+#[test]
+fn error_codes() {
+    assert_eq!(
+        LaunchpadError::NotOngoing.to_string(),
+        "ERR_2001: Launchpad is not ongoing"
+    );
+    assert_eq!(
+        LaunchpadError::WithdrawFailed.with_message("Withdraw failed: no funds"),
+        "ERR_3007: Withdraw failed: no funds"
+    );
+
+    for (index, error) in LaunchpadError::ALL.iter().enumerate() {
+        assert_eq!(LaunchpadError::from_code(error.code()), Some(*error));
+        assert!(
+            LaunchpadError::ALL[index + 1..]
+                .iter()
+                .all(|other| other.code() > error.code())
+        );
+    }
+
+    assert_eq!(LaunchpadError::from_code(0), None);
+}
+
+#[test]
+fn error_parsing() {
+    assert_eq!(
+        LaunchpadError::parse("Smart contract panicked: ERR_3002: Withdraw is not allowed"),
+        Some((
+            LaunchpadError::WithdrawNotAllowed,
+            "Withdraw is not allowed"
+        ))
+    );
+    assert_eq!(
+        LaunchpadError::parse("ERR_6009: Discount phase with id 5 not found"),
+        Some((
+            LaunchpadError::PhaseNotFound,
+            "Discount phase with id 5 not found"
+        ))
+    );
+    assert_eq!(LaunchpadError::parse("ERR_9999: Unknown error"), None);
+    assert_eq!(LaunchpadError::parse("ERR_: No code"), None);
+    assert_eq!(LaunchpadError::parse("Launchpad is not ongoing"), None);
+}
+
+#[test]
+fn deserialize_config_with_sale_token_account_id() {
+    let mut json = near_sdk::serde_json::to_value(config()).unwrap();
+    let object = json.as_object_mut().unwrap();
+    object.remove("sale_token");
+    object.insert("sale_token_account_id".to_string(), "sale.near".into());
+
+    let config: LaunchpadConfig = near_sdk::serde_json::from_value(json).unwrap();
+    assert_eq!(
+        config.sale_token,
+        SaleToken::Nep141("sale.near".parse().unwrap())
+    );
+}
+
+#[test]
+fn deserialize_deposits_with_fee_account() {
+    let deposits: DepositDistributionProportion =
+        near_sdk::serde_json::from_value(near_sdk::serde_json::json!({
+            "solver_percentage": 9000,
+            "fee_account": "fee.near",
+            "fee_percentage": 1000,
+        }))
+        .unwrap();
+
+    assert_eq!(
+        deposits,
+        DepositDistributionProportion {
+            solver_percentage: 9000,
+            recipients: vec![DepositRecipient {
+                account: DistributionAccount::new_intents("fee.near").unwrap(),
+                percentage: 1000,
+            }],
+        }
+    );
+}
+
+#[test]
+fn serialize_deposit_distribution_view() {
+    let view = DepositDistributionView::from(DepositDistribution::WithDiscount {
+        phase_weights: vec![(1, u128::MAX)],
+        public_sale_weight: 1000,
+        refund: 0,
+    });
+
+    assert_eq!(
+        near_sdk::serde_json::to_value(view).unwrap(),
+        near_sdk::serde_json::json!({
+            "WithDiscount": {
+                "phase_weights": [[1, u128::MAX.to_string()]],
+                "public_sale_weight": "1000",
+                "refund": "0",
+            }
+        })
+    );
+}
+
+fn deposits(solver_percentage: u16, percentages: &[u16]) -> DepositDistributionProportion {
+    DepositDistributionProportion {
+        solver_percentage,
+        recipients: (1..)
+            .zip(percentages)
+            .map(|(i, percentage)| DepositRecipient {
+                account: if i % 2 == 0 {
+                    DistributionAccount::new_near(format!("recipient-{i}.near")).unwrap()
+                } else {
+                    DistributionAccount::new_intents(format!("recipient-{i}.near")).unwrap()
+                },
+                percentage: *percentage,
+            })
+            .collect(),
+    }
+}
+
+fn liquidity() -> LiquidityProportion {
+    LiquidityProportion {
+        dex_account_id: "dex.near".parse().unwrap(),
+        pool_fee: 30,
+        deposit_percentage: 3000,
+        sale_allocation: 500.into(),
+        shares_receiver_id: None,
+    }
+}
+
+fn bundled_sale_token() -> BundledSaleToken {
+    BundledSaleToken {
+        token: SaleToken::Nep141("utility.near".parse().unwrap()),
+        total_sale_amount: 2500.into(),
+        ratio: BundleRatio {
+            sale_token: 2.into(),
+            bundled_token: 5.into(),
+        },
+        vesting_schedule: None,
+    }
+}
+
 fn config() -> LaunchpadConfig {
     LaunchpadConfig {
         deposit_token: DepositToken::Nep141("token.near".parse().unwrap()),
         min_deposit: 100.into(),
-        sale_token_account_id: "sale.near".parse().unwrap(),
+        sale_token: SaleToken::Nep141("sale.near".parse().unwrap()),
         intents_account_id: "intents.near".parse().unwrap(),
         start_date: 0,
-        end_date: 0,
+        end_date: 100,
         tge: None,
         soft_cap: 0.into(),
         mechanics: Mechanics::PriceDiscovery,
@@ -70,7 +551,10 @@ fn config() -> LaunchpadConfig {
                 },
             ],
             deposits: None,
+            liquidity: None,
         },
         discounts: None,
+        bundled_sale_tokens: vec![],
+        allow_unreachable_soft_cap: false,
     }
 }
@@ -27,6 +27,20 @@ where
     true
 }
 
+/// Returns the indices of the elements which are equal to one of the previous elements.
+pub fn duplicates<I>(iter: I) -> Vec<usize>
+where
+    I: IntoIterator,
+    I::Item: Ord,
+{
+    let mut set = BTreeSet::new();
+
+    iter.into_iter()
+        .enumerate()
+        .filter_map(|(index, item)| (!set.insert(item)).then_some(index))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{is_all_unique, to_u128};